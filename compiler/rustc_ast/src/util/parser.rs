@@ -273,6 +273,7 @@ pub enum ExprPrecedence {
 
     Array,
     Repeat,
+    ConstBlock,
     Tup,
     Lit,
     Path,
@@ -337,6 +338,7 @@ impl ExprPrecedence {
             // Never need parens
             ExprPrecedence::Array |
             ExprPrecedence::Repeat |
+            ExprPrecedence::ConstBlock |
             ExprPrecedence::Tup |
             ExprPrecedence::Lit |
             ExprPrecedence::Path |
@@ -35,7 +35,9 @@
 #![feature(or_patterns)]
 #![recursion_limit = "256"]
 
+use rustc_ast::mut_visit::{self, MutVisitor};
 use rustc_ast::node_id::NodeMap;
+use rustc_ast::ptr::P;
 use rustc_ast::token::{self, DelimToken, Nonterminal, Token};
 use rustc_ast::tokenstream::{DelimSpan, TokenStream, TokenTree};
 use rustc_ast::visit::{self, AssocCtxt, Visitor};
@@ -417,6 +419,18 @@ impl Visitor<'_> for ImplTraitTypeIdVisitor<'_> {
     }
 }
 
+/// Turns `mut` by-value bindings into immutable ones, see `lower_let_else`.
+struct StripBindingMut;
+
+impl MutVisitor for StripBindingMut {
+    fn visit_pat(&mut self, pat: &mut P<Pat>) {
+        if let PatKind::Ident(BindingMode::ByValue(ref mut mutbl), ..) = pat.kind {
+            *mutbl = Mutability::Not;
+        }
+        mut_visit::noop_visit_pat(pat, self);
+    }
+}
+
 impl<'a, 'hir> LoweringContext<'a, 'hir> {
     fn lower_crate(mut self, c: &Crate) -> hir::Crate<'hir> {
         /// Full-crate AST visitor that inserts into a fresh
@@ -2301,28 +2315,139 @@ impl<'a, 'hir> LoweringContext<'a, 'hir> {
     }
 
     fn lower_block_noalloc(&mut self, b: &Block, targeted_by_break: bool) -> hir::Block<'hir> {
+        let (stmts, expr) = self.lower_stmts(&b.stmts);
+        hir::Block {
+            hir_id: self.lower_node_id(b.id),
+            stmts,
+            expr,
+            rules: self.lower_block_check_mode(&b.rules),
+            span: b.span,
+            targeted_by_break,
+        }
+    }
+
+    /// Lowers the statements of a block, returning the HIR statements and the tail expression.
+    fn lower_stmts(
+        &mut self,
+        mut ast_stmts: &[Stmt],
+    ) -> (&'hir [hir::Stmt<'hir>], Option<&'hir hir::Expr<'hir>>) {
         let mut stmts = vec![];
         let mut expr: Option<&'hir _> = None;
 
-        for (index, stmt) in b.stmts.iter().enumerate() {
-            if index == b.stmts.len() - 1 {
-                if let StmtKind::Expr(ref e) = stmt.kind {
+        while let [stmt, tail @ ..] = ast_stmts {
+            match stmt.kind {
+                StmtKind::Local(ref local) if local.els.is_some() => {
+                    stmts.push(self.lower_let_else(stmt, local));
+                }
+                StmtKind::Expr(ref e) if tail.is_empty() => {
                     expr = Some(self.lower_expr(e));
-                } else {
-                    stmts.extend(self.lower_stmt(stmt));
                 }
-            } else {
-                stmts.extend(self.lower_stmt(stmt));
+                _ => stmts.extend(self.lower_stmt(stmt)),
             }
+            ast_stmts = tail;
         }
 
-        hir::Block {
-            hir_id: self.lower_node_id(b.id),
-            stmts: self.arena.alloc_from_iter(stmts),
-            expr,
-            rules: self.lower_block_check_mode(&b.rules),
-            span: b.span,
-            targeted_by_break,
+        (self.arena.alloc_from_iter(stmts), expr)
+    }
+
+    /// Desugars `let <pat>: <ty> = <init> else { <els> };` into:
+    ///
+    /// ```rust
+    /// let (<b1>, .., <bn>) = match <init>: <ty> {
+    ///     <pat> => (<b1>, .., <bn>),
+    ///     _ => { <els> }
+    /// };
+    /// ```
+    ///
+    /// where `<b1>, .., <bn>` are the bindings of `<pat>`. Keeping the `match` inside the
+    /// initializer makes the temporaries of `<init>` end with the statement, as they do for a
+    /// plain `let`. The `else` block is required to diverge during type checking.
+    fn lower_let_else(&mut self, stmt: &Stmt, local: &Local) -> hir::Stmt<'hir> {
+        let init = local.init.as_ref().expect("`let...else` without an initializer");
+        let els = local.els.as_ref().expect("`let...else` without an `else` block");
+
+        // Collect the bindings of the pattern in order, visiting each one only once even if
+        // it occurs in several alternatives of an or-pattern.
+        let mut bindings = vec![];
+        local.pat.walk(&mut |p| {
+            if let PatKind::Ident(binding_mode, ident, _) = p.kind {
+                let canonical_id =
+                    match self.resolver.get_partial_res(p.id).map(|d| d.base_res()) {
+                        None => p.id,
+                        Some(Res::Local(id)) => id,
+                        Some(_) => return true,
+                    };
+                if !bindings.iter().any(|&(id, ..)| id == canonical_id) {
+                    bindings.push((canonical_id, binding_mode, ident));
+                }
+            }
+            true
+        });
+
+        let mut scrutinee = self.lower_expr(init);
+        if let Some(ref ty) = local.ty {
+            let ty = self.lower_ty(ty, ImplTraitContext::Disallowed(ImplTraitPosition::Binding));
+            scrutinee = self.arena.alloc(self.expr(
+                init.span,
+                hir::ExprKind::Type(scrutinee, ty),
+                AttrVec::new(),
+            ));
+        }
+
+        // `<pat> => (<b1>, .., <bn>)`
+        let then_arm = {
+            // The bindings of the arm are only moved into the tuple, so any `mut` belongs on
+            // the bindings of the `let` instead.
+            let mut pat = local.pat.clone();
+            StripBindingMut.visit_pat(&mut pat);
+            let pat = self.lower_pat(&pat);
+            let fields = self.arena.alloc_from_iter(bindings.iter().map(|&(id, _, ident)| {
+                let hir_id = self.lower_node_id(id);
+                self.expr_ident_mut(ident.span, ident, hir_id)
+            }));
+            let tuple = hir::ExprKind::Tup(fields);
+            let expr = self.arena.alloc(self.expr(local.pat.span, tuple, AttrVec::new()));
+            self.arm(pat, expr)
+        };
+
+        // `_ => { <els> }`
+        let else_arm = {
+            let pat = self.pat_wild(els.span);
+            let expr = self.arena.alloc(self.lower_block_expr(els));
+            self.arm(pat, expr)
+        };
+
+        let arms = arena_vec![self; then_arm, else_arm];
+        let source = hir::MatchSource::LetElseDesugar;
+        let init = self.arena.alloc(self.expr_match(local.span, scrutinee, arms, source));
+
+        // `let (<b1>, .., <bn>) = ...`
+        let pats = self.arena.alloc_from_iter(bindings.into_iter().map(|(id, mode, ident)| {
+            let mode = match mode {
+                BindingMode::ByValue(Mutability::Mut) => hir::BindingAnnotation::Mutable,
+                _ => hir::BindingAnnotation::Unannotated,
+            };
+            let (pat, hir_id) = self.pat_ident_binding_mode(ident.span, ident, mode);
+            // Uses of the binding after the statement resolve to the pattern's `NodeId`,
+            // so point it at the binding of the `let` rather than the one in the arm.
+            self.node_id_to_hir_id[id] = Some(hir_id);
+            pat
+        }));
+        let pat = self.pat(local.pat.span, hir::PatKind::Tuple(pats, None));
+
+        let local = hir::Local {
+            hir_id: self.lower_node_id(local.id),
+            ty: None,
+            pat,
+            init: Some(init),
+            span: local.span,
+            attrs: local.attrs.clone(),
+            source: hir::LocalSource::Normal,
+        };
+        hir::Stmt {
+            hir_id: self.lower_node_id(stmt.id),
+            kind: hir::StmtKind::Local(self.arena.alloc(local)),
+            span: stmt.span,
         }
     }
 
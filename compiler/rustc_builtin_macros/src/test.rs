@@ -8,7 +8,7 @@ use rustc_ast_pretty::pprust;
 use rustc_expand::base::*;
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Ident, Symbol};
-use rustc_span::Span;
+use rustc_span::{Pos, Span};
 
 use std::iter;
 
@@ -107,6 +107,9 @@ pub fn expand_test_or_bench(
         return vec![Annotatable::Item(item)];
     }
 
+    // The location reported for the test, the same one `file!()`, `line!()` and `column!()`
+    // would give for the test function's name.
+    let loc = cx.source_map().lookup_char_pos(item.ident.span.lo());
     let (sp, attr_sp) = (cx.with_def_site_ctxt(item.span), cx.with_def_site_ctxt(attr_sp));
 
     let test_id = Ident::new(sym::test, attr_sp);
@@ -240,11 +243,66 @@ pub fn expand_test_or_bench(
                                         "ignore",
                                         cx.expr_bool(sp, should_ignore(&cx.sess, &item)),
                                     ),
+                                    // ignore_message: Some("...") | None
+                                    field(
+                                        "ignore_message",
+                                        match ignore_message(&cx.sess, &item) {
+                                            Some(msg) => cx.expr_some(sp, cx.expr_str(sp, msg)),
+                                            None => cx.expr_none(sp),
+                                        },
+                                    ),
+                                    // location: Some(test::TestLocation { ... })
+                                    field(
+                                        "location",
+                                        cx.expr_some(
+                                            sp,
+                                            cx.expr_struct(
+                                                sp,
+                                                test_path("TestLocation"),
+                                                vec![
+                                                    // file: "path/to/file.rs"
+                                                    field(
+                                                        "file",
+                                                        cx.expr_str(
+                                                            sp,
+                                                            Symbol::intern(
+                                                                &loc.file.name.to_string(),
+                                                            ),
+                                                        ),
+                                                    ),
+                                                    // line: 42
+                                                    field("line", cx.expr_usize(sp, loc.line)),
+                                                    // col: 4
+                                                    field(
+                                                        "col",
+                                                        cx.expr_usize(sp, loc.col.to_usize() + 1),
+                                                    ),
+                                                ],
+                                            ),
+                                        ),
+                                    ),
                                     // allow_fail: true | false
                                     field(
                                         "allow_fail",
                                         cx.expr_bool(sp, should_fail(&cx.sess, &item)),
                                     ),
+                                    // timeout: Some(secs) | None
+                                    field(
+                                        "timeout",
+                                        match test_timeout(cx, &item) {
+                                            Some(secs) => cx.expr_some(
+                                                sp,
+                                                cx.expr_lit(
+                                                    sp,
+                                                    ast::LitKind::Int(
+                                                        secs.into(),
+                                                        ast::LitIntType::Unsuffixed,
+                                                    ),
+                                                ),
+                                            ),
+                                            None => cx.expr_none(sp),
+                                        },
+                                    ),
                                     // should_panic: ...
                                     field(
                                         "should_panic",
@@ -332,10 +390,29 @@ fn should_ignore(sess: &Session, i: &ast::Item) -> bool {
     sess.contains_name(&i.attrs, sym::ignore)
 }
 
+fn ignore_message(sess: &Session, i: &ast::Item) -> Option<Symbol> {
+    sess.find_by_name(&i.attrs, sym::ignore).and_then(|attr| attr.value_str())
+}
+
 fn should_fail(sess: &Session, i: &ast::Item) -> bool {
     sess.contains_name(&i.attrs, sym::allow_fail)
 }
 
+fn test_timeout(cx: &ExtCtxt<'_>, i: &ast::Item) -> Option<u64> {
+    let attr = cx.sess.find_by_name(&i.attrs, sym::test_timeout)?;
+    let secs = attr.value_str()?;
+    match secs.as_str().parse::<u64>() {
+        Ok(secs) if secs > 0 => Some(secs),
+        _ => {
+            let sd = &cx.sess.parse_sess.span_diagnostic;
+            sd.struct_span_err(attr.span, "invalid `test_timeout` attribute")
+                .help("the timeout must be a positive number of seconds, e.g. `\"30\"`")
+                .emit();
+            None
+        }
+    }
+}
+
 fn should_panic(cx: &ExtCtxt<'_>, i: &ast::Item) -> ShouldPanic {
     match cx.sess.find_by_name(&i.attrs, sym::should_panic) {
         Some(attr) => {
@@ -23,11 +23,27 @@ struct Test {
     ident: Ident,
 }
 
+/// A function marked with `#[test_setup]` or `#[test_teardown]`.
+struct Fixture {
+    span: Span,
+    ident: Ident,
+    kind: FixtureKind,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum FixtureKind {
+    Setup,
+    Teardown,
+    SetupEach,
+    TeardownEach,
+}
+
 struct TestCtxt<'a> {
     ext_cx: ExtCtxt<'a>,
     panic_strategy: PanicStrategy,
     def_site: Span,
     test_cases: Vec<Test>,
+    fixtures: Vec<Fixture>,
     reexport_test_harness_main: Option<Symbol>,
     test_runner: Option<ast::Path>,
 }
@@ -82,6 +98,7 @@ pub fn inject(sess: &Session, resolver: &mut dyn ResolverExpand, krate: &mut ast
 struct TestHarnessGenerator<'a> {
     cx: TestCtxt<'a>,
     tests: Vec<Test>,
+    fixtures: Vec<Fixture>,
 }
 
 impl<'a> MutVisitor for TestHarnessGenerator<'a> {
@@ -99,16 +116,23 @@ impl<'a> MutVisitor for TestHarnessGenerator<'a> {
 
             let test = Test { span: item.span, ident: item.ident };
             self.tests.push(test);
+        } else if let Some(kind) = fixture_kind(&self.cx.ext_cx.sess, &item) {
+            debug!("this is a test fixture");
+
+            let fixture = Fixture { span: item.span, ident: item.ident, kind };
+            self.fixtures.push(fixture);
         }
 
         // We don't want to recurse into anything other than mods, since
         // mods or tests inside of functions will break things
         if let ast::ItemKind::Mod(mut module) = item.kind {
             let tests = mem::take(&mut self.tests);
+            let fixtures = mem::take(&mut self.fixtures);
             noop_visit_mod(&mut module, self);
             let mut tests = mem::replace(&mut self.tests, tests);
+            let mut fixtures = mem::replace(&mut self.fixtures, fixtures);
 
-            if !tests.is_empty() {
+            if !tests.is_empty() || !fixtures.is_empty() {
                 let parent =
                     if item.id == ast::DUMMY_NODE_ID { ast::CRATE_NODE_ID } else { item.id };
                 // Create an identifier that will hygienically resolve the test
@@ -124,7 +148,12 @@ impl<'a> MutVisitor for TestHarnessGenerator<'a> {
                     // `apply_mark` directly.
                     test.ident.span = test.ident.span.apply_mark(expn_id, Transparency::Opaque);
                 }
+                for fixture in &mut fixtures {
+                    fixture.ident.span =
+                        fixture.ident.span.apply_mark(expn_id, Transparency::Opaque);
+                }
                 self.cx.test_cases.extend(tests);
+                self.cx.fixtures.extend(fixtures);
             }
             item.kind = ast::ItemKind::Mod(module);
         }
@@ -239,11 +268,12 @@ fn generate_test_harness(
         panic_strategy,
         def_site,
         test_cases: Vec::new(),
+        fixtures: Vec::new(),
         reexport_test_harness_main,
         test_runner,
     };
 
-    TestHarnessGenerator { cx, tests: Vec::new() }.visit_crate(krate);
+    TestHarnessGenerator { cx, tests: Vec::new(), fixtures: Vec::new() }.visit_crate(krate);
 }
 
 /// Creates a function item for use as the main function of a test build.
@@ -263,8 +293,23 @@ fn generate_test_harness(
 /// }
 /// ```
 ///
+/// If the crate has test fixtures, they are passed to `test::test_main_static_with_fixtures`
+/// instead:
+///
+/// ```
+/// test::test_main_static_with_fixtures(
+///     &[&test_const1, &test_const2],
+///     &test::TestFixtures {
+///         setup: &[setup_fn],
+///         teardown: &[],
+///         setup_each: &[],
+///         teardown_each: &[teardown_each_fn],
+///     },
+/// );
+/// ```
+///
 /// Most of the Ident have the usual def-site hygiene for the AST pass. The
-/// exception is the `test_const`s. These have a syntax context that has two
+/// exception is the `test_const`s and the fixtures. These have a syntax context that has two
 /// opaque marks: one from the expansion of `test` or `test_case`, and one
 /// generated  in `TestHarnessGenerator::flat_map_item`. When resolving this
 /// identifier after failing to find a matching identifier in the root module
@@ -281,9 +326,23 @@ fn mk_main(cx: &mut TestCtxt<'_>) -> P<ast::Item> {
     let ecx = &cx.ext_cx;
     let test_id = Ident::new(sym::test, sp);
 
-    let runner_name = match cx.panic_strategy {
-        PanicStrategy::Unwind => "test_main_static",
-        PanicStrategy::Abort => "test_main_static_abort",
+    if cx.test_runner.is_some() {
+        if let Some(fixture) = cx.fixtures.first() {
+            ecx.sess
+                .parse_sess
+                .span_diagnostic
+                .struct_span_err(fixture.span, "test fixtures require the default test runner")
+                .note("`#![test_runner]` replaces the runner that calls the fixtures")
+                .emit();
+        }
+    }
+    let with_fixtures = cx.test_runner.is_none() && !cx.fixtures.is_empty();
+
+    let runner_name = match (cx.panic_strategy, with_fixtures) {
+        (PanicStrategy::Unwind, false) => "test_main_static",
+        (PanicStrategy::Abort, false) => "test_main_static_abort",
+        (PanicStrategy::Unwind, true) => "test_main_static_with_fixtures",
+        (PanicStrategy::Abort, true) => "test_main_static_abort_with_fixtures",
     };
 
     // test::test_main_static(...)
@@ -295,7 +354,11 @@ fn mk_main(cx: &mut TestCtxt<'_>) -> P<ast::Item> {
     test_runner.span = sp;
 
     let test_main_path_expr = ecx.expr_path(test_runner);
-    let call_test_main = ecx.expr_call(sp, test_main_path_expr, vec![mk_tests_slice(cx, sp)]);
+    let mut test_main_args = vec![mk_tests_slice(cx, sp)];
+    if with_fixtures {
+        test_main_args.push(mk_fixtures(cx, sp));
+    }
+    let call_test_main = ecx.expr_call(sp, test_main_path_expr, test_main_args);
     let call_test_main = ecx.stmt_expr(call_test_main);
 
     // extern crate test
@@ -359,10 +422,93 @@ fn mk_tests_slice(cx: &TestCtxt<'_>, sp: Span) -> P<ast::Expr> {
     )
 }
 
+/// Creates a reference to the fixtures of every kind like so:
+/// &test::TestFixtures { setup: &[setup1, setup2], teardown: &[], ... }
+fn mk_fixtures(cx: &TestCtxt<'_>, sp: Span) -> P<ast::Expr> {
+    debug!("building test fixtures from {} fixtures", cx.fixtures.len());
+    let ecx = &cx.ext_cx;
+    let test_id = Ident::new(sym::test, sp);
+
+    let field = |name, kind| {
+        let fixtures = cx
+            .fixtures
+            .iter()
+            .filter(|fixture| fixture.kind == kind)
+            .map(|fixture| ecx.expr_path(ecx.path(fixture.span, vec![fixture.ident])))
+            .collect();
+        ecx.field_imm(sp, Ident::from_str_and_span(name, sp), ecx.expr_vec_slice(sp, fixtures))
+    };
+
+    let path = ecx.path(sp, vec![test_id, Ident::from_str_and_span("TestFixtures", sp)]);
+    ecx.expr_addr_of(
+        sp,
+        ecx.expr_struct(
+            sp,
+            path,
+            vec![
+                field("setup", FixtureKind::Setup),
+                field("teardown", FixtureKind::Teardown),
+                field("setup_each", FixtureKind::SetupEach),
+                field("teardown_each", FixtureKind::TeardownEach),
+            ],
+        ),
+    )
+}
+
 fn is_test_case(sess: &Session, i: &ast::Item) -> bool {
     sess.contains_name(&i.attrs, sym::rustc_test_marker)
 }
 
+/// Returns the kind of the fixture if the item is marked with `#[test_setup]` or
+/// `#[test_teardown]`, and reports misuses of these attributes.
+fn fixture_kind(sess: &Session, i: &ast::Item) -> Option<FixtureKind> {
+    let sd = &sess.parse_sess.span_diagnostic;
+    let (attr, kind, each_kind) = match (
+        sess.find_by_name(&i.attrs, sym::test_setup),
+        sess.find_by_name(&i.attrs, sym::test_teardown),
+    ) {
+        (None, None) => return None,
+        (Some(attr), None) => (attr, FixtureKind::Setup, FixtureKind::SetupEach),
+        (None, Some(attr)) => (attr, FixtureKind::Teardown, FixtureKind::TeardownEach),
+        (Some(_), Some(attr)) => {
+            sd.span_err(attr.span, "a function can't be both a setup and a teardown fixture");
+            return None;
+        }
+    };
+
+    let kind = match attr.meta_item_list().as_deref() {
+        None => kind,
+        Some([each]) if each.has_name(sym::each) && each.is_word() => each_kind,
+        Some(_) => {
+            sd.struct_span_err(attr.span, "malformed test fixture attribute")
+                .help(
+                    "use `#[test_setup]` to run a function once for all tests, \
+                     or `#[test_setup(each)]` to run it for every test",
+                )
+                .emit();
+            return None;
+        }
+    };
+
+    let is_plain_fn = match &i.kind {
+        ast::ItemKind::Fn(_, sig, generics, _) => {
+            sig.decl.inputs.is_empty()
+                && matches!(sig.decl.output, ast::FnRetTy::Default(..))
+                && generics.params.is_empty()
+                && !sig.header.asyncness.is_async()
+        }
+        _ => false,
+    };
+    if !is_plain_fn {
+        sd.struct_span_err(i.span, "test fixtures must be functions of type `fn()`")
+            .span_label(attr.span, "marked as a test fixture here")
+            .emit();
+        return None;
+    }
+
+    Some(kind)
+}
+
 fn get_test_runner(
     sess: &Session,
     sd: &rustc_errors::Handler,
@@ -778,7 +778,17 @@ impl SyntaxExtension {
             }
         }
 
-        let is_builtin = sess.contains_name(attrs, sym::rustc_builtin_macro);
+        let mut helper_attrs = helper_attrs;
+        let builtin_attr = sess.find_by_name(attrs, sym::rustc_builtin_macro);
+        let is_builtin = builtin_attr.is_some();
+        // Built-in derives may declare helper attributes the same way `#[proc_macro_derive]`
+        // does, e.g. `#[rustc_builtin_macro(attributes(default))]`.
+        if let Some(list) = builtin_attr.and_then(|attr| attr.meta_item_list()) {
+            for nested in list.iter().filter(|nested| nested.has_name(sym::attributes)) {
+                let names = nested.meta_item_list().unwrap_or_default();
+                helper_attrs.extend(names.iter().filter_map(|name| name.ident()).map(|i| i.name));
+            }
+        }
         let (stability, const_stability) = attr::find_stability(&sess, attrs, span);
         if const_stability.is_some() {
             sess.parse_sess
@@ -598,6 +598,27 @@ declare_features! (
     /// Allows `#[instruction_set(_)]` attribute
     (active, isa_attribute, "1.48.0", Some(74727), None),
 
+    /// Allows `let PAT = EXPR else { .. };` statements.
+    (active, let_else, "1.49.0", Some(87335), None),
+
+    /// Allows destructuring assignments like `(a, b) = (b, a);`.
+    (active, destructuring_assignment, "1.49.0", Some(71126), None),
+
+    /// Allows closures to capture disjoint fields (RFC 2229) instead of entire variables.
+    (active, capture_disjoint_fields, "1.49.0", Some(53488), None),
+
+    /// Allows `const { ... }` blocks in expression and pattern position.
+    (active, inline_const, "1.49.0", Some(76001), None),
+
+    /// Allows `#[derive(Default)]` and `#[default]` on enums.
+    (active, derive_default_enum, "1.49.0", Some(86985), None),
+
+    /// Allows `#[test_timeout = "SECS"]` to kill a test that runs for too long.
+    (active, test_timeout, "1.49.0", Some(50297), None),
+
+    /// Allows `#[test_setup]` and `#[test_teardown]` to run code around the tests of a crate.
+    (active, test_fixtures, "1.49.0", Some(50297), None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -618,6 +639,8 @@ pub const INCOMPLETE_FEATURES: &[Symbol] = &[
     sym::const_trait_bound_opt_out,
     sym::lazy_normalization_consts,
     sym::specialization,
+    sym::capture_disjoint_fields,
+    sym::inline_const,
 ];
 
 /// Some features are not allowed to be used together at the same time, if
@@ -320,6 +320,16 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
 
     // Testing:
     gated!(allow_fail, Normal, template!(Word), experimental!(allow_fail)),
+    gated!(test_timeout, Normal, template!(NameValueStr: "seconds"), experimental!(test_timeout)),
+    // Only read by the test harness generator, so they'd be unused in non-test builds.
+    gated!(
+        test_setup, AssumedUsed, template!(Word, List: "each"), test_fixtures,
+        experimental!(test_setup),
+    ),
+    gated!(
+        test_teardown, AssumedUsed, template!(Word, List: "each"), test_fixtures,
+        experimental!(test_teardown),
+    ),
     gated!(
         test_runner, CrateLevel, template!(List: "path"), custom_test_frameworks,
         "custom test frameworks are an unstable feature",
@@ -440,7 +450,10 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
     // Internal attributes, Macro related:
     // ==========================================================================
 
-    rustc_attr!(rustc_builtin_macro, AssumedUsed, template!(Word), IMPL_DETAIL),
+    rustc_attr!(
+        rustc_builtin_macro, AssumedUsed,
+        template!(Word, List: "attributes(name1, name2, ...)"), IMPL_DETAIL
+    ),
     rustc_attr!(rustc_proc_macro_decls, Normal, template!(Word), INTERNAL_UNSTABLE),
     rustc_attr!(
         rustc_macro_transparency, AssumedUsed,
@@ -553,6 +566,7 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
         template!(Word, List: "delay_span_bug_from_inside_query")
     ),
     rustc_attr!(TEST, rustc_dump_user_substs, AssumedUsed, template!(Word)),
+    rustc_attr!(TEST, rustc_capture_analysis, AssumedUsed, template!(Word)),
     rustc_attr!(TEST, rustc_if_this_changed, AssumedUsed, template!(Word, List: "DepNode")),
     rustc_attr!(TEST, rustc_then_this_would_need, AssumedUsed, template!(List: "DepNode")),
     rustc_attr!(
@@ -1362,6 +1362,7 @@ impl Expr<'_> {
         match self.kind {
             ExprKind::Box(_) => ExprPrecedence::Box,
             ExprKind::Array(_) => ExprPrecedence::Array,
+            ExprKind::ConstBlock(_) => ExprPrecedence::ConstBlock,
             ExprKind::Call(..) => ExprPrecedence::Call,
             ExprKind::MethodCall(..) => ExprPrecedence::MethodCall,
             ExprKind::Tup(_) => ExprPrecedence::Tup,
@@ -1437,6 +1438,7 @@ impl Expr<'_> {
             | ExprKind::Block(..)
             | ExprKind::Repeat(..)
             | ExprKind::Array(..)
+            | ExprKind::ConstBlock(..)
             | ExprKind::Break(..)
             | ExprKind::Continue(..)
             | ExprKind::Ret(..)
@@ -1503,6 +1505,8 @@ pub enum ExprKind<'hir> {
     Box(&'hir Expr<'hir>),
     /// An array (e.g., `[a, b, c, d]`).
     Array(&'hir [Expr<'hir>]),
+    /// Allow anonymous constants from an inline `const` block
+    ConstBlock(AnonConst),
     /// A function call.
     ///
     /// The first field resolves to the function itself (usually an `ExprKind::Path`),
@@ -1687,6 +1691,10 @@ pub enum LocalSource {
     AsyncFn,
     /// A desugared `<expr>.await`.
     AwaitDesugar,
+    /// A desugared destructuring assignment `(a, b) = rhs`, which becomes
+    /// `{ let (lhs1, lhs2) = rhs; a = lhs1; b = lhs2; }`.
+    /// The span is that of the `=` sign.
+    AssignDesugar(Span),
 }
 
 /// Hints at the original code for a `match _ { .. }`.
@@ -1704,6 +1712,9 @@ pub enum MatchSource {
     /// A `while let _ = _ { .. }` (which was desugared to a
     /// `loop { match _ { .. } }`).
     WhileLetDesugar,
+    /// A `let _ = _ else { .. };` statement (which was desugared to a
+    /// `let (..) = match _ { _ => (..), _ => { .. } };`).
+    LetElseDesugar,
     /// A desugared `for _ in _ { .. }` loop.
     ForLoopDesugar,
     /// A desugared `?` operator.
@@ -1719,6 +1730,7 @@ impl MatchSource {
             Normal => "match",
             IfDesugar { .. } | IfLetDesugar { .. } => "if",
             WhileDesugar | WhileLetDesugar => "while",
+            LetElseDesugar => "let...else",
             ForLoopDesugar => "for",
             TryDesugar => "?",
             AwaitDesugar => ".await",
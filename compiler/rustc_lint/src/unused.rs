@@ -664,7 +664,9 @@ trait UnusedDelimLint {
     fn check_stmt(&mut self, cx: &EarlyContext<'_>, s: &ast::Stmt) {
         match s.kind {
             StmtKind::Local(ref local) if Self::LINT_EXPR_IN_PATTERN_MATCHING_CTX => {
-                if let Some(ref value) = local.init {
+                // Delimiters around the initializer of a `let...else` may be needed to
+                // disambiguate it, so only check plain `let` statements.
+                if let (Some(value), None) = (&local.init, &local.els) {
                     self.check_unused_delims_expr(
                         cx,
                         &value,
@@ -2244,11 +2244,11 @@ impl<'tcx> Debug for Rvalue<'tcx> {
                             };
                             let mut struct_fmt = fmt.debug_struct(&name);
 
-                            if let Some(upvars) = tcx.upvars_mentioned(def_id) {
-                                for (&var_id, place) in upvars.keys().zip(places) {
-                                    let var_name = tcx.hir().name(var_id);
-                                    struct_fmt.field(&var_name.as_str(), place);
-                                }
+                            let typeck_results = tcx.typeck(def_id);
+                            let captures =
+                                typeck_results.closure_min_captures_flattened(def_id.to_def_id());
+                            for (captured_place, place) in captures.zip(places) {
+                                struct_fmt.field(&captured_place.to_string(tcx), place);
                             }
 
                             struct_fmt.finish()
@@ -2263,11 +2263,11 @@ impl<'tcx> Debug for Rvalue<'tcx> {
                             let name = format!("[generator@{:?}]", tcx.hir().span(hir_id));
                             let mut struct_fmt = fmt.debug_struct(&name);
 
-                            if let Some(upvars) = tcx.upvars_mentioned(def_id) {
-                                for (&var_id, place) in upvars.keys().zip(places) {
-                                    let var_name = tcx.hir().name(var_id);
-                                    struct_fmt.field(&var_name.as_str(), place);
-                                }
+                            let typeck_results = tcx.typeck(def_id);
+                            let captures =
+                                typeck_results.closure_min_captures_flattened(def_id.to_def_id());
+                            for (captured_place, place) in captures.zip(places) {
+                                struct_fmt.field(&captured_place.to_string(tcx), place);
                             }
 
                             struct_fmt.finish()
@@ -820,12 +820,12 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         let tcx_hir = tcx.hir();
         let hir_typeck_results = self.hir.typeck_results();
 
-        // In analyze_closure() in upvar.rs we gathered a list of upvars used by a
-        // indexed closure and we stored in a map called closure_captures in TypeckResults
-        // with the closure's DefId. Here, we run through that vec of UpvarIds for
+        // In analyze_closure() in upvar.rs we gathered a list of places captured by a
+        // closure and we stored them in a map called closure_min_captures in TypeckResults
+        // with the closure's DefId. Here, we run through those captured places for
         // the given closure and use the necessary information to create upvar
         // debuginfo and to fill `self.upvar_mutbls`.
-        if let Some(upvars) = hir_typeck_results.closure_captures.get(&fn_def_id) {
+        if hir_typeck_results.closure_min_captures.get(&fn_def_id).is_some() {
             let closure_env_arg = Local::new(1);
             let mut closure_env_projs = vec![];
             let mut closure_ty = self.local_decls[closure_env_arg].ty;
@@ -839,11 +839,13 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                 _ => span_bug!(self.fn_span, "upvars with non-closure env ty {:?}", closure_ty),
             };
             let upvar_tys = upvar_substs.upvar_tys();
-            let upvars_with_tys = upvars.iter().zip(upvar_tys);
-            self.upvar_mutbls = upvars_with_tys
+            let captures_with_tys =
+                hir_typeck_results.closure_min_captures_flattened(fn_def_id).zip(upvar_tys);
+            self.upvar_mutbls = captures_with_tys
                 .enumerate()
-                .map(|(i, ((&var_id, &upvar_id), ty))| {
-                    let capture = hir_typeck_results.upvar_capture(upvar_id);
+                .map(|(i, (captured_place, ty))| {
+                    let capture = captured_place.info.capture_kind;
+                    let var_id = captured_place.get_root_variable();
 
                     let mut mutability = Mutability::Not;
                     let mut name = kw::Invalid;
@@ -862,23 +864,27 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                         }
                     }
 
-                    let mut projs = closure_env_projs.clone();
-                    projs.push(ProjectionElem::Field(Field::new(i), ty));
-                    match capture {
-                        ty::UpvarCapture::ByValue(_) => {}
-                        ty::UpvarCapture::ByRef(..) => {
-                            projs.push(ProjectionElem::Deref);
-                        }
-                    };
+                    // Only places that capture a variable entirely correspond to
+                    // something the user can name in a debugger.
+                    if captured_place.place.projections.is_empty() {
+                        let mut projs = closure_env_projs.clone();
+                        projs.push(ProjectionElem::Field(Field::new(i), ty));
+                        match capture {
+                            ty::UpvarCapture::ByValue(_) => {}
+                            ty::UpvarCapture::ByRef(..) => {
+                                projs.push(ProjectionElem::Deref);
+                            }
+                        };
 
-                    self.var_debug_info.push(VarDebugInfo {
-                        name,
-                        source_info: SourceInfo::outermost(tcx_hir.span(var_id)),
-                        place: Place {
-                            local: closure_env_arg,
-                            projection: tcx.intern_place_elems(&projs),
-                        },
-                    });
+                        self.var_debug_info.push(VarDebugInfo {
+                            name,
+                            source_info: SourceInfo::outermost(tcx_hir.span(var_id)),
+                            place: Place {
+                                local: closure_env_arg,
+                                projection: tcx.intern_place_elems(&projs),
+                            },
+                        });
+                    }
 
                     mutability
                 })
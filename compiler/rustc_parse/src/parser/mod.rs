@@ -864,13 +864,34 @@ impl<'a> Parser<'a> {
 
     /// Parses constness: `const` or nothing.
     fn parse_constness(&mut self) -> Const {
-        if self.eat_keyword(kw::Const) {
+        // Avoid const blocks to be parsed as const items
+        if !self.check_inline_const() && self.eat_keyword(kw::Const) {
             Const::Yes(self.prev_token.uninterpolated_span())
         } else {
             Const::No
         }
     }
 
+    /// Is the current token the start of an inline const block, `const { ... }`?
+    fn check_inline_const(&mut self) -> bool {
+        self.check_keyword(kw::Const)
+            && self.look_ahead(1, |t| t == &token::OpenDelim(token::Brace))
+    }
+
+    /// Parses an inline const block, `const { ... }`, in expression or pattern position.
+    fn parse_const_block(&mut self, attrs: AttrVec) -> PResult<'a, P<ast::Expr>> {
+        let lo = self.token.span;
+        self.expect_keyword(kw::Const)?;
+        self.sess.gated_spans.gate(sym::inline_const, lo);
+        let blk = self.parse_block()?;
+        let anon_const = ast::AnonConst {
+            id: DUMMY_NODE_ID,
+            value: self.mk_expr(blk.span, ast::ExprKind::Block(blk, None), AttrVec::new()),
+        };
+        let kind = ast::ExprKind::ConstBlock(anon_const);
+        Ok(self.mk_expr(lo.to(self.prev_token.span), kind, attrs))
+    }
+
     /// Parses mutability (`mut` or nothing).
     fn parse_mutability(&mut self) -> Mutability {
         if self.eat_keyword(kw::Mut) { Mutability::Mut } else { Mutability::Not }
@@ -218,8 +218,59 @@ impl<'a> Parser<'a> {
                 return Err(err);
             }
         };
+        let els = if init.is_some() && self.eat_keyword(kw::Else) {
+            let else_span = self.prev_token.span;
+            if let Some(init) = &init {
+                self.check_let_else_init(init);
+            }
+            let els = self.parse_block()?;
+            self.sess.gated_spans.gate(sym::let_else, else_span.to(els.span));
+            Some(els)
+        } else {
+            None
+        };
         let hi = if self.token == token::Semi { self.token.span } else { self.prev_token.span };
-        Ok(P(ast::Local { ty, pat, init, id: DUMMY_NODE_ID, span: lo.to(hi), attrs }))
+        Ok(P(ast::Local { ty, pat, init, els, id: DUMMY_NODE_ID, span: lo.to(hi), attrs }))
+    }
+
+    /// Rejects initializers of a `let...else` statement that would be ambiguous
+    /// with an `if`/`else` or a lazy boolean operator.
+    fn check_let_else_init(&self, init: &Expr) {
+        if let ExprKind::Binary(op, ..) = init.kind {
+            if op.node.lazy() {
+                self.struct_span_err(
+                    init.span,
+                    &format!(
+                        "a `{}` expression cannot be directly assigned in `let...else`",
+                        op.node.to_string()
+                    ),
+                )
+                .multipart_suggestion(
+                    "wrap the expression in parentheses",
+                    vec![
+                        (init.span.shrink_to_lo(), "(".to_string()),
+                        (init.span.shrink_to_hi(), ")".to_string()),
+                    ],
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+            }
+        }
+        if !classify::expr_requires_semi_to_be_stmt(init) {
+            self.struct_span_err(
+                init.span,
+                "right curly brace `}` before `else` in a `let...else` statement not allowed",
+            )
+            .multipart_suggestion(
+                "try wrapping the expression in parentheses",
+                vec![
+                    (init.span.shrink_to_lo(), "(".to_string()),
+                    (init.span.shrink_to_hi(), ")".to_string()),
+                ],
+                Applicability::MachineApplicable,
+            )
+            .emit();
+        }
     }
 
     /// Parses the RHS of a local variable declaration (e.g., '= 14;').
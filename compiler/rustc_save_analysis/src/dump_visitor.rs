@@ -1441,6 +1441,12 @@ impl<'tcx> Visitor<'tcx> for DumpVisitor<'tcx> {
                     v.visit_expr(&map.body(anon_const.body).value)
                 });
             }
+            hir::ExprKind::ConstBlock(ref anon_const) => {
+                let map = self.tcx.hir();
+                self.nest_typeck_results(self.tcx.hir().local_def_id(anon_const.hir_id), |v| {
+                    v.visit_expr(&map.body(anon_const.body).value)
+                });
+            }
             // In particular, we take this branch for call and path expressions,
             // where we'll index the idents involved just by continuing to walk.
             _ => intravisit::walk_expr(self, ex),
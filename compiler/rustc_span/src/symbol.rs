@@ -315,6 +315,7 @@ symbols! {
         call_mut,
         call_once,
         caller_location,
+        capture_disjoint_fields,
         cdylib,
         ceilf32,
         ceilf64,
@@ -430,6 +431,8 @@ symbols! {
         deref_mut,
         deref_target,
         derive,
+        derive_default_enum,
+        destructuring_assignment,
         diagnostic,
         direct,
         discriminant_kind,
@@ -455,6 +458,7 @@ symbols! {
         dropck_parametricity,
         dylib,
         dyn_trait,
+        each,
         eh_catch_typeinfo,
         eh_personality,
         emit_enum,
@@ -593,6 +597,7 @@ symbols! {
         infer_static_outlives_requirements,
         inlateout,
         inline,
+        inline_const,
         inout,
         instruction_set,
         intel,
@@ -618,6 +623,7 @@ symbols! {
         lazy_normalization_consts,
         le,
         let_chains,
+        let_else,
         lhs,
         lib,
         libc,
@@ -895,6 +901,7 @@ symbols! {
         rustc_args_required_const,
         rustc_attrs,
         rustc_builtin_macro,
+        rustc_capture_analysis,
         rustc_clean,
         rustc_const_stable,
         rustc_const_unstable,
@@ -1093,8 +1100,12 @@ symbols! {
         test_2018_feature,
         test_accepted_feature,
         test_case,
+        test_fixtures,
         test_removed_feature,
         test_runner,
+        test_setup,
+        test_teardown,
+        test_timeout,
         then_with,
         thread,
         thread_local,
@@ -1,5 +1,6 @@
 use crate::check::coercion::CoerceMany;
 use crate::check::{Diverges, Expectation, FnCtxt, Needs};
+use rustc_errors::struct_span_err;
 use rustc_hir::{self as hir, ExprKind};
 use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use rustc_infer::traits::Obligation;
@@ -109,6 +110,8 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 && self.if_fallback_coercion(expr.span, &arms[0].body, &mut coercion)
             {
                 tcx.ty_error()
+            } else if match_src == LetElseDesugar && i != 0 {
+                self.check_let_else_diverges(&arm.body)
             } else {
                 // Only call this if this is not an `if` expr with an expected type and no `else`
                 // clause to avoid duplicated type errors. (#60254)
@@ -252,6 +255,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         let msg = match source {
             IfDesugar { .. } | IfLetDesugar { .. } => "block in `if` expression",
             WhileDesugar { .. } | WhileLetDesugar { .. } => "block in `while` expression",
+            LetElseDesugar => "block in `let...else` statement",
             _ => "arm",
         };
         for arm in arms {
@@ -259,6 +263,25 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         }
     }
 
+    /// Checks that the `else` block of a desugared `let...else` diverges.
+    fn check_let_else_diverges(&self, els: &'tcx hir::Expr<'tcx>) -> Ty<'tcx> {
+        let ty = self.check_expr_with_expectation(els, Expectation::NoExpectation);
+        if self.diverges.get().is_always() || ty.references_error() {
+            return ty;
+        }
+        struct_span_err!(
+            self.tcx.sess,
+            els.span,
+            E0308,
+            "`else` clause of `let...else` does not diverge"
+        )
+        .span_label(els.span, format!("expected `!`, found `{}`", ty))
+        .help("try adding a diverging expression, such as `return` or `panic!(..)`")
+        .help("or use `match` instead of `let...else`")
+        .emit();
+        self.tcx.ty_error()
+    }
+
     /// Handle the fallback arm of a desugared if(-let) like a missing else.
     ///
     /// Returns `true` if there was an error forcing the coercion to the `()` type.
@@ -286,6 +286,12 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             }
             ExprKind::DropTemps(ref e) => self.check_expr_with_expectation(e, expected),
             ExprKind::Array(ref args) => self.check_expr_array(args, expected, expr),
+            ExprKind::ConstBlock(ref anon_const) => {
+                // Unlike array lengths, inline `const` blocks may depend on generic
+                // parameters and are only required to evaluate once monomorphized,
+                // so no `ConstEvaluatable` obligation is registered here.
+                tcx.type_of(tcx.hir().local_def_id(anon_const.hir_id))
+            }
             ExprKind::Repeat(ref element, ref count) => {
                 self.check_expr_repeat(element, count, expected, expr)
             }
@@ -727,6 +733,21 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         }
     }
 
+    /// Returns whether `lhs` is the left-hand side of one of the assignments a destructuring
+    /// assignment is desugared into, i.e. `a` in `{ let (lhs1, lhs2) = rhs; a = lhs1; .. }`.
+    fn is_destructured_lhs(&self, lhs: &'tcx hir::Expr<'tcx>) -> bool {
+        let hir = self.tcx.hir();
+        let assign_id = hir.get_parent_node(lhs.hir_id);
+        let stmt_id = hir.get_parent_node(assign_id);
+        if let hir::Node::Block(block) = hir.get(hir.get_parent_node(stmt_id)) {
+            if let Some(hir::Stmt { kind: hir::StmtKind::Local(local), .. }) = block.stmts.first()
+            {
+                return matches!(local.source, hir::LocalSource::AssignDesugar(_));
+            }
+        }
+        false
+    }
+
     pub(crate) fn check_lhs_assignable(
         &self,
         lhs: &'tcx hir::Expr<'tcx>,
@@ -741,9 +762,15 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 DiagnosticId::Error(err_code.into()),
             );
             err.span_label(lhs.span, "cannot assign to this expression");
+            // Plain `=` destructuring is desugared during lowering, so only compound
+            // assignment operators can get here with a destructuring left-hand side.
             if self.is_destructuring_place_expr(lhs) {
-                err.note("destructuring assignments are not currently supported");
-                err.note("for more information, see https://github.com/rust-lang/rfcs/issues/372");
+                err.note("destructuring assignments cannot use compound assignment operators");
+            } else if self.is_destructured_lhs(lhs) {
+                err.note(
+                    "each part of the left-hand side of a destructuring assignment must be a \
+                     place expression, a nested destructuring or `_`",
+                );
             }
             err.emit();
         }
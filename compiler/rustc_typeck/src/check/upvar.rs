@@ -29,20 +29,49 @@
 //! `ty::InferBorrow(upvar_id)` or something like that, but this would
 //! then mean that all later passes would have to check for these figments
 //! and report an error, and it just seems like more mess in the end.)
+//!
+//! ### Capturing places instead of variables
+//!
+//! With `#![feature(capture_disjoint_fields)]` (RFC 2229) a closure does not
+//! capture whole variables but the *places* it actually uses, e.g. `self.a`
+//! rather than `self`. The places seen by the `ExprUseVisitor` are collected
+//! together with their capture kind, and then reduced to the minimal set of
+//! places that covers all of them: if both `p` and `p.x` are used, only `p`
+//! is captured, with the strongest of the two capture kinds. The result is
+//! stored in `closure_min_captures`, which also determines the layout of the
+//! closure. Without the feature every place is truncated to its root
+//! variable, which gives the usual one-upvar-per-variable behavior.
 
 use super::FnCtxt;
 
 use crate::expr_use_visitor as euv;
-use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_errors::Applicability;
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::def_id::LocalDefId;
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_infer::infer::UpvarRegion;
-use rustc_middle::hir::place::{PlaceBase, PlaceWithHirId};
-use rustc_middle::ty::{self, Ty, TyCtxt, UpvarSubsts};
+use rustc_middle::hir::place::{Place, PlaceBase, PlaceWithHirId, ProjectionKind};
+use rustc_middle::ty::{self, Ty, TyCtxt, TypeFoldable, UpvarSubsts};
+use rustc_session::lint;
+use rustc_span::sym;
 use rustc_span::{Span, Symbol};
-use std::collections::hash_map::Entry;
+
+/// Describe the relationship between the paths of two places
+/// eg:
+/// - `foo` is ancestor of `foo.bar.baz`
+/// - `foo.bar.baz` is an descendant of `foo.bar`
+/// - `foo.bar` and `foo.baz` are divergent
+enum PlaceAncestryRelation {
+    Ancestor,
+    Descendant,
+    Divergent,
+}
+
+/// Intermediate format to store every place used by a closure, together with the capture
+/// kind it requires and the use that required it, in the order in which they were seen.
+type InferredCaptureInformation<'tcx> = FxIndexMap<Place<'tcx>, ty::CaptureInfo<'tcx>>;
 
 impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
     pub fn closure_analyze(&self, body: &'tcx hir::Body<'tcx>) {
@@ -81,7 +110,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         &self,
         closure_hir_id: hir::HirId,
         span: Span,
-        body: &hir::Body<'_>,
+        body: &'tcx hir::Body<'tcx>,
         capture_clause: hir::CaptureBy,
     ) {
         debug!("analyze_closure(id={:?}, body.id={:?})", closure_hir_id, body.id());
@@ -111,51 +140,18 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             None
         };
 
-        if let Some(upvars) = self.tcx.upvars_mentioned(closure_def_id) {
-            let mut closure_captures: FxIndexMap<hir::HirId, ty::UpvarId> =
-                FxIndexMap::with_capacity_and_hasher(upvars.len(), Default::default());
-            for (&var_hir_id, _) in upvars.iter() {
-                let upvar_id = ty::UpvarId {
-                    var_path: ty::UpvarPath { hir_id: var_hir_id },
-                    closure_expr_id: closure_def_id.expect_local(),
-                };
-                debug!("seed upvar_id {:?}", upvar_id);
-                // Adding the upvar Id to the list of Upvars, which will be added
-                // to the map for the closure at the end of the for loop.
-                closure_captures.insert(var_hir_id, upvar_id);
-
-                let capture_kind = match capture_clause {
-                    hir::CaptureBy::Value => ty::UpvarCapture::ByValue(None),
-                    hir::CaptureBy::Ref => {
-                        let origin = UpvarRegion(upvar_id, span);
-                        let upvar_region = self.next_region_var(origin);
-                        let upvar_borrow =
-                            ty::UpvarBorrow { kind: ty::ImmBorrow, region: upvar_region };
-                        ty::UpvarCapture::ByRef(upvar_borrow)
-                    }
-                };
-
-                self.typeck_results.borrow_mut().upvar_capture_map.insert(upvar_id, capture_kind);
-            }
-            // Add the vector of upvars to the map keyed with the closure id.
-            // This gives us an easier access to them without having to call
-            // tcx.upvars again..
-            if !closure_captures.is_empty() {
-                self.typeck_results
-                    .borrow_mut()
-                    .closure_captures
-                    .insert(closure_def_id, closure_captures);
-            }
-        }
-
         let body_owner_def_id = self.tcx.hir().body_owner_def_id(body.id());
         assert_eq!(body_owner_def_id.to_def_id(), closure_def_id);
+        let enable_precise_capture = self.tcx.features().capture_disjoint_fields;
         let mut delegate = InferBorrowKind {
             fcx: self,
             closure_def_id,
+            closure_span: span,
+            capture_clause,
+            enable_precise_capture,
             current_closure_kind: ty::ClosureKind::LATTICE_BOTTOM,
             current_origin: None,
-            adjust_upvar_captures: ty::UpvarCaptureMap::default(),
+            capture_information: Default::default(),
         };
         euv::ExprUseVisitor::new(
             &mut delegate,
@@ -166,6 +162,14 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         )
         .consume_body(body);
 
+        let mut capture_information = delegate.capture_information;
+        self.capture_unused_upvars(closure_def_id, span, capture_clause, &mut capture_information);
+
+        let log_capture_analysis = self.tcx.has_attr(closure_def_id, sym::rustc_capture_analysis);
+        if log_capture_analysis {
+            self.log_capture_analysis_first_pass(&capture_information, span);
+        }
+
         if let Some(closure_substs) = infer_kind {
             // Unify the (as yet unbound) type variable in the closure
             // substs with the kind we inferred.
@@ -182,10 +186,27 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             }
         }
 
-        self.typeck_results.borrow_mut().upvar_capture_map.extend(delegate.adjust_upvar_captures);
+        if !enable_precise_capture && self.should_do_migration_analysis(closure_hir_id) {
+            let need_migrations = self.compute_2229_migrations_for_drop(
+                closure_def_id,
+                span,
+                body,
+                capture_clause,
+                &capture_information,
+            );
+            if !need_migrations.is_empty() {
+                self.report_2229_migrations(closure_hir_id, span, body, &need_migrations);
+            }
+        }
+
+        self.compute_min_captures(closure_def_id, capture_information);
+        if log_capture_analysis {
+            self.log_closure_min_capture_info(closure_def_id, span);
+        }
+        self.record_root_variable_captures(closure_def_id);
 
         // Now that we've analyzed the closure, we know how each
-        // variable is borrowed, and we know what traits the closure
+        // place is borrowed, and we know what traits the closure
         // implements (Fn vs FnMut etc). We now have some updates to do
         // with that information.
         //
@@ -197,7 +218,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         // inference algorithm will reject it).
 
         // Equate the type variables for the upvars with the actual types.
-        let final_upvar_tys = self.final_upvar_tys(closure_hir_id);
+        let final_upvar_tys = self.final_upvar_tys(closure_def_id);
         debug!(
             "analyze_closure: id={:?} substs={:?} final_upvar_tys={:?}",
             closure_hir_id, substs, final_upvar_tys
@@ -217,39 +238,424 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
     }
 
     // Returns a list of `Ty`s for each upvar.
-    fn final_upvar_tys(&self, closure_id: hir::HirId) -> Vec<Ty<'tcx>> {
+    fn final_upvar_tys(&self, closure_def_id: DefId) -> Vec<Ty<'tcx>> {
         // Presently an unboxed closure type cannot "escape" out of a
         // function, so we will only encounter ones that originated in the
         // local crate or were inlined into it along with some function.
         // This may change if abstract return types of some sort are
         // implemented.
         let tcx = self.tcx;
-        let closure_def_id = tcx.hir().local_def_id(closure_id);
-
-        tcx.upvars_mentioned(closure_def_id)
-            .iter()
-            .flat_map(|upvars| {
-                upvars.iter().map(|(&var_hir_id, _)| {
-                    let upvar_ty = self.node_ty(var_hir_id);
-                    let upvar_id = ty::UpvarId {
-                        var_path: ty::UpvarPath { hir_id: var_hir_id },
-                        closure_expr_id: closure_def_id,
-                    };
-                    let capture = self.typeck_results.borrow().upvar_capture(upvar_id);
 
-                    debug!("var_id={:?} upvar_ty={:?} capture={:?}", var_hir_id, upvar_ty, capture);
+        self.typeck_results
+            .borrow()
+            .closure_min_captures_flattened(closure_def_id)
+            .map(|captured_place| {
+                let upvar_ty = captured_place.place.ty();
+                let capture = captured_place.info.capture_kind;
 
-                    match capture {
-                        ty::UpvarCapture::ByValue(_) => upvar_ty,
-                        ty::UpvarCapture::ByRef(borrow) => tcx.mk_ref(
-                            borrow.region,
-                            ty::TypeAndMut { ty: upvar_ty, mutbl: borrow.kind.to_mutbl_lossy() },
-                        ),
-                    }
-                })
+                debug!(
+                    "place={:?} upvar_ty={:?} capture={:?}",
+                    captured_place.place, upvar_ty, capture
+                );
+
+                match capture {
+                    ty::UpvarCapture::ByValue(_) => upvar_ty,
+                    ty::UpvarCapture::ByRef(borrow) => tcx.mk_ref(
+                        borrow.region,
+                        ty::TypeAndMut { ty: upvar_ty, mutbl: borrow.kind.to_mutbl_lossy() },
+                    ),
+                }
             })
             .collect()
     }
+
+    /// The capture kind a place starts out with when it is first used by the closure.
+    fn init_capture_kind(
+        &self,
+        capture_clause: hir::CaptureBy,
+        upvar_id: ty::UpvarId,
+        closure_span: Span,
+    ) -> ty::UpvarCapture<'tcx> {
+        match capture_clause {
+            hir::CaptureBy::Value => ty::UpvarCapture::ByValue(None),
+            hir::CaptureBy::Ref => {
+                let origin = UpvarRegion(upvar_id, closure_span);
+                let upvar_region = self.next_region_var(origin);
+                let upvar_borrow = ty::UpvarBorrow { kind: ty::ImmBorrow, region: upvar_region };
+                ty::UpvarCapture::ByRef(upvar_borrow)
+            }
+        }
+    }
+
+    /// Captures the variables that are mentioned in the closure but that no captured place
+    /// starts from. This happens for uses the `ExprUseVisitor` doesn't report, like
+    /// `let _ = x;`; such variables are captured entirely.
+    fn capture_unused_upvars(
+        &self,
+        closure_def_id: DefId,
+        closure_span: Span,
+        capture_clause: hir::CaptureBy,
+        capture_information: &mut InferredCaptureInformation<'tcx>,
+    ) {
+        let upvars = match self.tcx.upvars_mentioned(closure_def_id) {
+            Some(upvars) => upvars,
+            None => return,
+        };
+
+        for &var_hir_id in upvars.keys() {
+            let upvar_id = ty::UpvarId {
+                var_path: ty::UpvarPath { hir_id: var_hir_id },
+                closure_expr_id: closure_def_id.expect_local(),
+            };
+            let base = PlaceBase::Upvar(upvar_id);
+            if capture_information.keys().any(|place| place.base == base) {
+                continue;
+            }
+
+            debug!("capture_unused_upvars: capturing {:?} entirely", upvar_id);
+            let place = Place { base_ty: self.node_ty(var_hir_id), base, projections: vec![] };
+            let capture_kind = self.init_capture_kind(capture_clause, upvar_id, closure_span);
+            capture_information.insert(place, ty::CaptureInfo { expr_id: None, capture_kind });
+        }
+    }
+
+    /// Analyzes the information collected by `InferBorrowKind` to compute the min number of
+    /// Places (and corresponding capture kind) that we need to keep track of to support all
+    /// the required captured paths.
+    ///
+    /// Eg:
+    /// ```rust,no_run
+    /// struct Point { x: i32, y: i32 }
+    ///
+    /// let s: String;  // hir_id_s
+    /// let mut p: Point; // his_id_p
+    /// let c = || {
+    ///        println!("{}", s);  // L1
+    ///        p.x += 10;  // L2
+    ///        println!("{}" , p.y) // L3
+    ///        println!("{}", p) // L4
+    ///        drop(s);   // L5
+    /// };
+    /// ```
+    /// and let hir_id_L1..5 be the expressions pointing to use of a captured variable on
+    /// the lines L1..5 respectively.
+    ///
+    /// InferBorrowKind results in a structure like this:
+    ///
+    /// ```
+    /// {
+    ///       Place(base: hir_id_s, projections: [], ....) -> (hir_id_L5, ByValue),
+    ///       Place(base: hir_id_p, projections: [Field(0, 0)], ...) -> (hir_id_L2, ByRef(Mut))
+    ///       Place(base: hir_id_p, projections: [Field(1, 0)], ...) -> (hir_id_L3, ByRef(Imm))
+    ///       Place(base: hir_id_p, projections: [], ...) -> (hir_id_L4, ByRef(Imm))
+    /// ```
+    ///
+    /// After the min capture analysis, we get:
+    /// ```
+    /// {
+    ///       hir_id_s -> [
+    ///            Place(base: hir_id_s, projections: [], ....) -> (hir_id_L4, ByValue)
+    ///       ],
+    ///       hir_id_p -> [
+    ///            Place(base: hir_id_p, projections: [], ...) -> (hir_id_L2, ByRef(MutBorrow)),
+    ///       ],
+    /// ```
+    fn compute_min_captures(
+        &self,
+        closure_def_id: DefId,
+        capture_information: InferredCaptureInformation<'tcx>,
+    ) {
+        if capture_information.is_empty() {
+            return;
+        }
+
+        let mut root_var_min_capture_list: FxHashMap<hir::HirId, ty::MinCaptureList<'tcx>> =
+            Default::default();
+
+        for (place, capture_info) in capture_information.into_iter() {
+            let var_hir_id = match place.base {
+                PlaceBase::Upvar(upvar_id) => upvar_id.var_path.hir_id,
+                base => bug!("Expected upvar, found={:?}", base),
+            };
+
+            let min_cap_list = match root_var_min_capture_list.get_mut(&var_hir_id) {
+                None => {
+                    let min_cap_list = vec![ty::CapturedPlace { place, info: capture_info }];
+                    root_var_min_capture_list.insert(var_hir_id, min_cap_list);
+                    continue;
+                }
+                Some(min_cap_list) => min_cap_list,
+            };
+
+            // Go through each entry in the current list of min_captures
+            // - if ancestor is found, update it's capture kind to account for current place's
+            // capture information.
+            //
+            // - if descendant is found, remove it from the list, and update the current place's
+            // capture information to account for the descendants's capture kind.
+            //
+            // We can never be in a case where the list contains both an ancestor and a descendant
+            // Also there can only be ancestor but in case of descendants there might be
+            // multiple.
+
+            let mut descendant_found = false;
+            let mut updated_capture_info = capture_info;
+            min_cap_list.retain(|possible_descendant| {
+                match determine_place_ancestry_relation(&place, &possible_descendant.place) {
+                    // current place is ancestor of possible_descendant
+                    PlaceAncestryRelation::Ancestor => {
+                        descendant_found = true;
+                        updated_capture_info =
+                            determine_capture_info(updated_capture_info, possible_descendant.info);
+                        false
+                    }
+
+                    _ => true,
+                }
+            });
+
+            let mut ancestor_found = false;
+            if !descendant_found {
+                for possible_ancestor in min_cap_list.iter_mut() {
+                    match determine_place_ancestry_relation(&place, &possible_ancestor.place) {
+                        // current place is descendant of possible_ancestor
+                        PlaceAncestryRelation::Descendant => {
+                            ancestor_found = true;
+                            possible_ancestor.info =
+                                determine_capture_info(possible_ancestor.info, capture_info);
+
+                            // Only one ancestor of the current place will be in the list.
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            // Only need to insert when we don't have an ancestor in the existing min capture list
+            if !ancestor_found {
+                let captured_place = ty::CapturedPlace { place, info: updated_capture_info };
+                min_cap_list.push(captured_place);
+            }
+        }
+
+        // Lay the root variables out in the order in which they are mentioned in the closure,
+        // so that the layout doesn't depend on the order in which their uses were visited.
+        let mut min_captures = ty::RootVariableMinCaptureList::default();
+        let upvars = self.tcx.upvars_mentioned(closure_def_id);
+        for &var_hir_id in upvars.into_iter().flat_map(|upvars| upvars.keys()) {
+            if let Some(min_cap_list) = root_var_min_capture_list.remove(&var_hir_id) {
+                min_captures.insert(var_hir_id, min_cap_list);
+            }
+        }
+        assert!(root_var_min_capture_list.is_empty(), "captured a variable that isn't mentioned");
+
+        debug!("For closure={:?}, min_captures={:#?}", closure_def_id, min_captures);
+        self.typeck_results.borrow_mut().closure_min_captures.insert(closure_def_id, min_captures);
+    }
+
+    /// Records the root variables captured by the closure in `closure_captures`, and the
+    /// strongest capture kind of each of them in `upvar_capture_map`. These are used by the
+    /// passes that reason about captured variables rather than captured places.
+    fn record_root_variable_captures(&self, closure_def_id: DefId) {
+        let typeck_results = &mut *self.typeck_results.borrow_mut();
+        let min_captures = match typeck_results.closure_min_captures.get(&closure_def_id) {
+            Some(min_captures) => min_captures,
+            None => return,
+        };
+
+        let mut closure_captures: FxIndexMap<hir::HirId, ty::UpvarId> =
+            FxIndexMap::with_capacity_and_hasher(min_captures.len(), Default::default());
+        for (&var_hir_id, min_cap_list) in min_captures.iter() {
+            let upvar_id = ty::UpvarId {
+                var_path: ty::UpvarPath { hir_id: var_hir_id },
+                closure_expr_id: closure_def_id.expect_local(),
+            };
+            let capture_info = min_cap_list[1..]
+                .iter()
+                .fold(min_cap_list[0].info, |acc, captured_place| {
+                    determine_capture_info(acc, captured_place.info)
+                });
+            closure_captures.insert(var_hir_id, upvar_id);
+            typeck_results.upvar_capture_map.insert(upvar_id, capture_info.capture_kind);
+        }
+
+        // Add the vector of upvars to the map keyed with the closure id.
+        // This gives us an easier access to them without having to call
+        // tcx.upvars again..
+        typeck_results.closure_captures.insert(closure_def_id, closure_captures);
+    }
+
+    fn should_do_migration_analysis(&self, closure_hir_id: hir::HirId) -> bool {
+        let (level, _) = self
+            .tcx
+            .lint_level_at_node(lint::builtin::DISJOINT_CAPTURE_DROP_REORDER, closure_hir_id);
+
+        !matches!(level, lint::Level::Allow)
+    }
+
+    /// Figures out the list of root variables whose drop order **might** be affected when
+    /// `capture_disjoint_fields` is enabled. This is the case if the variable:
+    /// - is moved into the closure when `capture_disjoint_fields` isn't enabled, **and**
+    /// - isn't captured entirely when `capture_disjoint_fields` is enabled, **and**
+    /// - has a type that needs to be dropped.
+    ///
+    /// `capture_information` is the (root variable) capture information of the closure
+    /// without `capture_disjoint_fields`.
+    fn compute_2229_migrations_for_drop(
+        &self,
+        closure_def_id: DefId,
+        closure_span: Span,
+        body: &'tcx hir::Body<'tcx>,
+        capture_clause: hir::CaptureBy,
+        capture_information: &InferredCaptureInformation<'tcx>,
+    ) -> Vec<hir::HirId> {
+        let upvars = match self.tcx.upvars_mentioned(closure_def_id) {
+            Some(upvars) => upvars,
+            None => return vec![],
+        };
+
+        // Figure out which places would be captured with `capture_disjoint_fields`.
+        let mut delegate = InferBorrowKind {
+            fcx: self,
+            closure_def_id,
+            closure_span,
+            capture_clause,
+            enable_precise_capture: true,
+            current_closure_kind: ty::ClosureKind::LATTICE_BOTTOM,
+            current_origin: None,
+            capture_information: Default::default(),
+        };
+        euv::ExprUseVisitor::new(
+            &mut delegate,
+            &self.infcx,
+            closure_def_id.expect_local(),
+            self.param_env,
+            &self.typeck_results.borrow(),
+        )
+        .consume_body(body);
+        let precise_capture_information = delegate.capture_information;
+
+        let is_root = |place: &Place<'tcx>, var_hir_id: hir::HirId| match place.base {
+            PlaceBase::Upvar(upvar_id) => upvar_id.var_path.hir_id == var_hir_id,
+            _ => false,
+        };
+
+        let mut need_migrations = Vec::new();
+        for &var_hir_id in upvars.keys() {
+            let moved_into_closure = capture_information.iter().any(|(place, capture_info)| {
+                is_root(place, var_hir_id)
+                    && matches!(capture_info.capture_kind, ty::UpvarCapture::ByValue(_))
+            });
+            if !moved_into_closure {
+                continue;
+            }
+
+            let ty = self.infcx.resolve_vars_if_possible(&self.node_ty(var_hir_id));
+            let ty = self.tcx.erase_regions(&ty);
+            if ty.needs_infer() || !ty.needs_drop(self.tcx, self.param_env) {
+                continue;
+            }
+
+            let mut root_var_places =
+                precise_capture_information.keys().filter(|place| is_root(place, var_hir_id));
+            let captured_entirely = match root_var_places.clone().next() {
+                // Unused variables are captured entirely, see `capture_unused_upvars`.
+                None => true,
+                Some(_) => root_var_places.any(|place| place.projections.is_empty()),
+            };
+            if !captured_entirely {
+                need_migrations.push(var_hir_id);
+            }
+        }
+
+        need_migrations
+    }
+
+    fn report_2229_migrations(
+        &self,
+        closure_hir_id: hir::HirId,
+        closure_span: Span,
+        body: &'tcx hir::Body<'tcx>,
+        need_migrations: &[hir::HirId],
+    ) {
+        let names: Vec<_> =
+            need_migrations.iter().map(|&var_hir_id| var_name(self.tcx, var_hir_id)).collect();
+        let migrations_text = match &names[..] {
+            [name] => format!("let _ = &{}", name),
+            names => format!(
+                "let _ = ({})",
+                names.iter().map(|name| format!("&{}", name)).collect::<Vec<_>>().join(", ")
+            ),
+        };
+        let names_text =
+            names.iter().map(|name| format!("`{}`", name)).collect::<Vec<_>>().join(", ");
+
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::DISJOINT_CAPTURE_DROP_REORDER,
+            closure_hir_id,
+            closure_span,
+            |lint| {
+                let mut diagnostics_builder = lint.build(
+                    "drop order affected for closure because of `capture_disjoint_fields`",
+                );
+                let closure_body_span = body.value.span;
+                let (sugg, app) =
+                    match self.tcx.sess.source_map().span_to_snippet(closure_body_span) {
+                        Ok(s) => (
+                            format!("{{ {}; {} }}", migrations_text, s),
+                            Applicability::MachineApplicable,
+                        ),
+                        Err(_) => (migrations_text.clone(), Applicability::HasPlaceholders),
+                    };
+                diagnostics_builder.span_suggestion(
+                    closure_body_span,
+                    &format!("add a dummy let to cause {} to be fully captured", names_text),
+                    sugg,
+                    app,
+                );
+                diagnostics_builder.emit();
+            },
+        );
+    }
+
+    fn log_capture_analysis_first_pass(
+        &self,
+        capture_information: &InferredCaptureInformation<'tcx>,
+        closure_span: Span,
+    ) {
+        let mut diag = self.tcx.sess.struct_span_err(closure_span, "First Pass analysis includes:");
+        for (place, capture_info) in capture_information {
+            let output_str = format!(
+                "Capturing {} -> {}",
+                ty::place_to_string_for_capture(self.tcx, place),
+                capture_kind_to_string(capture_info.capture_kind),
+            );
+            match capture_info.expr_id {
+                Some(expr_id) => diag.span_note(self.tcx.hir().span(expr_id), &output_str),
+                None => diag.note(&output_str),
+            };
+        }
+        diag.emit();
+    }
+
+    fn log_closure_min_capture_info(&self, closure_def_id: DefId, closure_span: Span) {
+        let typeck_results = self.typeck_results.borrow();
+        let mut diag =
+            self.tcx.sess.struct_span_err(closure_span, "Min Capture analysis includes:");
+        for captured_place in typeck_results.closure_min_captures_flattened(closure_def_id) {
+            let output_str = format!(
+                "Min Capture {} -> {}",
+                captured_place.to_string(self.tcx),
+                capture_kind_to_string(captured_place.info.capture_kind),
+            );
+            match captured_place.info.expr_id {
+                Some(expr_id) => diag.span_note(self.tcx.hir().span(expr_id), &output_str),
+                None => diag.note(&output_str),
+            };
+        }
+        diag.emit();
+    }
 }
 
 struct InferBorrowKind<'a, 'tcx> {
@@ -258,6 +664,15 @@ struct InferBorrowKind<'a, 'tcx> {
     // The def-id of the closure whose kind and upvar accesses are being inferred.
     closure_def_id: DefId,
 
+    // The span of the closure, used for the origin of the regions of by-ref captures.
+    closure_span: Span,
+
+    // Whether the closure is a `move` closure, in which case everything is captured by value.
+    capture_clause: hir::CaptureBy,
+
+    // Whether places are captured precisely (RFC 2229), or truncated to their root variable.
+    enable_precise_capture: bool,
+
     // The kind that we have inferred that the current closure
     // requires. Note that we *always* infer a minimal kind, even if
     // we don't always *use* that in the final result (i.e., sometimes
@@ -270,9 +685,9 @@ struct InferBorrowKind<'a, 'tcx> {
     // variable access that caused us to do so.
     current_origin: Option<(Span, Symbol)>,
 
-    // For each upvar that we access, we track the minimal kind of
-    // access we need (ref, ref mut, move, etc).
-    adjust_upvar_captures: ty::UpvarCaptureMap<'tcx>,
+    // For each place that we access, we track the minimal kind of
+    // access we need (ref, ref mut, move, etc) and the use that required it.
+    capture_information: InferredCaptureInformation<'tcx>,
 }
 
 impl<'a, 'tcx> InferBorrowKind<'a, 'tcx> {
@@ -286,20 +701,22 @@ impl<'a, 'tcx> InferBorrowKind<'a, 'tcx> {
             place_with_id, mode
         );
 
-        // we only care about moves
+        let upvar_id = if let PlaceBase::Upvar(upvar_id) = place_with_id.place.base {
+            upvar_id
+        } else {
+            return;
+        };
+
+        // Copying out of a place only requires it to be readable.
         match mode {
             euv::Copy => {
+                self.adjust_upvar_borrow_kind(place_with_id, upvar_id, ty::ImmBorrow);
                 return;
             }
             euv::Move => {}
         }
 
         let tcx = self.fcx.tcx;
-        let upvar_id = if let PlaceBase::Upvar(upvar_id) = place_with_id.place.base {
-            upvar_id
-        } else {
-            return;
-        };
 
         debug!("adjust_upvar_borrow_kind_for_consume: upvar={:?}", upvar_id);
 
@@ -320,25 +737,25 @@ impl<'a, 'tcx> InferBorrowKind<'a, 'tcx> {
             _ => Some(usage_span),
         };
 
-        let new_capture = ty::UpvarCapture::ByValue(by_value_span);
-        match self.adjust_upvar_captures.entry(upvar_id) {
-            Entry::Occupied(mut e) => {
-                match e.get() {
-                    // We always overwrite `ByRef`, since we require
-                    // that the upvar be available by value.
-                    //
-                    // If we had a previous by-value usage without a specific
-                    // span, use ours instead. Otherwise, keep the first span
-                    // we encountered, since there isn't an obviously better one.
-                    ty::UpvarCapture::ByRef(_) | ty::UpvarCapture::ByValue(None) => {
-                        e.insert(new_capture);
-                    }
-                    _ => {}
-                }
-            }
-            Entry::Vacant(e) => {
-                e.insert(new_capture);
+        let (place, _) =
+            self.restrict_capture_precision(place_with_id.place.clone(), ty::ImmBorrow);
+        let place = truncate_at_first_deref(place);
+
+        let new_capture = ty::CaptureInfo {
+            expr_id: Some(place_with_id.hir_id),
+            capture_kind: ty::UpvarCapture::ByValue(by_value_span),
+        };
+        match self.capture_information.get(&place).map(|capture_info| capture_info.capture_kind) {
+            // We always overwrite `ByRef`, since we require
+            // that the place be available by value.
+            //
+            // If we had a previous by-value usage without a specific
+            // span, use ours instead. Otherwise, keep the first span
+            // we encountered, since there isn't an obviously better one.
+            None | Some(ty::UpvarCapture::ByRef(_) | ty::UpvarCapture::ByValue(None)) => {
+                self.capture_information.insert(place, new_capture);
             }
+            Some(ty::UpvarCapture::ByValue(Some(_))) => {}
         }
     }
 
@@ -349,24 +766,7 @@ impl<'a, 'tcx> InferBorrowKind<'a, 'tcx> {
         debug!("adjust_upvar_borrow_kind_for_mut(place_with_id={:?})", place_with_id);
 
         if let PlaceBase::Upvar(upvar_id) = place_with_id.place.base {
-            let mut borrow_kind = ty::MutBorrow;
-            for pointer_ty in place_with_id.place.deref_tys() {
-                match pointer_ty.kind() {
-                    // Raw pointers don't inherit mutability.
-                    ty::RawPtr(_) => return,
-                    // assignment to deref of an `&mut`
-                    // borrowed pointer implies that the
-                    // pointer itself must be unique, but not
-                    // necessarily *mutable*
-                    ty::Ref(.., hir::Mutability::Mut) => borrow_kind = ty::UniqueImmBorrow,
-                    _ => (),
-                }
-            }
-            self.adjust_upvar_deref(
-                upvar_id,
-                self.fcx.tcx.hir().span(place_with_id.hir_id),
-                borrow_kind,
-            );
+            self.adjust_upvar_borrow_kind(place_with_id, upvar_id, ty::MutBorrow);
         }
     }
 
@@ -374,86 +774,133 @@ impl<'a, 'tcx> InferBorrowKind<'a, 'tcx> {
         debug!("adjust_upvar_borrow_kind_for_unique(place_with_id={:?})", place_with_id);
 
         if let PlaceBase::Upvar(upvar_id) = place_with_id.place.base {
-            if place_with_id.place.deref_tys().any(ty::TyS::is_unsafe_ptr) {
-                // Raw pointers don't inherit mutability.
-                return;
-            }
-            // for a borrowed pointer to be unique, its base must be unique
-            self.adjust_upvar_deref(
-                upvar_id,
-                self.fcx.tcx.hir().span(place_with_id.hir_id),
-                ty::UniqueImmBorrow,
-            );
+            self.adjust_upvar_borrow_kind(place_with_id, upvar_id, ty::UniqueImmBorrow);
         }
     }
 
-    fn adjust_upvar_deref(
-        &mut self,
-        upvar_id: ty::UpvarId,
-        place_span: Span,
-        borrow_kind: ty::BorrowKind,
-    ) {
-        assert!(match borrow_kind {
-            ty::MutBorrow => true,
-            ty::UniqueImmBorrow => true,
-
-            // imm borrows never require adjusting any kinds, so we don't wind up here
-            ty::ImmBorrow => false,
-        });
-
-        let tcx = self.fcx.tcx;
+    /// Truncates `place` to the part of it that the closure can capture, and weakens
+    /// `kind` to what the uses of the dropped projections still require of the truncated
+    /// place.
+    ///
+    /// Without `capture_disjoint_fields` only root variables are captured. Otherwise
+    /// places are cut before indexing (arrays and slices are captured entirely), before
+    /// fields of enums, unions and packed structs, and before dereferences of raw pointers.
+    fn restrict_capture_precision(
+        &self,
+        mut place: Place<'tcx>,
+        mut kind: ty::BorrowKind,
+    ) -> (Place<'tcx>, ty::BorrowKind) {
+        let len = if self.enable_precise_capture {
+            place
+                .projections
+                .iter()
+                .enumerate()
+                .position(|(i, proj)| match proj.kind {
+                    ProjectionKind::Index | ProjectionKind::Subslice => true,
+                    ProjectionKind::Deref => place.ty_before_projection(i).is_unsafe_ptr(),
+                    ProjectionKind::Field(..) => match place.ty_before_projection(i).kind() {
+                        ty::Adt(def, _) => def.is_enum() || def.is_union() || def.repr.packed(),
+                        _ => false,
+                    },
+                })
+                .unwrap_or(place.projections.len())
+        } else {
+            0
+        };
 
-        // if this is an implicit deref of an
-        // upvar, then we need to modify the
-        // borrow_kind of the upvar to make sure it
-        // is inferred to mutable if necessary
-        self.adjust_upvar_borrow_kind(upvar_id, borrow_kind);
+        for (i, proj) in place.projections.iter().enumerate().skip(len) {
+            if proj.kind != ProjectionKind::Deref {
+                continue;
+            }
+            match place.ty_before_projection(i).kind() {
+                // Raw pointers don't inherit mutability.
+                ty::RawPtr(_) => {
+                    kind = ty::ImmBorrow;
+                    break;
+                }
+                // assignment to deref of an `&mut`
+                // borrowed pointer implies that the
+                // pointer itself must be unique, but not
+                // necessarily *mutable*
+                ty::Ref(.., hir::Mutability::Mut) if kind == ty::MutBorrow => {
+                    kind = ty::UniqueImmBorrow;
+                }
+                _ => {}
+            }
+        }
 
-        // also need to be in an FnMut closure since this is not an ImmBorrow
-        self.adjust_closure_kind(
-            upvar_id.closure_expr_id,
-            ty::ClosureKind::FnMut,
-            place_span,
-            var_name(tcx, upvar_id.var_path.hir_id),
-        );
+        place.projections.truncate(len);
+        (place, kind)
     }
 
     /// We infer the borrow_kind with which to borrow upvars in a stack closure.
     /// The borrow_kind basically follows a lattice of `imm < unique-imm < mut`,
     /// moving from left to right as needed (but never right to left).
-    /// Here the argument `mutbl` is the borrow_kind that is required by
-    /// some particular use.
-    fn adjust_upvar_borrow_kind(&mut self, upvar_id: ty::UpvarId, kind: ty::BorrowKind) {
-        let upvar_capture = self
-            .adjust_upvar_captures
-            .get(&upvar_id)
-            .copied()
-            .unwrap_or_else(|| self.fcx.typeck_results.borrow().upvar_capture(upvar_id));
+    /// Here the argument `kind` is the borrow_kind that is required by
+    /// some particular use of `place_with_id`.
+    fn adjust_upvar_borrow_kind(
+        &mut self,
+        place_with_id: &PlaceWithHirId<'tcx>,
+        upvar_id: ty::UpvarId,
+        kind: ty::BorrowKind,
+    ) {
+        let (place, kind) = self.restrict_capture_precision(place_with_id.place.clone(), kind);
+        // Everything is captured by value in a `move` closure, and values can't be moved
+        // out from behind a pointer.
+        let place = match self.capture_clause {
+            hir::CaptureBy::Value => truncate_at_first_deref(place),
+            hir::CaptureBy::Ref => place,
+        };
+        let capture_kind = self.capture_information.get(&place).map(|info| info.capture_kind);
         debug!(
-            "adjust_upvar_borrow_kind(upvar_id={:?}, upvar_capture={:?}, kind={:?})",
-            upvar_id, upvar_capture, kind
+            "adjust_upvar_borrow_kind(place={:?}, capture_kind={:?}, kind={:?})",
+            place, capture_kind, kind
         );
 
-        match upvar_capture {
-            ty::UpvarCapture::ByValue(_) => {
-                // Upvar is already by-value, the strongest criteria.
+        if kind != ty::ImmBorrow {
+            // also need to be in an FnMut closure since this is not an ImmBorrow
+            self.adjust_closure_kind(
+                upvar_id.closure_expr_id,
+                ty::ClosureKind::FnMut,
+                self.fcx.tcx.hir().span(place_with_id.hir_id),
+                var_name(self.fcx.tcx, upvar_id.var_path.hir_id),
+            );
+        }
+
+        let new_capture_kind = match capture_kind {
+            None => match self.fcx.init_capture_kind(
+                self.capture_clause,
+                upvar_id,
+                self.closure_span,
+            ) {
+                ty::UpvarCapture::ByRef(upvar_borrow) => {
+                    ty::UpvarCapture::ByRef(ty::UpvarBorrow { kind, ..upvar_borrow })
+                }
+                by_value => by_value,
+            },
+            Some(ty::UpvarCapture::ByValue(_)) => {
+                // Place is already by-value, the strongest criteria.
+                return;
             }
-            ty::UpvarCapture::ByRef(mut upvar_borrow) => {
+            Some(ty::UpvarCapture::ByRef(mut upvar_borrow)) => {
                 match (upvar_borrow.kind, kind) {
                     // Take RHS:
                     (ty::ImmBorrow, ty::UniqueImmBorrow | ty::MutBorrow)
                     | (ty::UniqueImmBorrow, ty::MutBorrow) => {
                         upvar_borrow.kind = kind;
-                        self.adjust_upvar_captures
-                            .insert(upvar_id, ty::UpvarCapture::ByRef(upvar_borrow));
+                        ty::UpvarCapture::ByRef(upvar_borrow)
                     }
                     // Take LHS:
                     (ty::ImmBorrow, ty::ImmBorrow)
                     | (ty::UniqueImmBorrow, ty::ImmBorrow | ty::UniqueImmBorrow)
-                    | (ty::MutBorrow, _) => {}
+                    | (ty::MutBorrow, _) => return,
                 }
             }
-        }
+        };
+
+        let capture_info =
+            ty::CaptureInfo { expr_id: Some(place_with_id.hir_id), capture_kind: new_capture_kind };
+        self.capture_information.insert(place, capture_info);
     }
 
     fn adjust_closure_kind(
@@ -509,7 +956,11 @@ impl<'a, 'tcx> euv::Delegate<'tcx> for InferBorrowKind<'a, 'tcx> {
         debug!("borrow(place_with_id={:?}, bk={:?})", place_with_id, bk);
 
         match bk {
-            ty::ImmBorrow => {}
+            ty::ImmBorrow => {
+                if let PlaceBase::Upvar(upvar_id) = place_with_id.place.base {
+                    self.adjust_upvar_borrow_kind(place_with_id, upvar_id, ty::ImmBorrow);
+                }
+            }
             ty::UniqueImmBorrow => {
                 self.adjust_upvar_borrow_kind_for_unique(place_with_id);
             }
@@ -529,3 +980,116 @@ impl<'a, 'tcx> euv::Delegate<'tcx> for InferBorrowKind<'a, 'tcx> {
 fn var_name(tcx: TyCtxt<'_>, var_hir_id: hir::HirId) -> Symbol {
     tcx.hir().name(var_hir_id)
 }
+
+/// Truncates `place` before its first dereference.
+fn truncate_at_first_deref(mut place: Place<'tcx>) -> Place<'tcx> {
+    if let Some(pos) = place.projections.iter().position(|proj| proj.kind == ProjectionKind::Deref)
+    {
+        place.projections.truncate(pos);
+    }
+    place
+}
+
+/// Helper function to determine if we need to escalate CaptureKind from
+/// CaptureInfo A to B and returns the escalated CaptureInfo.
+/// (Note: CaptureInfo contains CaptureKind and an expression that led to capture it in that way)
+///
+/// If both `CaptureKind`s are considered equivalent, then the CaptureInfo is selected based
+/// on the `CaptureInfo` containing an associated expression id.
+///
+/// If both the CaptureKind and Expression are considered to be equivalent,
+/// then `CaptureInfo` A is preferred.
+fn determine_capture_info(
+    capture_info_a: ty::CaptureInfo<'tcx>,
+    capture_info_b: ty::CaptureInfo<'tcx>,
+) -> ty::CaptureInfo<'tcx> {
+    // If the capture kind is equivalent then, we don't need to escalate and can compare the
+    // expressions.
+    let eq_capture_kind = match (capture_info_a.capture_kind, capture_info_b.capture_kind) {
+        (ty::UpvarCapture::ByValue(_), ty::UpvarCapture::ByValue(_)) => true,
+        (ty::UpvarCapture::ByRef(ref_a), ty::UpvarCapture::ByRef(ref_b)) => {
+            ref_a.kind == ref_b.kind
+        }
+        (ty::UpvarCapture::ByValue(_), _) | (ty::UpvarCapture::ByRef(_), _) => false,
+    };
+
+    if eq_capture_kind {
+        match (capture_info_a.expr_id, capture_info_b.expr_id) {
+            (Some(_), _) | (None, None) => capture_info_a,
+            (None, Some(_)) => capture_info_b,
+        }
+    } else {
+        // We select the CaptureKind which ranks higher based the following priority order:
+        // ByValue > MutBorrow > UniqueImmBorrow > ImmBorrow
+        match (capture_info_a.capture_kind, capture_info_b.capture_kind) {
+            (ty::UpvarCapture::ByValue(_), _) => capture_info_a,
+            (_, ty::UpvarCapture::ByValue(_)) => capture_info_b,
+            (ty::UpvarCapture::ByRef(ref_a), ty::UpvarCapture::ByRef(ref_b)) => {
+                match (ref_a.kind, ref_b.kind) {
+                    // Take LHS:
+                    (ty::UniqueImmBorrow | ty::MutBorrow, ty::ImmBorrow)
+                    | (ty::MutBorrow, ty::UniqueImmBorrow) => capture_info_a,
+
+                    // Take RHS:
+                    (ty::ImmBorrow, ty::UniqueImmBorrow | ty::MutBorrow)
+                    | (ty::UniqueImmBorrow, ty::MutBorrow) => capture_info_b,
+
+                    (ty::ImmBorrow, ty::ImmBorrow)
+                    | (ty::UniqueImmBorrow, ty::UniqueImmBorrow)
+                    | (ty::MutBorrow, ty::MutBorrow) => {
+                        bug!("Expected unequal capture kinds");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Determines the Ancestry relationship of Place A relative to Place B
+///
+/// `PlaceAncestryRelation::Ancestor` implies Place A is ancestor of Place B
+/// `PlaceAncestryRelation::Descendant` implies Place A is descendant of Place B
+/// `PlaceAncestryRelation::Divergent` implies neither of them is the ancestor of the other.
+fn determine_place_ancestry_relation(
+    place_a: &Place<'tcx>,
+    place_b: &Place<'tcx>,
+) -> PlaceAncestryRelation {
+    // If Place A and Place B, don't start off from the same root variable, they are divergent.
+    if place_a.base != place_b.base {
+        return PlaceAncestryRelation::Divergent;
+    }
+
+    // Assume of length of projections_a = n
+    let projections_a = &place_a.projections;
+
+    // Assume of length of projections_b = m
+    let projections_b = &place_b.projections;
+
+    let same_initial_projections = projections_a
+        .iter()
+        .zip(projections_b.iter())
+        .all(|(proj_a, proj_b)| proj_a.kind == proj_b.kind);
+
+    if same_initial_projections {
+        // First min(n, m) projections are the same
+        // Select Ancestor/Descendant
+        if projections_b.len() >= projections_a.len() {
+            PlaceAncestryRelation::Ancestor
+        } else {
+            PlaceAncestryRelation::Descendant
+        }
+    } else {
+        PlaceAncestryRelation::Divergent
+    }
+}
+
+fn capture_kind_to_string(capture_kind: ty::UpvarCapture<'_>) -> &'static str {
+    match capture_kind {
+        ty::UpvarCapture::ByValue(_) => "ByValue",
+        ty::UpvarCapture::ByRef(borrow) => match borrow.kind {
+            ty::ImmBorrow => "ImmBorrow",
+            ty::UniqueImmBorrow => "UniqueImmBorrow",
+            ty::MutBorrow => "MutBorrow",
+        },
+    }
+}
@@ -56,6 +56,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         }
         wbcx.visit_body(body);
         wbcx.visit_upvar_capture_map();
+        wbcx.visit_min_capture_map();
         wbcx.visit_closures();
         wbcx.visit_liberated_fn_sigs();
         wbcx.visit_fru_field_types();
@@ -342,6 +343,45 @@ impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
         }
     }
 
+    fn visit_min_capture_map(&mut self) {
+        let min_captures =
+            mem::take(&mut self.fcx.typeck_results.borrow_mut().closure_min_captures);
+        for (closure_def_id, root_min_captures) in min_captures {
+            let closure_hir_id =
+                self.tcx().hir().local_def_id_to_hir_id(closure_def_id.expect_local());
+            let mut root_var_map_wb = ty::RootVariableMinCaptureList::default();
+            for (var_hir_id, min_list) in root_min_captures {
+                let min_list_wb = min_list
+                    .into_iter()
+                    .map(|captured_place| {
+                        let locatable = captured_place.info.expr_id.unwrap_or(closure_hir_id);
+                        self.resolve_captured_place(captured_place, &locatable)
+                    })
+                    .collect();
+                root_var_map_wb.insert(var_hir_id, min_list_wb);
+            }
+
+            debug!("closure={:?} min captures resolved to {:?}", closure_def_id, root_var_map_wb);
+            self.typeck_results.closure_min_captures.insert(closure_def_id, root_var_map_wb);
+        }
+    }
+
+    fn resolve_captured_place(
+        &mut self,
+        mut captured_place: ty::CapturedPlace<'tcx>,
+        locatable: &hir::HirId,
+    ) -> ty::CapturedPlace<'tcx> {
+        let place = &mut captured_place.place;
+        place.base_ty = self.resolve(&place.base_ty, locatable);
+        for projection in place.projections.iter_mut() {
+            projection.ty = self.resolve(&projection.ty, locatable);
+        }
+        if let ty::UpvarCapture::ByRef(ref mut upvar_borrow) = captured_place.info.capture_kind {
+            upvar_borrow.region = self.tcx().lifetimes.re_erased;
+        }
+        captured_place
+    }
+
     fn visit_closures(&mut self) {
         let fcx_typeck_results = self.fcx.typeck_results.borrow();
         assert_eq!(fcx_typeck_results.hir_owner, self.typeck_results.hir_owner);
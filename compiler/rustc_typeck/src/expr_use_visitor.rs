@@ -258,7 +258,10 @@ impl<'a, 'tcx> ExprUseVisitor<'a, 'tcx> {
                 self.consume_exprs(&ia.inputs_exprs);
             }
 
-            hir::ExprKind::Continue(..) | hir::ExprKind::Lit(..) | hir::ExprKind::Err => {}
+            hir::ExprKind::Continue(..)
+            | hir::ExprKind::Lit(..)
+            | hir::ExprKind::ConstBlock(..)
+            | hir::ExprKind::Err => {}
 
             hir::ExprKind::Loop(ref blk, _, _) => {
                 self.walk_block(blk);
@@ -542,29 +545,40 @@ impl<'a, 'tcx> ExprUseVisitor<'a, 'tcx> {
         }));
     }
 
+    /// Handle the case where the current body contains a closure.
+    ///
+    /// The closure captures places (see `closure_min_captures`), which are reported to the
+    /// delegate as uses of the corresponding places in the body of the closure's creator.
     fn walk_captures(&mut self, closure_expr: &hir::Expr<'_>, fn_decl_span: Span) {
         debug!("walk_captures({:?})", closure_expr);
 
-        let closure_def_id = self.tcx().hir().local_def_id(closure_expr.hir_id);
-        if let Some(upvars) = self.tcx().upvars_mentioned(closure_def_id) {
-            for &var_id in upvars.keys() {
-                let upvar_id = ty::UpvarId {
-                    var_path: ty::UpvarPath { hir_id: var_id },
-                    closure_expr_id: closure_def_id,
-                };
-                let upvar_capture = self.mc.typeck_results.upvar_capture(upvar_id);
-                let captured_place = return_if_err!(self.cat_captured_var(
+        let closure_def_id = self.tcx().hir().local_def_id(closure_expr.hir_id).to_def_id();
+        let min_captures = match self.mc.typeck_results.closure_min_captures.get(&closure_def_id) {
+            Some(min_captures) => min_captures,
+            None => return,
+        };
+
+        for (&var_id, min_cap_list) in min_captures {
+            for captured_place in min_cap_list {
+                let var_place = return_if_err!(self.cat_captured_var(
                     closure_expr.hir_id,
                     fn_decl_span,
                     var_id,
                 ));
-                match upvar_capture {
+                // Rebase the captured place on the variable as seen from the creator.
+                let place_with_id = PlaceWithHirId::new(
+                    var_place.hir_id,
+                    captured_place.place.base_ty,
+                    var_place.place.base,
+                    captured_place.place.projections.clone(),
+                );
+                match captured_place.info.capture_kind {
                     ty::UpvarCapture::ByValue(_) => {
-                        let mode = copy_or_move(&self.mc, &captured_place);
-                        self.delegate.consume(&captured_place, mode);
+                        let mode = copy_or_move(&self.mc, &place_with_id);
+                        self.delegate.consume(&place_with_id, mode);
                     }
                     ty::UpvarCapture::ByRef(upvar_borrow) => {
-                        self.delegate.borrow(&captured_place, upvar_borrow.kind);
+                        self.delegate.borrow(&place_with_id, upvar_borrow.kind);
                     }
                 }
             }
@@ -5,13 +5,14 @@ use core::hash::{Hash, Hasher};
 use core::iter::{FromIterator, FusedIterator, Peekable};
 use core::marker::PhantomData;
 use core::mem::{self, ManuallyDrop};
-use core::ops::{Index, RangeBounds};
+use core::ops::{Bound, Index, RangeBounds};
 use core::ptr;
 
 use super::borrow::DormantMutRef;
 use super::node::{self, marker, ForceResult::*, Handle, InsertResult::*, NodeRef};
 use super::search::{self, SearchResult::*};
 use super::unwrap_unchecked;
+use crate::alloc::{AllocRef, Global};
 
 use Entry::*;
 use UnderflowResult::*;
@@ -122,13 +123,22 @@ use UnderflowResult::*;
 /// *stat += random_stat_buff();
 /// ```
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct BTreeMap<K, V> {
+pub struct BTreeMap<
+    K,
+    V,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
     root: Option<node::Root<K, V>>,
     length: usize,
+    // Wrapped in `ManuallyDrop` so that `into_iter` can move it out of a map that
+    // must not be dropped.
+    alloc: ManuallyDrop<A>,
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for BTreeMap<K, V> {
+unsafe impl<#[may_dangle] K, #[may_dangle] V, A: AllocRef + Clone> Drop
+    for BTreeMap<K, V, A>
+{
     fn drop(&mut self) {
         unsafe {
             drop(ptr::read(self).into_iter());
@@ -137,18 +147,23 @@ unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
-    fn clone(&self) -> BTreeMap<K, V> {
-        fn clone_subtree<'a, K: Clone, V: Clone>(
+impl<K: Clone, V: Clone, A: AllocRef + Clone> Clone for BTreeMap<K, V, A> {
+    fn clone(&self) -> BTreeMap<K, V, A> {
+        fn clone_subtree<'a, K: Clone, V: Clone, A: AllocRef + Clone>(
             node: node::NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>,
-        ) -> BTreeMap<K, V>
+            alloc: A,
+        ) -> BTreeMap<K, V, A>
         where
             K: 'a,
             V: 'a,
         {
             match node.force() {
                 Leaf(leaf) => {
-                    let mut out_tree = BTreeMap { root: Some(node::Root::new_leaf()), length: 0 };
+                    let mut out_tree = BTreeMap {
+                        root: Some(node::Root::new_leaf(alloc.clone())),
+                        length: 0,
+                        alloc: ManuallyDrop::new(alloc),
+                    };
 
                     {
                         let root = out_tree.root.as_mut().unwrap(); // unwrap succeeds because we just wrapped
@@ -170,11 +185,12 @@ impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
                     out_tree
                 }
                 Internal(internal) => {
-                    let mut out_tree = clone_subtree(internal.first_edge().descend());
+                    let mut out_tree =
+                        clone_subtree(internal.first_edge().descend(), alloc.clone());
 
                     {
-                        let out_root = BTreeMap::ensure_is_owned(&mut out_tree.root);
-                        let mut out_node = out_root.push_internal_level();
+                        let out_root = BTreeMap::ensure_is_owned(&mut out_tree.root, &alloc);
+                        let mut out_node = out_root.push_internal_level(alloc.clone());
                         let mut in_edge = internal.first_edge();
                         while let Ok(kv) = in_edge.right_kv() {
                             let (k, v) = kv.into_kv();
@@ -182,18 +198,21 @@ impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
 
                             let k = (*k).clone();
                             let v = (*v).clone();
-                            let subtree = clone_subtree(in_edge.descend());
+                            let subtree = clone_subtree(in_edge.descend(), alloc.clone());
 
                             // We can't destructure subtree directly
                             // because BTreeMap implements Drop
                             let (subroot, sublength) = unsafe {
-                                let subtree = ManuallyDrop::new(subtree);
+                                let mut subtree = ManuallyDrop::new(subtree);
                                 let root = ptr::read(&subtree.root);
                                 let length = subtree.length;
+                                ManuallyDrop::drop(&mut subtree.alloc);
                                 (root, length)
                             };
 
-                            out_node.push(k, v, subroot.unwrap_or_else(node::Root::new_leaf));
+                            let subroot =
+                                subroot.unwrap_or_else(|| node::Root::new_leaf(alloc.clone()));
+                            out_node.push(k, v, subroot);
                             out_tree.length += 1 + sublength;
                         }
                     }
@@ -206,14 +225,15 @@ impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
         if self.is_empty() {
             // Ideally we'd call `BTreeMap::new` here, but that has the `K:
             // Ord` constraint, which this method lacks.
-            BTreeMap { root: None, length: 0 }
+            BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new((*self.alloc).clone()) }
         } else {
-            clone_subtree(self.root.as_ref().unwrap().node_as_ref()) // unwrap succeeds because not empty
+            let root = self.root.as_ref().unwrap(); // unwrap succeeds because not empty
+            clone_subtree(root.node_as_ref(), (*self.alloc).clone())
         }
     }
 }
 
-impl<K, Q: ?Sized> super::Recover<Q> for BTreeMap<K, ()>
+impl<K, Q: ?Sized, A: AllocRef + Clone> super::Recover<Q> for BTreeMap<K, (), A>
 where
     K: Borrow<Q> + Ord,
     Q: Ord,
@@ -229,23 +249,26 @@ where
     }
 
     fn take(&mut self, key: &Q) -> Option<K> {
+        let alloc = (*self.alloc).clone();
         let (map, dormant_map) = DormantMutRef::new(self);
         let root_node = map.root.as_mut()?.node_as_mut();
         match search::search_tree(root_node, key) {
             Found(handle) => {
-                Some(OccupiedEntry { handle, dormant_map, _marker: PhantomData }.remove_kv().0)
+                let entry = OccupiedEntry { handle, dormant_map, alloc, _marker: PhantomData };
+                Some(entry.remove_kv().0)
             }
             GoDown(_) => None,
         }
     }
 
     fn replace(&mut self, key: K) -> Option<K> {
+        let alloc = (*self.alloc).clone();
         let (map, dormant_map) = DormantMutRef::new(self);
-        let root_node = Self::ensure_is_owned(&mut map.root).node_as_mut();
+        let root_node = Self::ensure_is_owned(&mut map.root, &alloc).node_as_mut();
         match search::search_tree::<marker::Mut<'_>, K, (), K>(root_node, &key) {
             Found(handle) => Some(mem::replace(handle.into_key_mut(), key)),
             GoDown(handle) => {
-                VacantEntry { key, handle, dormant_map, _marker: PhantomData }.insert(());
+                VacantEntry { key, handle, dormant_map, alloc, _marker: PhantomData }.insert(());
                 None
             }
         }
@@ -291,13 +314,18 @@ pub struct IterMut<'a, K: 'a, V: 'a> {
 ///
 /// [`into_iter`]: IntoIterator::into_iter
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct IntoIter<K, V> {
+pub struct IntoIter<
+    K,
+    V,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
     front: Option<Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>>,
     back: Option<Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>>,
     length: usize,
+    alloc: A,
 }
 
-impl<K, V> IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> IntoIter<K, V, A> {
     /// Returns an iterator of references over the remaining items.
     #[inline]
     pub(super) fn iter(&self) -> Iter<'_, K, V> {
@@ -311,7 +339,7 @@ impl<K, V> IntoIter<K, V> {
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for IntoIter<K, V> {
+impl<K: fmt::Debug, V: fmt::Debug, A: AllocRef + Clone> fmt::Debug for IntoIter<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
@@ -378,12 +406,16 @@ impl<K, V: fmt::Debug> fmt::Debug for ValuesMut<'_, K, V> {
 ///
 /// [`into_keys`]: BTreeMap::into_keys
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-pub struct IntoKeys<K, V> {
-    inner: IntoIter<K, V>,
+pub struct IntoKeys<
+    K,
+    V,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
+    inner: IntoIter<K, V, A>,
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K: fmt::Debug, V> fmt::Debug for IntoKeys<K, V> {
+impl<K: fmt::Debug, V, A: AllocRef + Clone> fmt::Debug for IntoKeys<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.inner.iter().map(|(key, _)| key)).finish()
     }
@@ -396,12 +428,16 @@ impl<K: fmt::Debug, V> fmt::Debug for IntoKeys<K, V> {
 ///
 /// [`into_values`]: BTreeMap::into_values
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-pub struct IntoValues<K, V> {
-    inner: IntoIter<K, V>,
+pub struct IntoValues<
+    K,
+    V,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
+    inner: IntoIter<K, V, A>,
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V: fmt::Debug> fmt::Debug for IntoValues<K, V> {
+impl<K, V: fmt::Debug, A: AllocRef + Clone> fmt::Debug for IntoValues<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.inner.iter().map(|(_, val)| val)).finish()
     }
@@ -458,18 +494,23 @@ impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for RangeMut<'_, K, V> {
 ///
 /// [`entry`]: BTreeMap::entry
 #[stable(feature = "rust1", since = "1.0.0")]
-pub enum Entry<'a, K: 'a, V: 'a> {
+pub enum Entry<
+    'a,
+    K: 'a,
+    V: 'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
     /// A vacant entry.
     #[stable(feature = "rust1", since = "1.0.0")]
-    Vacant(#[stable(feature = "rust1", since = "1.0.0")] VacantEntry<'a, K, V>),
+    Vacant(#[stable(feature = "rust1", since = "1.0.0")] VacantEntry<'a, K, V, A>),
 
     /// An occupied entry.
     #[stable(feature = "rust1", since = "1.0.0")]
-    Occupied(#[stable(feature = "rust1", since = "1.0.0")] OccupiedEntry<'a, K, V>),
+    Occupied(#[stable(feature = "rust1", since = "1.0.0")] OccupiedEntry<'a, K, V, A>),
 }
 
 #[stable(feature = "debug_btree_map", since = "1.12.0")]
-impl<K: Debug + Ord, V: Debug> Debug for Entry<'_, K, V> {
+impl<K: Debug + Ord, V: Debug, A: AllocRef + Clone> Debug for Entry<'_, K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Vacant(ref v) => f.debug_tuple("Entry").field(v).finish(),
@@ -481,17 +522,23 @@ impl<K: Debug + Ord, V: Debug> Debug for Entry<'_, K, V> {
 /// A view into a vacant entry in a `BTreeMap`.
 /// It is part of the [`Entry`] enum.
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct VacantEntry<'a, K: 'a, V: 'a> {
+pub struct VacantEntry<
+    'a,
+    K: 'a,
+    V: 'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
     key: K,
     handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>,
-    dormant_map: DormantMutRef<'a, BTreeMap<K, V>>,
+    dormant_map: DormantMutRef<'a, BTreeMap<K, V, A>>,
+    alloc: A,
 
     // Be invariant in `K` and `V`
     _marker: PhantomData<&'a mut (K, V)>,
 }
 
 #[stable(feature = "debug_btree_map", since = "1.12.0")]
-impl<K: Debug + Ord, V> Debug for VacantEntry<'_, K, V> {
+impl<K: Debug + Ord, V, A: AllocRef + Clone> Debug for VacantEntry<'_, K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("VacantEntry").field(self.key()).finish()
     }
@@ -500,21 +547,100 @@ impl<K: Debug + Ord, V> Debug for VacantEntry<'_, K, V> {
 /// A view into an occupied entry in a `BTreeMap`.
 /// It is part of the [`Entry`] enum.
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
+pub struct OccupiedEntry<
+    'a,
+    K: 'a,
+    V: 'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
     handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>,
-    dormant_map: DormantMutRef<'a, BTreeMap<K, V>>,
+    dormant_map: DormantMutRef<'a, BTreeMap<K, V, A>>,
+    alloc: A,
 
     // Be invariant in `K` and `V`
     _marker: PhantomData<&'a mut (K, V)>,
 }
 
 #[stable(feature = "debug_btree_map", since = "1.12.0")]
-impl<K: Debug + Ord, V: Debug> Debug for OccupiedEntry<'_, K, V> {
+impl<K: Debug + Ord, V: Debug, A: AllocRef + Clone> Debug for OccupiedEntry<'_, K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("OccupiedEntry").field("key", self.key()).field("value", self.get()).finish()
     }
 }
 
+/// A cursor over a `BTreeMap`.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always point to a gap between two elements in the map, and can
+/// operate on the two immediately adjacent elements.
+///
+/// This `struct` is created by the [`lower_bound`] and [`upper_bound`] methods on
+/// [`BTreeMap`]. See their documentation for more.
+///
+/// [`lower_bound`]: BTreeMap::lower_bound
+/// [`upper_bound`]: BTreeMap::upper_bound
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct Cursor<'a, K: 'a, V: 'a> {
+    /// The leaf edge in the gap the cursor points to. Empty if the map has no root.
+    current: Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf>, marker::Edge>>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K, V> Clone for Cursor<'_, K, V> {
+    fn clone(&self) -> Self {
+        Cursor { current: self.current }
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug> Debug for Cursor<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.peek_prev()).field(&self.peek_next()).finish()
+    }
+}
+
+/// A cursor over a `BTreeMap` with editing operations.
+///
+/// A `CursorMut` is like an iterator, except that it can freely seek back-and-forth,
+/// and can insert and remove elements at its position without searching the map
+/// again. The references it yields borrow the cursor itself rather than the map,
+/// so at most one element can be borrowed through it at a time.
+///
+/// Cursors always point to a gap between two elements in the map, and can
+/// operate on the two immediately adjacent elements.
+///
+/// This `struct` is created by the [`lower_bound_mut`] and [`upper_bound_mut`] methods
+/// on [`BTreeMap`]. See their documentation for more.
+///
+/// [`lower_bound_mut`]: BTreeMap::lower_bound_mut
+/// [`upper_bound_mut`]: BTreeMap::upper_bound_mut
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct CursorMut<
+    'a,
+    K: 'a,
+    V: 'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> {
+    /// The leaf edge in the gap the cursor points to.
+    /// Only empty while the tree is being restructured.
+    current: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>>,
+    /// Burried reference to the root field in the borrowed map.
+    /// Wrapped in `Option` to allow `take`-ing it while the height of the tree changes.
+    dormant_root: Option<DormantMutRef<'a, node::Root<K, V>>>,
+    /// Reference to the length field in the borrowed map, updated live.
+    length: &'a mut usize,
+    alloc: A,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug, A: AllocRef + Clone> Debug for CursorMut<'_, K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cursor = self.as_cursor();
+        f.debug_tuple("CursorMut").field(&cursor.peek_prev()).field(&cursor.peek_next()).finish()
+    }
+}
+
 // An iterator for merging two sorted sequences into one
 struct MergeIter<K, V, I: Iterator<Item = (K, V)>> {
     left: Peekable<I>,
@@ -541,7 +667,33 @@ impl<K: Ord, V> BTreeMap<K, V> {
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_const_unstable(feature = "const_btree_new", issue = "71835")]
     pub const fn new() -> BTreeMap<K, V> {
-        BTreeMap { root: None, length: 0 }
+        BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new(Global) }
+    }
+}
+
+impl<K: Ord, V, A: AllocRef + Clone> BTreeMap<K, V, A> {
+    /// Makes a new empty BTreeMap whose nodes are allocated in the given allocator.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new_in(System);
+    ///
+    /// // entries can now be inserted into the empty map
+    /// map.insert(1, "a");
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn new_in(alloc: A) -> BTreeMap<K, V, A> {
+        BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new(alloc) }
     }
 
     /// Clears the map, removing all elements.
@@ -560,7 +712,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn clear(&mut self) {
-        *self = BTreeMap::new();
+        *self = BTreeMap::new_in((*self.alloc).clone());
     }
 
     /// Returns a reference to the value corresponding to the key.
@@ -665,11 +817,17 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// assert_eq!(*map.get(&2).unwrap(), "b");
     /// ```
     #[unstable(feature = "map_first_last", issue = "62924")]
-    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>> {
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A>> {
+        let alloc = (*self.alloc).clone();
         let (map, dormant_map) = DormantMutRef::new(self);
         let root_node = map.root.as_mut()?.node_as_mut();
         let kv = root_node.first_leaf_edge().right_kv().ok()?;
-        Some(OccupiedEntry { handle: kv.forget_node_type(), dormant_map, _marker: PhantomData })
+        Some(OccupiedEntry {
+            handle: kv.forget_node_type(),
+            dormant_map,
+            alloc,
+            _marker: PhantomData,
+        })
     }
 
     /// Removes and returns the first element in the map.
@@ -739,11 +897,17 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// assert_eq!(*map.get(&2).unwrap(), "last");
     /// ```
     #[unstable(feature = "map_first_last", issue = "62924")]
-    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>> {
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A>> {
+        let alloc = (*self.alloc).clone();
         let (map, dormant_map) = DormantMutRef::new(self);
         let root_node = map.root.as_mut()?.node_as_mut();
         let kv = root_node.last_leaf_edge().left_kv().ok()?;
-        Some(OccupiedEntry { handle: kv.forget_node_type(), dormant_map, _marker: PhantomData })
+        Some(OccupiedEntry {
+            handle: kv.forget_node_type(),
+            dormant_map,
+            alloc,
+            _marker: PhantomData,
+        })
     }
 
     /// Removes and returns the last element in the map.
@@ -917,11 +1081,13 @@ impl<K: Ord, V> BTreeMap<K, V> {
         K: Borrow<Q>,
         Q: Ord,
     {
+        let alloc = (*self.alloc).clone();
         let (map, dormant_map) = DormantMutRef::new(self);
         let root_node = map.root.as_mut()?.node_as_mut();
         match search::search_tree(root_node, key) {
             Found(handle) => {
-                Some(OccupiedEntry { handle, dormant_map, _marker: PhantomData }.remove_entry())
+                let entry = OccupiedEntry { handle, dormant_map, alloc, _marker: PhantomData };
+                Some(entry.remove_entry())
             }
             GoDown(_) => None,
         }
@@ -969,8 +1135,10 @@ impl<K: Ord, V> BTreeMap<K, V> {
         }
 
         // First, we merge `self` and `other` into a sorted sequence in linear time.
-        let self_iter = mem::take(self).into_iter();
-        let other_iter = mem::take(other).into_iter();
+        let self_empty = BTreeMap::new_in((*self.alloc).clone());
+        let other_empty = BTreeMap::new_in((*other.alloc).clone());
+        let self_iter = mem::replace(self, self_empty).into_iter();
+        let other_iter = mem::replace(other, other_empty).into_iter();
         let iter = MergeIter { left: self_iter.peekable(), right: other_iter.peekable() };
 
         // Second, we build a tree from the sorted sequence in linear time.
@@ -1068,6 +1236,165 @@ impl<K: Ord, V> BTreeMap<K, V> {
         }
     }
 
+    /// Returns a [`Cursor`] pointing at the gap before the first element that lies
+    /// above `bound`.
+    ///
+    /// Passing `Bound::Included(x)` returns a cursor pointing at the gap before the
+    /// smallest key greater than or equal to `x`, and passing `Bound::Excluded(x)` one
+    /// pointing at the gap before the smallest key greater than `x`.
+    /// Passing `Bound::Unbounded` returns a cursor pointing at the start of the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    ///
+    /// let cursor = map.lower_bound(Bound::Included(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&1, &"a")));
+    /// assert_eq!(cursor.peek_next(), Some((&2, &"b")));
+    ///
+    /// let cursor = map.lower_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&2, &"b")));
+    /// assert_eq!(cursor.peek_next(), Some((&3, &"c")));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        Cursor { current: self.root.as_ref().map(|root| root.node_as_ref().lower_bound(bound)) }
+    }
+
+    /// Returns a [`Cursor`] pointing at the gap after the last element that lies
+    /// below `bound`.
+    ///
+    /// Passing `Bound::Included(x)` returns a cursor pointing at the gap after the
+    /// largest key less than or equal to `x`, and passing `Bound::Excluded(x)` one
+    /// pointing at the gap after the largest key less than `x`.
+    /// Passing `Bound::Unbounded` returns a cursor pointing at the end of the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    ///
+    /// let cursor = map.upper_bound(Bound::Included(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&2, &"b")));
+    /// assert_eq!(cursor.peek_next(), Some((&3, &"c")));
+    ///
+    /// let cursor = map.upper_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&1, &"a")));
+    /// assert_eq!(cursor.peek_next(), Some((&2, &"b")));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        Cursor { current: self.root.as_ref().map(|root| root.node_as_ref().upper_bound(bound)) }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the gap before the first element that lies
+    /// above `bound`, as described for [`lower_bound`](BTreeMap::lower_bound).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(4, "d");
+    ///
+    /// let mut cursor = map.lower_bound_mut(Bound::Included(&2));
+    /// cursor.insert_after(3, "c");
+    /// cursor.insert_after(2, "b");
+    /// assert_eq!(cursor.remove_prev(), Some((1, "a")));
+    /// assert_eq!(map.into_iter().collect::<Vec<_>>(), [(2, "b"), (3, "c"), (4, "d")]);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let alloc = (*self.alloc).clone();
+        let root = Self::ensure_is_owned(&mut self.root, &alloc);
+        let (root, dormant_root) = DormantMutRef::new(root);
+        CursorMut {
+            current: Some(root.node_as_mut().lower_bound(bound)),
+            dormant_root: Some(dormant_root),
+            length: &mut self.length,
+            alloc,
+        }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the gap after the last element that lies
+    /// below `bound`, as described for [`upper_bound`](BTreeMap::upper_bound).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map: BTreeMap<i32, i32> = (0..8).map(|i| (i, i)).collect();
+    ///
+    /// // Double the values of all elements up to 4, walking backwards.
+    /// let mut cursor = map.upper_bound_mut(Bound::Included(&4));
+    /// while let Some((_, value)) = cursor.prev() {
+    ///     *value *= 2;
+    /// }
+    /// assert_eq!(map.values().copied().collect::<Vec<_>>(), [0, 2, 4, 6, 8, 5, 6, 7]);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let alloc = (*self.alloc).clone();
+        let root = Self::ensure_is_owned(&mut self.root, &alloc);
+        let (root, dormant_root) = DormantMutRef::new(root);
+        CursorMut {
+            current: Some(root.node_as_mut().upper_bound(bound)),
+            dormant_root: Some(dormant_root),
+            length: &mut self.length,
+            alloc,
+        }
+    }
+
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
     ///
     /// # Examples
@@ -1087,20 +1414,23 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// assert_eq!(count["a"], 3);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, A> {
         // FIXME(@porglezomp) Avoid allocating if we don't insert
+        let alloc = (*self.alloc).clone();
         let (map, dormant_map) = DormantMutRef::new(self);
-        let root_node = Self::ensure_is_owned(&mut map.root).node_as_mut();
+        let root_node = Self::ensure_is_owned(&mut map.root, &alloc).node_as_mut();
         match search::search_tree(root_node, &key) {
-            Found(handle) => Occupied(OccupiedEntry { handle, dormant_map, _marker: PhantomData }),
+            Found(handle) => {
+                Occupied(OccupiedEntry { handle, dormant_map, alloc, _marker: PhantomData })
+            }
             GoDown(handle) => {
-                Vacant(VacantEntry { key, handle, dormant_map, _marker: PhantomData })
+                Vacant(VacantEntry { key, handle, dormant_map, alloc, _marker: PhantomData })
             }
         }
     }
 
     fn from_sorted_iter<I: Iterator<Item = (K, V)>>(&mut self, iter: I) {
-        let root = Self::ensure_is_owned(&mut self.root);
+        let root = Self::ensure_is_owned(&mut self.root, &self.alloc);
         let mut cur_node = root.node_as_mut().last_leaf_edge().into_node();
         // Iterate through all key-value pairs, pushing them into nodes at the right level.
         for (key, value) in iter {
@@ -1126,7 +1456,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                         }
                         Err(_) => {
                             // We are at the top, create a new root node and push there.
-                            open_node = root.push_internal_level();
+                            open_node = root.push_internal_level((*self.alloc).clone());
                             break;
                         }
                     }
@@ -1134,9 +1464,9 @@ impl<K: Ord, V> BTreeMap<K, V> {
 
                 // Push key-value pair and new right subtree.
                 let tree_height = open_node.height() - 1;
-                let mut right_tree = node::Root::new_leaf();
+                let mut right_tree = node::Root::new_leaf((*self.alloc).clone());
                 for _ in 0..tree_height {
-                    right_tree.push_internal_level();
+                    right_tree.push_internal_level((*self.alloc).clone());
                 }
                 open_node.push(key, value, right_tree);
 
@@ -1206,16 +1536,16 @@ impl<K: Ord, V> BTreeMap<K, V> {
         K: Borrow<Q>,
     {
         if self.is_empty() {
-            return Self::new();
+            return Self::new_in((*self.alloc).clone());
         }
 
         let total_num = self.len();
         let left_root = self.root.as_mut().unwrap(); // unwrap succeeds because not empty
 
-        let mut right = Self::new();
-        let right_root = Self::ensure_is_owned(&mut right.root);
+        let mut right = Self::new_in((*self.alloc).clone());
+        let right_root = Self::ensure_is_owned(&mut right.root, &right.alloc);
         for _ in 0..left_root.height() {
-            right_root.push_internal_level();
+            right_root.push_internal_level((*self.alloc).clone());
         }
 
         {
@@ -1246,8 +1576,8 @@ impl<K: Ord, V> BTreeMap<K, V> {
             }
         }
 
-        left_root.fix_right_border();
-        right_root.fix_left_border();
+        left_root.fix_right_border((*self.alloc).clone());
+        right_root.fix_left_border((*self.alloc).clone());
 
         if left_root.height() < right_root.height() {
             self.length = left_root.node_as_ref().calc_length();
@@ -1291,14 +1621,15 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// assert_eq!(odds.keys().copied().collect::<Vec<_>>(), vec![1, 3, 5, 7]);
     /// ```
     #[unstable(feature = "btree_drain_filter", issue = "70530")]
-    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, K, V, F>
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, K, V, F, A>
     where
         F: FnMut(&K, &mut V) -> bool,
     {
         DrainFilter { pred, inner: self.drain_filter_inner() }
     }
 
-    pub(super) fn drain_filter_inner(&mut self) -> DrainFilterInner<'_, K, V> {
+    pub(super) fn drain_filter_inner(&mut self) -> DrainFilterInner<'_, K, V, A> {
+        let alloc = (*self.alloc).clone();
         if let Some(root) = self.root.as_mut() {
             let (root, dormant_root) = DormantMutRef::new(root);
             let front = root.node_as_mut().first_leaf_edge();
@@ -1306,9 +1637,15 @@ impl<K: Ord, V> BTreeMap<K, V> {
                 length: &mut self.length,
                 dormant_root: Some(dormant_root),
                 cur_leaf_edge: Some(front),
+                alloc,
             }
         } else {
-            DrainFilterInner { length: &mut self.length, dormant_root: None, cur_leaf_edge: None }
+            DrainFilterInner {
+                length: &mut self.length,
+                dormant_root: None,
+                cur_leaf_edge: None,
+                alloc,
+            }
         }
     }
 
@@ -1331,7 +1668,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// ```
     #[inline]
     #[unstable(feature = "map_into_keys_values", issue = "75294")]
-    pub fn into_keys(self) -> IntoKeys<K, V> {
+    pub fn into_keys(self) -> IntoKeys<K, V, A> {
         IntoKeys { inner: self.into_iter() }
     }
 
@@ -1354,13 +1691,13 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// ```
     #[inline]
     #[unstable(feature = "map_into_keys_values", issue = "75294")]
-    pub fn into_values(self) -> IntoValues<K, V> {
+    pub fn into_values(self) -> IntoValues<K, V, A> {
         IntoValues { inner: self.into_iter() }
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K, V> IntoIterator for &'a BTreeMap<K, V> {
+impl<'a, K, V, A: AllocRef + Clone> IntoIterator for &'a BTreeMap<K, V, A> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -1429,7 +1766,7 @@ impl<K, V> Clone for Iter<'_, K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K, V> IntoIterator for &'a mut BTreeMap<K, V> {
+impl<'a, K, V, A: AllocRef + Clone> IntoIterator for &'a mut BTreeMap<K, V, A> {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V>;
 
@@ -1501,28 +1838,30 @@ impl<'a, K, V> IterMut<'a, K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> IntoIterator for BTreeMap<K, V> {
+impl<K, V, A: AllocRef + Clone> IntoIterator for BTreeMap<K, V, A> {
     type Item = (K, V);
-    type IntoIter = IntoIter<K, V>;
+    type IntoIter = IntoIter<K, V, A>;
 
-    fn into_iter(self) -> IntoIter<K, V> {
+    fn into_iter(self) -> IntoIter<K, V, A> {
         let mut me = ManuallyDrop::new(self);
+        // SAFETY: `me` is never dropped, so the allocator is only moved out once.
+        let alloc = unsafe { ManuallyDrop::take(&mut me.alloc) };
         if let Some(root) = me.root.take() {
             let (f, b) = root.into_ref().full_range();
 
-            IntoIter { front: Some(f), back: Some(b), length: me.length }
+            IntoIter { front: Some(f), back: Some(b), length: me.length, alloc }
         } else {
-            IntoIter { front: None, back: None, length: 0 }
+            IntoIter { front: None, back: None, length: 0, alloc }
         }
     }
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-impl<K, V> Drop for IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> Drop for IntoIter<K, V, A> {
     fn drop(&mut self) {
-        struct DropGuard<'a, K, V>(&'a mut IntoIter<K, V>);
+        struct DropGuard<'a, K, V, A: AllocRef + Clone>(&'a mut IntoIter<K, V, A>);
 
-        impl<'a, K, V> Drop for DropGuard<'a, K, V> {
+        impl<'a, K, V, A: AllocRef + Clone> Drop for DropGuard<'a, K, V, A> {
             fn drop(&mut self) {
                 // Continue the same loop we perform below. This only runs when unwinding, so we
                 // don't have to care about panics this time (they'll abort).
@@ -1531,7 +1870,7 @@ impl<K, V> Drop for IntoIter<K, V> {
                 unsafe {
                     let mut node =
                         unwrap_unchecked(ptr::read(&self.0.front)).into_node().forget_type();
-                    while let Some(parent) = node.deallocate_and_ascend() {
+                    while let Some(parent) = node.deallocate_and_ascend(&self.0.alloc) {
                         node = parent.into_node().forget_type();
                     }
                 }
@@ -1549,7 +1888,7 @@ impl<K, V> Drop for IntoIter<K, V> {
                 let mut node = front.into_node().forget_type();
                 // Most of the nodes have been deallocated while traversing
                 // but one pile from a leaf up to the root is left standing.
-                while let Some(parent) = node.deallocate_and_ascend() {
+                while let Some(parent) = node.deallocate_and_ascend(&self.alloc) {
                     node = parent.into_node().forget_type();
                 }
             }
@@ -1558,7 +1897,7 @@ impl<K, V> Drop for IntoIter<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> Iterator for IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> Iterator for IntoIter<K, V, A> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<(K, V)> {
@@ -1566,7 +1905,7 @@ impl<K, V> Iterator for IntoIter<K, V> {
             None
         } else {
             self.length -= 1;
-            Some(unsafe { self.front.as_mut().unwrap().next_unchecked() })
+            Some(unsafe { self.front.as_mut().unwrap().next_unchecked(&self.alloc) })
         }
     }
 
@@ -1576,26 +1915,26 @@ impl<K, V> Iterator for IntoIter<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> DoubleEndedIterator for IntoIter<K, V, A> {
     fn next_back(&mut self) -> Option<(K, V)> {
         if self.length == 0 {
             None
         } else {
             self.length -= 1;
-            Some(unsafe { self.back.as_mut().unwrap().next_back_unchecked() })
+            Some(unsafe { self.back.as_mut().unwrap().next_back_unchecked(&self.alloc) })
         }
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+impl<K, V, A: AllocRef + Clone> ExactSizeIterator for IntoIter<K, V, A> {
     fn len(&self) -> usize {
         self.length
     }
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl<K, V> FusedIterator for IntoIter<K, V> {}
+impl<K, V, A: AllocRef + Clone> FusedIterator for IntoIter<K, V, A> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a, K, V> Iterator for Keys<'a, K, V> {
@@ -1689,18 +2028,23 @@ impl<K, V> Clone for Values<'_, K, V> {
 
 /// An iterator produced by calling `drain_filter` on BTreeMap.
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-pub struct DrainFilter<'a, K, V, F>
-where
+pub struct DrainFilter<
+    'a,
+    K,
+    V,
+    F,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: AllocRef + Clone = Global,
+> where
     K: 'a,
     V: 'a,
     F: 'a + FnMut(&K, &mut V) -> bool,
 {
     pred: F,
-    inner: DrainFilterInner<'a, K, V>,
+    inner: DrainFilterInner<'a, K, V, A>,
 }
 /// Most of the implementation of DrainFilter, independent of the type
 /// of the predicate, thus also serving for BTreeSet::DrainFilter.
-pub(super) struct DrainFilterInner<'a, K: 'a, V: 'a> {
+pub(super) struct DrainFilterInner<'a, K: 'a, V: 'a, A: AllocRef + Clone = Global> {
     /// Reference to the length field in the borrowed map, updated live.
     length: &'a mut usize,
     /// Burried reference to the root field in the borrowed map.
@@ -1710,10 +2054,12 @@ pub(super) struct DrainFilterInner<'a, K: 'a, V: 'a> {
     /// Empty if the map has no root, if iteration went beyond the last leaf edge,
     /// or if a panic occurred in the predicate.
     cur_leaf_edge: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>>,
+    /// The allocator the map's nodes live in, needed to merge nodes while removing elements.
+    alloc: A,
 }
 
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-impl<K, V, F> Drop for DrainFilter<'_, K, V, F>
+impl<K, V, F, A: AllocRef + Clone> Drop for DrainFilter<'_, K, V, F, A>
 where
     F: FnMut(&K, &mut V) -> bool,
 {
@@ -1723,7 +2069,7 @@ where
 }
 
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-impl<K, V, F> fmt::Debug for DrainFilter<'_, K, V, F>
+impl<K, V, F, A: AllocRef + Clone> fmt::Debug for DrainFilter<'_, K, V, F, A>
 where
     K: fmt::Debug,
     V: fmt::Debug,
@@ -1735,7 +2081,7 @@ where
 }
 
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-impl<K, V, F> Iterator for DrainFilter<'_, K, V, F>
+impl<K, V, F, A: AllocRef + Clone> Iterator for DrainFilter<'_, K, V, F, A>
 where
     F: FnMut(&K, &mut V) -> bool,
 {
@@ -1750,7 +2096,7 @@ where
     }
 }
 
-impl<'a, K: 'a, V: 'a> DrainFilterInner<'a, K, V> {
+impl<'a, K: 'a, V: 'a, A: AllocRef + Clone> DrainFilterInner<'a, K, V, A> {
     /// Allow Debug implementations to predict the next element.
     pub(super) fn peek(&self) -> Option<(&K, &V)> {
         let edge = self.cur_leaf_edge.as_ref()?;
@@ -1766,13 +2112,17 @@ impl<'a, K: 'a, V: 'a> DrainFilterInner<'a, K, V> {
             let (k, v) = kv.kv_mut();
             if pred(k, v) {
                 *self.length -= 1;
-                let (kv, pos) = kv.remove_kv_tracking(|| {
-                    // SAFETY: we will touch the root in a way that will not
-                    // invalidate the position returned.
-                    let root = unsafe { self.dormant_root.take().unwrap().awaken() };
-                    root.pop_internal_level();
-                    self.dormant_root = Some(DormantMutRef::new(root).1);
-                });
+                let alloc = self.alloc.clone();
+                let (kv, pos) = kv.remove_kv_tracking(
+                    || {
+                        // SAFETY: we will touch the root in a way that will not
+                        // invalidate the position returned.
+                        let root = unsafe { self.dormant_root.take().unwrap().awaken() };
+                        root.pop_internal_level(alloc.clone());
+                        self.dormant_root = Some(DormantMutRef::new(root).1);
+                    },
+                    alloc.clone(),
+                );
                 self.cur_leaf_edge = Some(pos);
                 return Some(kv);
             }
@@ -1792,7 +2142,10 @@ impl<'a, K: 'a, V: 'a> DrainFilterInner<'a, K, V> {
 }
 
 #[unstable(feature = "btree_drain_filter", issue = "70530")]
-impl<K, V, F> FusedIterator for DrainFilter<'_, K, V, F> where F: FnMut(&K, &mut V) -> bool {}
+impl<K, V, F, A: AllocRef + Clone> FusedIterator for DrainFilter<'_, K, V, F, A> where
+    F: FnMut(&K, &mut V) -> bool
+{
+}
 
 #[stable(feature = "btree_range", since = "1.17.0")]
 impl<'a, K, V> Iterator for Range<'a, K, V> {
@@ -1860,7 +2213,7 @@ impl<'a, K, V> Range<'a, K, V> {
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> Iterator for IntoKeys<K, V> {
+impl<K, V, A: AllocRef + Clone> Iterator for IntoKeys<K, V, A> {
     type Item = K;
 
     fn next(&mut self) -> Option<K> {
@@ -1885,24 +2238,24 @@ impl<K, V> Iterator for IntoKeys<K, V> {
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> DoubleEndedIterator for IntoKeys<K, V> {
+impl<K, V, A: AllocRef + Clone> DoubleEndedIterator for IntoKeys<K, V, A> {
     fn next_back(&mut self) -> Option<K> {
         self.inner.next_back().map(|(k, _)| k)
     }
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> ExactSizeIterator for IntoKeys<K, V> {
+impl<K, V, A: AllocRef + Clone> ExactSizeIterator for IntoKeys<K, V, A> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> FusedIterator for IntoKeys<K, V> {}
+impl<K, V, A: AllocRef + Clone> FusedIterator for IntoKeys<K, V, A> {}
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> Iterator for IntoValues<K, V> {
+impl<K, V, A: AllocRef + Clone> Iterator for IntoValues<K, V, A> {
     type Item = V;
 
     fn next(&mut self) -> Option<V> {
@@ -1919,21 +2272,21 @@ impl<K, V> Iterator for IntoValues<K, V> {
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> DoubleEndedIterator for IntoValues<K, V> {
+impl<K, V, A: AllocRef + Clone> DoubleEndedIterator for IntoValues<K, V, A> {
     fn next_back(&mut self) -> Option<V> {
         self.inner.next_back().map(|(_, v)| v)
     }
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> ExactSizeIterator for IntoValues<K, V> {
+impl<K, V, A: AllocRef + Clone> ExactSizeIterator for IntoValues<K, V, A> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
 #[unstable(feature = "map_into_keys_values", issue = "75294")]
-impl<K, V> FusedIterator for IntoValues<K, V> {}
+impl<K, V, A: AllocRef + Clone> FusedIterator for IntoValues<K, V, A> {}
 
 #[stable(feature = "btree_range", since = "1.17.0")]
 impl<'a, K, V> DoubleEndedIterator for Range<'a, K, V> {
@@ -2034,7 +2387,7 @@ impl<K: Ord, V> FromIterator<(K, V)> for BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V> Extend<(K, V)> for BTreeMap<K, V> {
+impl<K: Ord, V, A: AllocRef + Clone> Extend<(K, V)> for BTreeMap<K, V, A> {
     #[inline]
     fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
         iter.into_iter().for_each(move |(k, v)| {
@@ -2049,7 +2402,9 @@ impl<K: Ord, V> Extend<(K, V)> for BTreeMap<K, V> {
 }
 
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a, K: Ord + Copy, V: Copy> Extend<(&'a K, &'a V)> for BTreeMap<K, V> {
+impl<'a, K: Ord + Copy, V: Copy, A: AllocRef + Clone> Extend<(&'a K, &'a V)>
+    for BTreeMap<K, V, A>
+{
     fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
         self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
     }
@@ -2061,7 +2416,7 @@ impl<'a, K: Ord + Copy, V: Copy> Extend<(&'a K, &'a V)> for BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Hash, V: Hash> Hash for BTreeMap<K, V> {
+impl<K: Hash, V: Hash, A: AllocRef + Clone> Hash for BTreeMap<K, V, A> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         for elt in self {
             elt.hash(state);
@@ -2078,40 +2433,40 @@ impl<K: Ord, V> Default for BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialEq, V: PartialEq> PartialEq for BTreeMap<K, V> {
-    fn eq(&self, other: &BTreeMap<K, V>) -> bool {
+impl<K: PartialEq, V: PartialEq, A: AllocRef + Clone> PartialEq for BTreeMap<K, V, A> {
+    fn eq(&self, other: &BTreeMap<K, V, A>) -> bool {
         self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Eq, V: Eq> Eq for BTreeMap<K, V> {}
+impl<K: Eq, V: Eq, A: AllocRef + Clone> Eq for BTreeMap<K, V, A> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialOrd, V: PartialOrd> PartialOrd for BTreeMap<K, V> {
+impl<K: PartialOrd, V: PartialOrd, A: AllocRef + Clone> PartialOrd for BTreeMap<K, V, A> {
     #[inline]
-    fn partial_cmp(&self, other: &BTreeMap<K, V>) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &BTreeMap<K, V, A>) -> Option<Ordering> {
         self.iter().partial_cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V: Ord> Ord for BTreeMap<K, V> {
+impl<K: Ord, V: Ord, A: AllocRef + Clone> Ord for BTreeMap<K, V, A> {
     #[inline]
-    fn cmp(&self, other: &BTreeMap<K, V>) -> Ordering {
+    fn cmp(&self, other: &BTreeMap<K, V, A>) -> Ordering {
         self.iter().cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Debug, V: Debug> Debug for BTreeMap<K, V> {
+impl<K: Debug, V: Debug, A: AllocRef + Clone> Debug for BTreeMap<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, Q: ?Sized, V> Index<&Q> for BTreeMap<K, V>
+impl<K: Ord, Q: ?Sized, V, A: AllocRef + Clone> Index<&Q> for BTreeMap<K, V, A>
 where
     K: Borrow<Q>,
     Q: Ord,
@@ -2129,7 +2484,7 @@ where
     }
 }
 
-impl<K, V> BTreeMap<K, V> {
+impl<K, V, A: AllocRef + Clone> BTreeMap<K, V, A> {
     /// Gets an iterator over the entries of the map, sorted by key.
     ///
     /// # Examples
@@ -2305,12 +2660,15 @@ impl<K, V> BTreeMap<K, V> {
 
     /// If the root node is the empty (non-allocated) root node, allocate our
     /// own node. Is an associated function to avoid borrowing the entire BTreeMap.
-    fn ensure_is_owned(root: &mut Option<node::Root<K, V>>) -> &mut node::Root<K, V> {
-        root.get_or_insert_with(node::Root::new_leaf)
+    fn ensure_is_owned<'a>(
+        root: &'a mut Option<node::Root<K, V>>,
+        alloc: &A,
+    ) -> &'a mut node::Root<K, V> {
+        root.get_or_insert_with(|| node::Root::new_leaf(alloc.clone()))
     }
 }
 
-impl<'a, K: Ord, V> Entry<'a, K, V> {
+impl<'a, K: Ord, V, A: AllocRef + Clone> Entry<'a, K, V, A> {
     /// Ensures a value is in the entry by inserting the default if empty, and returns
     /// a mutable reference to the value in the entry.
     ///
@@ -2436,7 +2794,7 @@ impl<'a, K: Ord, V> Entry<'a, K, V> {
     }
 }
 
-impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
+impl<'a, K: Ord, V: Default, A: AllocRef + Clone> Entry<'a, K, V, A> {
     #[stable(feature = "entry_or_default", since = "1.28.0")]
     /// Ensures a value is in the entry by inserting the default value if empty,
     /// and returns a mutable reference to the value in the entry.
@@ -2459,7 +2817,7 @@ impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
     }
 }
 
-impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+impl<'a, K: Ord, V, A: AllocRef + Clone> VacantEntry<'a, K, V, A> {
     /// Gets a reference to the key that would be used when inserting a value
     /// through the VacantEntry.
     ///
@@ -2513,30 +2871,30 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn insert(self, value: V) -> &'a mut V {
-        let out_ptr = match self.handle.insert_recursing(self.key, value) {
-            (Fit(_), val_ptr) => {
+        let out_kv = match self.handle.insert_recursing(self.key, value, self.alloc.clone()) {
+            (Fit(_), kv) => {
                 // Safety: We have consumed self.handle and the handle returned.
                 let map = unsafe { self.dormant_map.awaken() };
                 map.length += 1;
-                val_ptr
+                kv
             }
-            (Split(ins), val_ptr) => {
+            (Split(ins), kv) => {
                 drop(ins.left);
                 // Safety: We have consumed self.handle and the reference returned.
                 let map = unsafe { self.dormant_map.awaken() };
                 let root = map.root.as_mut().unwrap();
-                root.push_internal_level().push(ins.k, ins.v, ins.right);
+                root.push_internal_level(self.alloc).push(ins.k, ins.v, ins.right);
                 map.length += 1;
-                val_ptr
+                kv
             }
         };
         // Now that we have finished growing the tree using borrowed references,
-        // dereference the pointer to a part of it, that we picked up along the way.
-        unsafe { &mut *out_ptr }
+        // dereference the handle to a part of it, that we picked up along the way.
+        out_kv.into_val_mut()
     }
 }
 
-impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+impl<'a, K: Ord, V, A: AllocRef + Clone> OccupiedEntry<'a, K, V, A> {
     /// Gets a reference to the key in the entry.
     ///
     /// # Examples
@@ -2701,24 +3059,266 @@ impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
     // Body of `remove_entry`, separate to keep the above implementations short.
     fn remove_kv(self) -> (K, V) {
         let mut emptied_internal_root = false;
-        let (old_kv, _) = self.handle.remove_kv_tracking(|| emptied_internal_root = true);
+        let (old_kv, _) =
+            self.handle.remove_kv_tracking(|| emptied_internal_root = true, self.alloc.clone());
         // SAFETY: we consumed the intermediate root borrow, `self.handle`.
         let map = unsafe { self.dormant_map.awaken() };
         map.length -= 1;
         if emptied_internal_root {
             let root = map.root.as_mut().unwrap();
-            root.pop_internal_level();
+            root.pop_internal_level(self.alloc);
         }
         old_kv
     }
 }
 
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Moves the cursor to the next gap, returning the key and value of the element
+    /// it moved over.
+    ///
+    /// If the cursor is already at the end of the map then `None` is returned and
+    /// the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let kv = self.current?.next_kv().ok()?;
+        self.current = Some(kv.next_leaf_edge());
+        Some(kv.into_kv())
+    }
+
+    /// Moves the cursor to the previous gap, returning the key and value of the element
+    /// it moved over.
+    ///
+    /// If the cursor is already at the start of the map then `None` is returned and
+    /// the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn prev(&mut self) -> Option<(&'a K, &'a V)> {
+        let kv = self.current?.next_back_kv().ok()?;
+        self.current = Some(kv.next_back_leaf_edge());
+        Some(kv.into_kv())
+    }
+
+    /// Returns a reference to the key and value of the next element without moving
+    /// the cursor, or `None` if the cursor is at the end of the map.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        self.current?.next_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Returns a reference to the key and value of the previous element without moving
+    /// the cursor, or `None` if the cursor is at the start of the map.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        self.current?.next_back_kv().ok().map(Handle::into_kv)
+    }
+}
+
+impl<'a, K, V, A: AllocRef + Clone> CursorMut<'a, K, V, A> {
+    /// Moves the cursor to the next gap, returning the key and value of the element
+    /// it moved over.
+    ///
+    /// If the cursor is already at the end of the map then `None` is returned and
+    /// the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn next(&mut self) -> Option<(&K, &mut V)> {
+        match self.current.take()?.next_kv() {
+            Ok(kv) => {
+                self.current = Some(kv.next_leaf_edge());
+                self.peek_prev()
+            }
+            Err(root) => {
+                self.current = Some(root.last_leaf_edge());
+                None
+            }
+        }
+    }
+
+    /// Moves the cursor to the previous gap, returning the key and value of the element
+    /// it moved over.
+    ///
+    /// If the cursor is already at the start of the map then `None` is returned and
+    /// the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn prev(&mut self) -> Option<(&K, &mut V)> {
+        match self.current.take()?.next_back_kv() {
+            Ok(kv) => {
+                self.current = Some(kv.next_back_leaf_edge());
+                self.peek_next()
+            }
+            Err(root) => {
+                self.current = Some(root.first_leaf_edge());
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to the key and value of the next element without moving
+    /// the cursor, or `None` if the cursor is at the end of the map.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let edge = unsafe { self.current.as_mut()?.reborrow_mut() };
+        let (k, v) = edge.next_kv().ok()?.into_kv_mut();
+        Some((&*k, v))
+    }
+
+    /// Returns a reference to the key and value of the previous element without moving
+    /// the cursor, or `None` if the cursor is at the start of the map.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let edge = unsafe { self.current.as_mut()?.reborrow_mut() };
+        let (k, v) = edge.next_back_kv().ok()?.into_kv_mut();
+        Some((&*k, v))
+    }
+
+    /// Returns a read-only cursor pointing at the same gap as this cursor.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the `CursorMut`,
+    /// which means it cannot outlive the `CursorMut` and that the `CursorMut` is
+    /// frozen for the lifetime of the `Cursor`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn as_cursor(&self) -> Cursor<'_, K, V> {
+        Cursor { current: self.current.as_ref().map(|edge| edge.reborrow()) }
+    }
+
+    /// Inserts a new element into the gap the cursor points to, leaving the cursor
+    /// pointing at the gap before the new element.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the map stays sorted, i.e. that the new key is
+    /// greater than the key of the previous element (if any) and less than the key
+    /// of the next element (if any).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
+        let kv = self.insert_kv(key, value);
+        self.current = Some(kv.left_edge());
+    }
+
+    /// Inserts a new element into the gap the cursor points to, leaving the cursor
+    /// pointing at the gap after the new element.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the map stays sorted, i.e. that the new key is
+    /// greater than the key of the previous element (if any) and less than the key
+    /// of the next element (if any).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
+        let kv = self.insert_kv(key, value);
+        self.current = Some(kv.right_edge());
+    }
+
+    /// Removes the next element from the map and returns it, or returns `None` if
+    /// the cursor is at the end of the map. The cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_next(&mut self) -> Option<(K, V)> {
+        match self.current.take()?.next_kv() {
+            Ok(kv) => Some(self.remove_kv(kv)),
+            Err(root) => {
+                self.current = Some(root.last_leaf_edge());
+                None
+            }
+        }
+    }
+
+    /// Removes the previous element from the map and returns it, or returns `None` if
+    /// the cursor is at the start of the map. The cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_prev(&mut self) -> Option<(K, V)> {
+        match self.current.take()?.next_back_kv() {
+            Ok(kv) => Some(self.remove_kv(kv)),
+            Err(root) => {
+                self.current = Some(root.first_leaf_edge());
+                None
+            }
+        }
+    }
+
+    // Body of the `insert_*_unchecked` methods, leaving the cursor without a position.
+    fn insert_kv(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+        let edge = self.current.take().unwrap();
+        let (result, kv) = edge.insert_recursing(key, value, self.alloc.clone());
+        if let Split(ins) = result {
+            drop(ins.left);
+            // SAFETY: we will touch the root in a way that will not
+            // invalidate the handle returned.
+            let root = unsafe { self.dormant_root.take().unwrap().awaken() };
+            root.push_internal_level(self.alloc.clone()).push(ins.k, ins.v, ins.right);
+            self.dormant_root = Some(DormantMutRef::new(root).1);
+        }
+        *self.length += 1;
+        kv
+    }
+
+    // Body of the `remove_*` methods, given the handle of an element next to the cursor.
+    fn remove_kv(
+        &mut self,
+        kv: Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>,
+    ) -> (K, V) {
+        let alloc = self.alloc.clone();
+        let (old_kv, pos) = kv.remove_kv_tracking(
+            || {
+                // SAFETY: we will touch the root in a way that will not
+                // invalidate the position returned.
+                let root = unsafe { self.dormant_root.take().unwrap().awaken() };
+                root.pop_internal_level(alloc.clone());
+                self.dormant_root = Some(DormantMutRef::new(root).1);
+            },
+            alloc.clone(),
+        );
+        self.current = Some(pos);
+        *self.length -= 1;
+        old_kv
+    }
+}
+
+impl<'a, K: Ord, V, A: AllocRef + Clone> CursorMut<'a, K, V, A> {
+    /// Inserts a new element into the gap the cursor points to, leaving the cursor
+    /// pointing at the gap before the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new key is not greater than the key of the previous element,
+    /// or not less than the key of the next element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_after(&mut self, key: K, value: V) {
+        self.assert_fits(&key);
+        unsafe { self.insert_after_unchecked(key, value) }
+    }
+
+    /// Inserts a new element into the gap the cursor points to, leaving the cursor
+    /// pointing at the gap after the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new key is not greater than the key of the previous element,
+    /// or not less than the key of the next element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_before(&mut self, key: K, value: V) {
+        self.assert_fits(&key);
+        unsafe { self.insert_before_unchecked(key, value) }
+    }
+
+    fn assert_fits(&mut self, key: &K) {
+        if let Some((prev, _)) = self.peek_prev() {
+            assert!(key > prev, "key must be greater than the key of the previous element");
+        }
+        if let Some((next, _)) = self.peek_next() {
+            assert!(key < next, "key must be less than the key of the next element");
+        }
+    }
+}
+
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV> {
     /// Removes a key/value-pair from the map, and returns that pair, as well as
     /// the leaf edge corresponding to that former pair.
-    fn remove_kv_tracking<F: FnOnce()>(
+    fn remove_kv_tracking<F: FnOnce(), A: AllocRef + Clone>(
         self,
         handle_emptied_internal_root: F,
+        alloc: A,
     ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
         let (old_kv, mut pos, was_internal) = match self.force() {
             Leaf(leaf) => {
@@ -2751,7 +3351,7 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInter
         let mut cur_node = unsafe { ptr::read(&pos).into_node().forget_type() };
         let mut at_leaf = true;
         while cur_node.len() < node::MIN_LEN {
-            match handle_underfull_node(cur_node) {
+            match handle_underfull_node(cur_node, alloc.clone()) {
                 AtRoot => break,
                 Merged(edge, merged_with_left, offset) => {
                     // If we merged with our right sibling then our tracked
@@ -2804,14 +3404,14 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInter
 
 impl<K, V> node::Root<K, V> {
     /// Removes empty levels on the top, but keep an empty leaf if the entire tree is empty.
-    fn fix_top(&mut self) {
+    fn fix_top<A: AllocRef + Clone>(&mut self, alloc: A) {
         while self.height() > 0 && self.node_as_ref().len() == 0 {
-            self.pop_internal_level();
+            self.pop_internal_level(alloc.clone());
         }
     }
 
-    fn fix_right_border(&mut self) {
-        self.fix_top();
+    fn fix_right_border<A: AllocRef + Clone>(&mut self, alloc: A) {
+        self.fix_top(alloc.clone());
 
         {
             let mut cur_node = self.node_as_mut();
@@ -2820,7 +3420,7 @@ impl<K, V> node::Root<K, V> {
                 let mut last_kv = node.last_kv();
 
                 if last_kv.can_merge() {
-                    cur_node = last_kv.merge().descend();
+                    cur_node = last_kv.merge(alloc.clone()).descend();
                 } else {
                     let right_len = last_kv.reborrow().right_edge().descend().len();
                     // `MINLEN + 1` to avoid readjust if merge happens on the next level.
@@ -2832,12 +3432,12 @@ impl<K, V> node::Root<K, V> {
             }
         }
 
-        self.fix_top();
+        self.fix_top(alloc);
     }
 
     /// The symmetric clone of `fix_right_border`.
-    fn fix_left_border(&mut self) {
-        self.fix_top();
+    fn fix_left_border<A: AllocRef + Clone>(&mut self, alloc: A) {
+        self.fix_top(alloc.clone());
 
         {
             let mut cur_node = self.node_as_mut();
@@ -2846,7 +3446,7 @@ impl<K, V> node::Root<K, V> {
                 let mut first_kv = node.first_kv();
 
                 if first_kv.can_merge() {
-                    cur_node = first_kv.merge().descend();
+                    cur_node = first_kv.merge(alloc.clone()).descend();
                 } else {
                     let left_len = first_kv.reborrow().left_edge().descend().len();
                     if left_len < node::MIN_LEN + 1 {
@@ -2857,7 +3457,7 @@ impl<K, V> node::Root<K, V> {
             }
         }
 
-        self.fix_top();
+        self.fix_top(alloc);
     }
 }
 
@@ -2867,8 +3467,9 @@ enum UnderflowResult<'a, K, V> {
     Stole(bool),
 }
 
-fn handle_underfull_node<'a, K: 'a, V: 'a>(
+fn handle_underfull_node<'a, K: 'a, V: 'a, A: AllocRef>(
     node: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
+    alloc: A,
 ) -> UnderflowResult<'_, K, V> {
     let parent = match node.ascend() {
         Ok(parent) => parent,
@@ -2889,7 +3490,7 @@ fn handle_underfull_node<'a, K: 'a, V: 'a>(
 
     if handle.can_merge() {
         let offset = if is_left { handle.reborrow().left_edge().descend().len() + 1 } else { 0 };
-        Merged(handle.merge(), is_left, offset)
+        Merged(handle.merge(alloc), is_left, offset)
     } else {
         if is_left {
             handle.steal_left();
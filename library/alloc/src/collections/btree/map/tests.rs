@@ -842,6 +842,120 @@ fn test_range_mut() {
     map.check();
 }
 
+#[test]
+fn test_cursor_bounds() {
+    let size = MIN_INSERTS_HEIGHT_2 as i32;
+    let map: BTreeMap<_, _> = (0..size).map(|i| (i * 2, i)).collect();
+    let key = |kv: Option<(&i32, &i32)>| kv.map(|(&k, _)| k);
+    let at_most = |k: i32| if k < 0 { None } else { Some(k.min(size * 2 - 2) & !1) };
+    let at_least = |k: i32| if k > size * 2 - 2 { None } else { Some((k.max(0) + 1) & !1) };
+
+    for k in -1..=size * 2 {
+        let cursor = map.lower_bound(Included(&k));
+        assert_eq!(key(cursor.peek_next()), at_least(k));
+        assert_eq!(key(cursor.peek_prev()), at_most(k - 1));
+        let cursor = map.lower_bound(Excluded(&k));
+        assert_eq!(key(cursor.peek_next()), at_least(k + 1));
+        assert_eq!(key(cursor.peek_prev()), at_most(k));
+        let cursor = map.upper_bound(Included(&k));
+        assert_eq!(key(cursor.peek_prev()), at_most(k));
+        assert_eq!(key(cursor.peek_next()), at_least(k + 1));
+        let cursor = map.upper_bound(Excluded(&k));
+        assert_eq!(key(cursor.peek_prev()), at_most(k - 1));
+        assert_eq!(key(cursor.peek_next()), at_least(k));
+    }
+    assert_eq!(key(map.lower_bound(Unbounded).peek_next()), Some(0));
+    assert_eq!(key(map.upper_bound(Unbounded).peek_prev()), Some(size * 2 - 2));
+}
+
+#[test]
+fn test_cursor_empty() {
+    let mut map: BTreeMap<i32, i32> = BTreeMap::new();
+    let mut cursor = map.lower_bound(Unbounded);
+    assert_eq!(cursor.next(), None);
+    assert_eq!(cursor.prev(), None);
+
+    let mut cursor = map.upper_bound_mut(Included(&0));
+    assert_eq!(cursor.next(), None);
+    assert_eq!(cursor.prev(), None);
+    assert_eq!(cursor.remove_next(), None);
+    assert_eq!(cursor.remove_prev(), None);
+    assert!(map.is_empty());
+    map.check();
+}
+
+#[test]
+fn test_cursor_walk() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map: BTreeMap<_, _> = (0..size).map(|i| (i, i)).collect();
+
+    let mut cursor = map.lower_bound(Unbounded);
+    assert_eq!(cursor.prev(), None);
+    for i in 0..size {
+        assert_eq!(cursor.next(), Some((&i, &i)));
+    }
+    assert_eq!(cursor.next(), None);
+    for i in (0..size).rev() {
+        assert_eq!(cursor.prev(), Some((&i, &i)));
+    }
+    assert_eq!(cursor.prev(), None);
+
+    let mut cursor = map.upper_bound_mut(Unbounded);
+    assert_eq!(cursor.next(), None);
+    while let Some((_, v)) = cursor.prev() {
+        *v += 1;
+    }
+    assert_eq!(cursor.peek_next(), Some((&0, &mut 1)));
+    assert!(map.iter().all(|(k, v)| *v == k + 1));
+    map.check();
+}
+
+#[test]
+fn test_cursor_mut_insert() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map = BTreeMap::new();
+
+    // Grow the tree from the middle, so that both neighbours get split.
+    let mut cursor = map.lower_bound_mut(Unbounded);
+    for i in 0..size {
+        cursor.insert_after(size * 2 - i - 1, ());
+        cursor.insert_before(i, ());
+    }
+    assert_eq!(cursor.peek_prev(), Some((&(size - 1), &mut ())));
+    assert_eq!(cursor.peek_next(), Some((&size, &mut ())));
+    assert_eq!(map.len(), size * 2);
+    assert!(map.keys().copied().eq(0..size * 2));
+    map.check();
+}
+
+#[test]
+#[should_panic(expected = "key must be less than the key of the next element")]
+fn test_cursor_mut_insert_unordered() {
+    let mut map: BTreeMap<_, _> = (0..3).map(|i| (i, i)).collect();
+    map.lower_bound_mut(Included(&1)).insert_after(2, 2);
+}
+
+#[test]
+fn test_cursor_mut_remove() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map: BTreeMap<_, _> = (0..size).map(|i| (i, i)).collect();
+
+    // Shrink the tree from the middle, so that both neighbours get merged or stolen from.
+    let mid = size / 2;
+    let mut cursor = map.lower_bound_mut(Included(&mid));
+    for i in 0..mid {
+        assert_eq!(cursor.remove_next(), Some((mid + i, mid + i)));
+        assert_eq!(cursor.remove_prev(), Some((mid - i - 1, mid - i - 1)));
+    }
+    assert_eq!(cursor.remove_prev(), None);
+    for i in mid * 2..size {
+        assert_eq!(cursor.remove_next(), Some((i, i)));
+    }
+    assert_eq!(cursor.remove_next(), None);
+    assert!(map.is_empty());
+    map.check();
+}
+
 mod test_drain_filter {
     use super::*;
 
@@ -2,13 +2,14 @@ use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::intrinsics;
 use core::mem;
-use core::ops::Bound::{Excluded, Included, Unbounded};
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
 use core::ops::RangeBounds;
 use core::ptr;
 
 use super::node::{marker, ForceResult::*, Handle, NodeRef};
 use super::search::{self, SearchResult};
 use super::unwrap_unchecked;
+use crate::alloc::AllocRef;
 
 /// Finds the leaf edges delimiting a specified range in or underneath a node.
 fn range_search<BorrowType, K, V, Q, R>(
@@ -275,15 +276,16 @@ impl<BorrowType, K, V> Handle<NodeRef<BorrowType, K, V, marker::Internal>, marke
 macro_rules! def_next_kv_uncheched_dealloc {
     { unsafe fn $name:ident : $adjacent_kv:ident } => {
         /// Given a leaf edge handle into an owned tree, returns a handle to the next KV,
-        /// while deallocating any node left behind yet leaving the corresponding edge
-        /// in its parent node dangling.
+        /// while deallocating any node left behind, using `alloc`, yet leaving the
+        /// corresponding edge in its parent node dangling.
         ///
         /// # Safety
         /// - The leaf edge must not be the last one in the direction travelled.
         /// - The node carrying the next KV returned must not have been deallocated by a
         ///   previous call on any handle obtained for this tree.
-        unsafe fn $name <K, V>(
+        unsafe fn $name <K, V, A: AllocRef + Clone>(
             leaf_edge: Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>,
+            alloc: A,
         ) -> Handle<NodeRef<marker::Owned, K, V, marker::LeafOrInternal>, marker::KV> {
             let mut edge = leaf_edge.forget_node_type();
             loop {
@@ -291,7 +293,8 @@ macro_rules! def_next_kv_uncheched_dealloc {
                     Ok(internal_kv) => return internal_kv,
                     Err(last_edge) => {
                         unsafe {
-                            let parent_edge = last_edge.into_node().deallocate_and_ascend();
+                            let parent_edge =
+                                last_edge.into_node().deallocate_and_ascend(alloc.clone());
                             unwrap_unchecked(parent_edge).forget_node_type()
                         }
                     }
@@ -418,13 +421,14 @@ impl<K, V> Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge> {
     /// - There must be another KV in the direction travelled.
     /// - That KV was not previously returned by counterpart `next_back_unchecked`
     ///   on any copy of the handles being used to traverse the tree.
+    /// - `alloc` must be the allocator that the nodes of the tree were allocated with.
     ///
     /// The only safe way to proceed with the updated handle is to compare it, drop it,
     /// call this method again subject to its safety conditions, or call counterpart
     /// `next_back_unchecked` subject to its safety conditions.
-    pub unsafe fn next_unchecked(&mut self) -> (K, V) {
+    pub unsafe fn next_unchecked<A: AllocRef + Clone>(&mut self, alloc: A) -> (K, V) {
         replace(self, |leaf_edge| {
-            let kv = unsafe { next_kv_unchecked_dealloc(leaf_edge) };
+            let kv = unsafe { next_kv_unchecked_dealloc(leaf_edge, alloc) };
             let k = unsafe { ptr::read(kv.reborrow().into_kv().0) };
             let v = unsafe { ptr::read(kv.reborrow().into_kv().1) };
             (kv.next_leaf_edge(), (k, v))
@@ -439,13 +443,14 @@ impl<K, V> Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge> {
     /// - There must be another KV in the direction travelled.
     /// - That leaf edge was not previously returned by counterpart `next_unchecked`
     ///   on any copy of the handles being used to traverse the tree.
+    /// - `alloc` must be the allocator that the nodes of the tree were allocated with.
     ///
     /// The only safe way to proceed with the updated handle is to compare it, drop it,
     /// call this method again subject to its safety conditions, or call counterpart
     /// `next_unchecked` subject to its safety conditions.
-    pub unsafe fn next_back_unchecked(&mut self) -> (K, V) {
+    pub unsafe fn next_back_unchecked<A: AllocRef + Clone>(&mut self, alloc: A) -> (K, V) {
         replace(self, |leaf_edge| {
-            let kv = unsafe { next_back_kv_unchecked_dealloc(leaf_edge) };
+            let kv = unsafe { next_back_kv_unchecked_dealloc(leaf_edge, alloc) };
             let k = unsafe { ptr::read(kv.reborrow().into_kv().0) };
             let v = unsafe { ptr::read(kv.reborrow().into_kv().1) };
             (kv.next_back_leaf_edge(), (k, v))
@@ -479,6 +484,52 @@ impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
             }
         }
     }
+
+    /// Returns the leaf edge right before the first KV in or underneath a node that
+    /// lies above `bound`, when `bound` is taken as the lower bound of a range.
+    pub fn lower_bound<Q: ?Sized>(
+        self,
+        bound: Bound<&Q>,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        match bound {
+            Included(key) => match search::search_tree(self, key) {
+                SearchResult::Found(kv) => kv.next_back_leaf_edge(),
+                SearchResult::GoDown(edge) => edge,
+            },
+            Excluded(key) => match search::search_tree(self, key) {
+                SearchResult::Found(kv) => kv.next_leaf_edge(),
+                SearchResult::GoDown(edge) => edge,
+            },
+            Unbounded => self.first_leaf_edge(),
+        }
+    }
+
+    /// Returns the leaf edge right after the last KV in or underneath a node that
+    /// lies below `bound`, when `bound` is taken as the upper bound of a range.
+    pub fn upper_bound<Q: ?Sized>(
+        self,
+        bound: Bound<&Q>,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        match bound {
+            Included(key) => match search::search_tree(self, key) {
+                SearchResult::Found(kv) => kv.next_leaf_edge(),
+                SearchResult::GoDown(edge) => edge,
+            },
+            Excluded(key) => match search::search_tree(self, key) {
+                SearchResult::Found(kv) => kv.next_back_leaf_edge(),
+                SearchResult::GoDown(edge) => edge,
+            },
+            Unbounded => self.last_leaf_edge(),
+        }
+    }
 }
 
 pub enum Position<BorrowType, K, V> {
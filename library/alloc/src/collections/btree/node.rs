@@ -34,7 +34,7 @@ use core::mem::{self, MaybeUninit};
 use core::ptr::{self, NonNull, Unique};
 use core::slice;
 
-use crate::alloc::{AllocRef, Global, Layout};
+use crate::alloc::{AllocRef, Layout};
 use crate::boxed::Box;
 
 const B: usize = 6;
@@ -119,12 +119,13 @@ struct BoxedNode<K, V> {
 }
 
 impl<K, V> BoxedNode<K, V> {
-    fn from_leaf(node: Box<LeafNode<K, V>>) -> Self {
-        BoxedNode { ptr: Box::into_unique(node) }
+    fn from_leaf<A: AllocRef>(node: Box<LeafNode<K, V>, A>) -> Self {
+        BoxedNode { ptr: Box::into_unique(node).0 }
     }
 
-    fn from_internal(node: Box<InternalNode<K, V>>) -> Self {
-        BoxedNode { ptr: Unique::from(&mut Box::leak(node).data) }
+    fn from_internal<A: AllocRef>(node: Box<InternalNode<K, V>, A>) -> Self {
+        // `InternalNode` is `repr(C)` and starts with its `LeafNode` portion.
+        BoxedNode { ptr: Box::into_unique(node).0.cast() }
     }
 
     unsafe fn from_ptr(ptr: NonNull<LeafNode<K, V>>) -> Self {
@@ -154,9 +155,11 @@ impl<K, V> Root<K, V> {
         self.height
     }
 
-    /// Returns a new owned tree, with its own root node that is initially empty.
-    pub fn new_leaf() -> Self {
-        Root { node: BoxedNode::from_leaf(Box::new(unsafe { LeafNode::new() })), height: 0 }
+    /// Returns a new owned tree, with its own root node that is initially empty,
+    /// allocated in `alloc`.
+    pub fn new_leaf<A: AllocRef>(alloc: A) -> Self {
+        let node = Box::new_in(unsafe { LeafNode::new() }, alloc);
+        Root { node: BoxedNode::from_leaf(node), height: 0 }
     }
 
     /// Borrows and returns an immutable reference to the node owned by the root.
@@ -196,8 +199,11 @@ impl<K, V> Root<K, V> {
     /// Adds a new internal node with a single edge pointing to the previous root node,
     /// make that new node the root node, and return it. This increases the height by 1
     /// and is the opposite of `pop_internal_level`.
-    pub fn push_internal_level(&mut self) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
-        let mut new_node = Box::new(unsafe { InternalNode::new() });
+    pub fn push_internal_level<A: AllocRef>(
+        &mut self,
+        alloc: A,
+    ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
+        let mut new_node = Box::new_in(unsafe { InternalNode::new() }, alloc);
         new_node.edges[0].write(unsafe { BoxedNode::from_ptr(self.node.as_ptr()) });
 
         self.node = BoxedNode::from_internal(new_node);
@@ -219,7 +225,7 @@ impl<K, V> Root<K, V> {
     /// it will not invalidate existing handles or references to the root node.
     ///
     /// Panics if there is no internal level, i.e., if the root node is a leaf.
-    pub fn pop_internal_level(&mut self) {
+    pub fn pop_internal_level<A: AllocRef>(&mut self, alloc: A) {
         assert!(self.height > 0);
 
         let top = self.node.ptr;
@@ -230,7 +236,7 @@ impl<K, V> Root<K, V> {
         self.node_as_mut().as_leaf_mut().parent = None;
 
         unsafe {
-            Global.dealloc(NonNull::from(top).cast(), Layout::new::<InternalNode<K, V>>());
+            alloc.dealloc(NonNull::from(top).cast(), Layout::new::<InternalNode<K, V>>());
         }
     }
 }
@@ -436,14 +442,15 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::LeafOrInternal> {
     /// Similar to `ascend`, gets a reference to a node's parent node, but also
     /// deallocate the current node in the process. This is unsafe because the
     /// current node will still be accessible despite being deallocated.
-    pub unsafe fn deallocate_and_ascend(
+    pub unsafe fn deallocate_and_ascend<A: AllocRef>(
         self,
+        alloc: A,
     ) -> Option<Handle<NodeRef<marker::Owned, K, V, marker::Internal>, marker::Edge>> {
         let height = self.height;
         let node = self.node;
         let ret = self.ascend().ok();
         unsafe {
-            Global.dealloc(
+            alloc.dealloc(
                 node.cast(),
                 if height > 0 {
                     Layout::new::<InternalNode<K, V>>()
@@ -477,6 +484,17 @@ impl<'a, K, V, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 
+    /// Unsafely replaces the lifetime of this mutable reference with an arbitrary one,
+    /// for when the borrow checker cannot follow that a node outlives the reference
+    /// it was reached through, such as a node that is about to be linked into the tree.
+    ///
+    /// # Safety
+    /// The node must remain allocated during `'new`, and the returned reference must
+    /// not be used while conflicting references to the node are in use.
+    unsafe fn with_lifetime<'new>(self) -> NodeRef<marker::Mut<'new>, K, V, Type> {
+        NodeRef { height: self.height, node: self.node, _marker: PhantomData }
+    }
+
     /// Exposes the leaf portion of any leaf or internal node for writing.
     ///
     /// We don't need to return a raw ptr because we have unique access to the entire node.
@@ -948,10 +966,14 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// this edge. This method assumes that there is enough space in the node for the new
     /// pair to fit.
     ///
-    /// The returned pointer points to the inserted value.
-    fn insert_fit(&mut self, key: K, val: V) -> *mut V {
+    /// Returns a handle to the inserted pair.
+    fn insert_fit(
+        mut self,
+        key: K,
+        val: V,
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
         self.leafy_insert_fit(key, val);
-        unsafe { self.node.val_mut_at(self.idx) }
+        unsafe { Handle::new_kv(self.node, self.idx) }
     }
 }
 
@@ -959,26 +981,36 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// Inserts a new key/value pair between the key/value pairs to the right and left of
     /// this edge. This method splits the node if there isn't enough room.
     ///
-    /// The returned pointer points to the inserted value.
-    fn insert(mut self, key: K, val: V) -> (InsertResult<'a, K, V, marker::Leaf>, *mut V) {
+    /// The returned handle points to the inserted pair.
+    fn insert<A: AllocRef>(
+        self,
+        key: K,
+        val: V,
+        alloc: A,
+    ) -> (
+        InsertResult<'a, K, V, marker::Leaf>,
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>,
+    ) {
         if self.node.len() < CAPACITY {
-            let val_ptr = self.insert_fit(key, val);
-            let kv = unsafe { Handle::new_kv(self.node, self.idx) };
-            (InsertResult::Fit(kv), val_ptr)
+            let kv = self.insert_fit(key, val);
+            // Both handles point to the inserted pair.
+            (InsertResult::Fit(unsafe { ptr::read(&kv) }), kv)
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let (mut left, k, v, mut right) = middle.split();
-            let mut insertion_edge = match insertion {
+            let (mut left, k, v, mut right) = middle.split(alloc);
+            // The node we insert into stays in place while the tree grows above it,
+            // so the handle to the inserted pair may live as long as the tree.
+            let insertion_edge = match insertion {
                 InsertionPlace::Left(insert_idx) => unsafe {
-                    Handle::new_edge(left.reborrow_mut(), insert_idx)
+                    Handle::new_edge(left.reborrow_mut().with_lifetime(), insert_idx)
                 },
                 InsertionPlace::Right(insert_idx) => unsafe {
-                    Handle::new_edge(right.leaf_node_as_mut(), insert_idx)
+                    Handle::new_edge(right.leaf_node_as_mut().with_lifetime(), insert_idx)
                 },
             };
-            let val_ptr = insertion_edge.insert_fit(key, val);
-            (InsertResult::Split(SplitResult { left: left.forget_type(), k, v, right }), val_ptr)
+            let kv = insertion_edge.insert_fit(key, val);
+            (InsertResult::Split(SplitResult { left: left.forget_type(), k, v, right }), kv)
         }
     }
 }
@@ -1013,11 +1045,12 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     /// Inserts a new key/value pair and an edge that will go to the right of that new pair
     /// between this edge and the key/value pair to the right of this edge. This method splits
     /// the node if there isn't enough room.
-    fn insert(
+    fn insert<A: AllocRef>(
         mut self,
         key: K,
         val: V,
         edge: Root<K, V>,
+        alloc: A,
     ) -> InsertResult<'a, K, V, marker::Internal> {
         assert!(edge.height == self.node.height - 1);
 
@@ -1028,7 +1061,7 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let (mut left, k, v, mut right) = middle.split();
+            let (mut left, k, v, mut right) = middle.split(alloc);
             let mut insertion_edge = match insertion {
                 InsertionPlace::Left(insert_idx) => unsafe {
                     Handle::new_edge(left.reborrow_mut(), insert_idx)
@@ -1050,29 +1083,33 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     ///
     /// If the returned result is a `Fit`, its handle's node can be this edge's node or an ancestor.
     /// If the returned result is a `Split`, the `left` field will be the root node.
-    /// The returned pointer points to the inserted value.
-    pub fn insert_recursing(
+    /// The returned handle points to the inserted pair, in the leaf it ended up in.
+    pub fn insert_recursing<A: AllocRef + Clone>(
         self,
         key: K,
         value: V,
-    ) -> (InsertResult<'a, K, V, marker::LeafOrInternal>, *mut V) {
-        let (mut split, val_ptr) = match self.insert(key, value) {
-            (InsertResult::Fit(handle), ptr) => {
-                return (InsertResult::Fit(handle.forget_node_type()), ptr);
+        alloc: A,
+    ) -> (
+        InsertResult<'a, K, V, marker::LeafOrInternal>,
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>,
+    ) {
+        let (mut split, kv) = match self.insert(key, value, alloc.clone()) {
+            (InsertResult::Fit(handle), kv) => {
+                return (InsertResult::Fit(handle.forget_node_type()), kv);
             }
-            (InsertResult::Split(split), val_ptr) => (split, val_ptr),
+            (InsertResult::Split(split), kv) => (split, kv),
         };
 
         loop {
             split = match split.left.ascend() {
-                Ok(parent) => match parent.insert(split.k, split.v, split.right) {
+                Ok(parent) => match parent.insert(split.k, split.v, split.right, alloc.clone()) {
                     InsertResult::Fit(handle) => {
-                        return (InsertResult::Fit(handle.forget_node_type()), val_ptr);
+                        return (InsertResult::Fit(handle.forget_node_type()), kv);
                     }
                     InsertResult::Split(split) => split,
                 },
                 Err(root) => {
-                    return (InsertResult::Split(SplitResult { left: root, ..split }), val_ptr);
+                    return (InsertResult::Split(SplitResult { left: root, ..split }), kv);
                 }
             };
         }
@@ -1125,6 +1162,10 @@ impl<'a, K, V, NodeType> Handle<NodeRef<marker::ValMut<'a>, K, V, NodeType>, mar
 
 impl<'a, K: 'a, V: 'a, NodeType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, marker::KV> {
     pub fn kv_mut(&mut self) -> (&mut K, &mut V) {
+        unsafe { self.reborrow_mut() }.into_kv_mut()
+    }
+
+    pub fn into_kv_mut(mut self) -> (&'a mut K, &'a mut V) {
         // We cannot call into_key_mut_at and into_val_mut_at, because calling the second one
         // invalidates the reference returned by the first.
         let leaf = self.node.as_leaf_mut();
@@ -1170,9 +1211,12 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// - The key and value pointed to by this handle are extracted.
     /// - All the key/value pairs to the right of this handle are put into a newly
     ///   allocated node.
-    pub fn split(mut self) -> (NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, K, V, Root<K, V>) {
+    pub fn split<A: AllocRef>(
+        mut self,
+        alloc: A,
+    ) -> (NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, K, V, Root<K, V>) {
         unsafe {
-            let mut new_node = Box::new(LeafNode::new());
+            let mut new_node = Box::new_in(LeafNode::new(), alloc);
 
             let (k, v, _) = self.leafy_split(&mut new_node);
 
@@ -1214,9 +1258,12 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     /// - The key and value pointed to by this handle are extracted.
     /// - All the edges and key/value pairs to the right of this handle are put into
     ///   a newly allocated node.
-    pub fn split(mut self) -> (NodeRef<marker::Mut<'a>, K, V, marker::Internal>, K, V, Root<K, V>) {
+    pub fn split<A: AllocRef>(
+        mut self,
+        alloc: A,
+    ) -> (NodeRef<marker::Mut<'a>, K, V, marker::Internal>, K, V, Root<K, V>) {
         unsafe {
-            let mut new_node = Box::new(InternalNode::new());
+            let mut new_node = Box::new_in(InternalNode::new(), alloc);
 
             let (k, v, new_len) = self.leafy_split(&mut new_node.data);
             let height = self.node.height;
@@ -1241,8 +1288,9 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     /// child of the underlying node, returning an edge referencing that new child.
     ///
     /// Panics unless this edge `.can_merge()`.
-    pub fn merge(
+    pub fn merge<A: AllocRef>(
         mut self,
+        alloc: A,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::Edge> {
         let self1 = unsafe { ptr::read(&self) };
         let self2 = unsafe { ptr::read(&self) };
@@ -1293,9 +1341,9 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
 
                 left_node.correct_childrens_parent_links(left_len + 1..=left_len + 1 + right_len);
 
-                Global.dealloc(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
+                alloc.dealloc(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
             } else {
-                Global.dealloc(right_node.node.cast(), Layout::new::<LeafNode<K, V>>());
+                alloc.dealloc(right_node.node.cast(), Layout::new::<LeafNode<K, V>>());
             }
 
             Handle::new_edge(self.node, self.idx)
@@ -1,4 +1,5 @@
 use super::*;
+use crate::alloc::Global;
 use core::cmp::Ordering::*;
 
 #[test]
@@ -27,11 +28,11 @@ fn test_splitpoint() {
 
 #[test]
 fn test_partial_cmp_eq() {
-    let mut root1: Root<i32, ()> = Root::new_leaf();
+    let mut root1: Root<i32, ()> = Root::new_leaf(Global);
     let mut leaf1 = unsafe { root1.leaf_node_as_mut() };
     leaf1.push(1, ());
-    root1.push_internal_level();
-    let root2: Root<i32, ()> = Root::new_leaf();
+    root1.push_internal_level(Global);
+    let root2: Root<i32, ()> = Root::new_leaf(Global);
 
     let leaf_edge_1a = root1.node_as_ref().first_leaf_edge().forget_node_type();
     let leaf_edge_1b = root1.node_as_ref().last_leaf_edge().forget_node_type();
@@ -52,9 +53,9 @@ fn test_partial_cmp_eq() {
     assert_eq!(top_edge_1.partial_cmp(&top_edge_1), Some(Equal));
     assert_eq!(top_edge_1.partial_cmp(&top_edge_2), None);
 
-    root1.pop_internal_level();
-    unsafe { root1.into_ref().deallocate_and_ascend() };
-    unsafe { root2.into_ref().deallocate_and_ascend() };
+    root1.pop_internal_level(Global);
+    unsafe { root1.into_ref().deallocate_and_ascend(Global) };
+    unsafe { root2.into_ref().deallocate_and_ascend(Global) };
 }
 
 #[test]
@@ -6,7 +6,7 @@ use core::cmp::Ordering::{Equal, Greater, Less};
 use core::cmp::{max, min};
 use core::fmt::{self, Debug};
 use core::iter::{FromIterator, FusedIterator, Peekable};
-use core::ops::{BitAnd, BitOr, BitXor, RangeBounds, Sub};
+use core::ops::{BitAnd, BitOr, BitXor, Bound, RangeBounds, Sub};
 
 use super::map::{BTreeMap, Keys};
 use super::Recover;
@@ -114,6 +114,38 @@ pub struct Range<'a, T: 'a> {
     iter: super::map::Range<'a, T, ()>,
 }
 
+/// A cursor over a `BTreeSet`.
+///
+/// Cursors always point to a gap between two elements in the set, and can
+/// operate on the two immediately adjacent elements.
+///
+/// This `struct` is created by the [`lower_bound`] and [`upper_bound`] methods on
+/// [`BTreeSet`]. See their documentation for more.
+///
+/// [`lower_bound`]: BTreeSet::lower_bound
+/// [`upper_bound`]: BTreeSet::upper_bound
+#[derive(Debug)]
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct Cursor<'a, T: 'a> {
+    inner: super::map::Cursor<'a, T, ()>,
+}
+
+/// A cursor over a `BTreeSet` with editing operations.
+///
+/// Cursors always point to a gap between two elements in the set, and can
+/// operate on the two immediately adjacent elements.
+///
+/// This `struct` is created by the [`lower_bound_mut`] and [`upper_bound_mut`] methods
+/// on [`BTreeSet`]. See their documentation for more.
+///
+/// [`lower_bound_mut`]: BTreeSet::lower_bound_mut
+/// [`upper_bound_mut`]: BTreeSet::upper_bound_mut
+#[derive(Debug)]
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct CursorMut<'a, T: 'a> {
+    inner: super::map::CursorMut<'a, T, ()>,
+}
+
 /// Core of SymmetricDifference and Union.
 /// More efficient than btree.map.MergeIter,
 /// and crucially for SymmetricDifference, nexts() reports on both sides.
@@ -338,6 +370,116 @@ impl<T: Ord> BTreeSet<T> {
         Range { iter: self.map.range(range) }
     }
 
+    /// Returns a [`Cursor`] pointing at the gap before the first element that lies
+    /// above `bound`.
+    ///
+    /// See [`BTreeMap::lower_bound`] for the meaning of the bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::ops::Bound;
+    ///
+    /// let set: BTreeSet<_> = [1, 3, 5].iter().cloned().collect();
+    /// let cursor = set.lower_bound(Bound::Included(&2));
+    /// assert_eq!(cursor.peek_prev(), Some(&1));
+    /// assert_eq!(cursor.peek_next(), Some(&3));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound<K: ?Sized>(&self, bound: Bound<&K>) -> Cursor<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K>,
+    {
+        Cursor { inner: self.map.lower_bound(bound) }
+    }
+
+    /// Returns a [`Cursor`] pointing at the gap after the last element that lies
+    /// below `bound`.
+    ///
+    /// See [`BTreeMap::upper_bound`] for the meaning of the bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::ops::Bound;
+    ///
+    /// let set: BTreeSet<_> = [1, 3, 5].iter().cloned().collect();
+    /// let cursor = set.upper_bound(Bound::Included(&3));
+    /// assert_eq!(cursor.peek_prev(), Some(&3));
+    /// assert_eq!(cursor.peek_next(), Some(&5));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound<K: ?Sized>(&self, bound: Bound<&K>) -> Cursor<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K>,
+    {
+        Cursor { inner: self.map.upper_bound(bound) }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the gap before the first element that lies
+    /// above `bound`.
+    ///
+    /// See [`BTreeMap::lower_bound`] for the meaning of the bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::ops::Bound;
+    ///
+    /// let mut set: BTreeSet<_> = [1, 3, 5].iter().cloned().collect();
+    /// let mut cursor = set.lower_bound_mut(Bound::Included(&2));
+    /// cursor.insert_before(2);
+    /// assert_eq!(cursor.remove_next(), Some(3));
+    /// assert_eq!(set.into_iter().collect::<Vec<_>>(), [1, 2, 5]);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound_mut<K: ?Sized>(&mut self, bound: Bound<&K>) -> CursorMut<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K>,
+    {
+        CursorMut { inner: self.map.lower_bound_mut(bound) }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the gap after the last element that lies
+    /// below `bound`.
+    ///
+    /// See [`BTreeMap::upper_bound`] for the meaning of the bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::ops::Bound;
+    ///
+    /// let mut set: BTreeSet<_> = [1, 3, 5].iter().cloned().collect();
+    /// let mut cursor = set.upper_bound_mut(Bound::Excluded(&3));
+    /// assert_eq!(cursor.remove_prev(), Some(1));
+    /// assert_eq!(cursor.remove_prev(), None);
+    /// assert_eq!(set.into_iter().collect::<Vec<_>>(), [3, 5]);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound_mut<K: ?Sized>(&mut self, bound: Bound<&K>) -> CursorMut<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K>,
+    {
+        CursorMut { inner: self.map.upper_bound_mut(bound) }
+    }
+
     /// Visits the values representing the difference,
     /// i.e., the values that are in `self` but not in `other`,
     /// in ascending order.
@@ -1382,6 +1524,147 @@ impl<'a, T> DoubleEndedIterator for Range<'a, T> {
 #[stable(feature = "fused", since = "1.26.0")]
 impl<T> FusedIterator for Range<'_, T> {}
 
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        Cursor { inner: self.inner.clone() }
+    }
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Moves the cursor to the next gap, returning the element it moved over.
+    ///
+    /// If the cursor is already at the end of the set then `None` is returned and
+    /// the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    /// Moves the cursor to the previous gap, returning the element it moved over.
+    ///
+    /// If the cursor is already at the start of the set then `None` is returned and
+    /// the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn prev(&mut self) -> Option<&'a T> {
+        self.inner.prev().map(|(k, _)| k)
+    }
+
+    /// Returns the next element without moving the cursor.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&self) -> Option<&'a T> {
+        self.inner.peek_next().map(|(k, _)| k)
+    }
+
+    /// Returns the previous element without moving the cursor.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        self.inner.peek_prev().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Moves the cursor to the next gap, returning the element it moved over.
+    ///
+    /// If the cursor is already at the end of the set then `None` is returned and
+    /// the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn next(&mut self) -> Option<&T> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    /// Moves the cursor to the previous gap, returning the element it moved over.
+    ///
+    /// If the cursor is already at the start of the set then `None` is returned and
+    /// the cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn prev(&mut self) -> Option<&T> {
+        self.inner.prev().map(|(k, _)| k)
+    }
+
+    /// Returns the next element without moving the cursor.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&mut self) -> Option<&T> {
+        self.inner.peek_next().map(|(k, _)| &*k)
+    }
+
+    /// Returns the previous element without moving the cursor.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&mut self) -> Option<&T> {
+        self.inner.peek_prev().map(|(k, _)| &*k)
+    }
+
+    /// Returns a read-only cursor pointing at the same gap as this cursor.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor { inner: self.inner.as_cursor() }
+    }
+
+    /// Inserts a new element into the gap the cursor points to, leaving the cursor
+    /// pointing at the gap before the new element.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the new element is greater than the previous
+    /// element (if any) and less than the next element (if any).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_after_unchecked(&mut self, value: T) {
+        unsafe { self.inner.insert_after_unchecked(value, ()) }
+    }
+
+    /// Inserts a new element into the gap the cursor points to, leaving the cursor
+    /// pointing at the gap after the new element.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the new element is greater than the previous
+    /// element (if any) and less than the next element (if any).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_before_unchecked(&mut self, value: T) {
+        unsafe { self.inner.insert_before_unchecked(value, ()) }
+    }
+
+    /// Removes the next element from the set and returns it, or returns `None` if
+    /// the cursor is at the end of the set. The cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_next(&mut self) -> Option<T> {
+        self.inner.remove_next().map(|(k, _)| k)
+    }
+
+    /// Removes the previous element from the set and returns it, or returns `None` if
+    /// the cursor is at the start of the set. The cursor is not moved.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_prev(&mut self) -> Option<T> {
+        self.inner.remove_prev().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T: Ord> CursorMut<'a, T> {
+    /// Inserts a new element into the gap the cursor points to, leaving the cursor
+    /// pointing at the gap before the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new element is not greater than the previous element,
+    /// or not less than the next element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_after(&mut self, value: T) {
+        self.inner.insert_after(value, ())
+    }
+
+    /// Inserts a new element into the gap the cursor points to, leaving the cursor
+    /// pointing at the gap after the new element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new element is not greater than the previous element,
+    /// or not less than the next element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_before(&mut self, value: T) {
+        self.inner.insert_before(value, ())
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> Clone for Difference<'_, T> {
     fn clone(&self) -> Self {
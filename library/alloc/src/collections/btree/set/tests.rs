@@ -2,6 +2,7 @@ use super::super::DeterministicRng;
 use super::*;
 use crate::vec::Vec;
 use std::iter::FromIterator;
+use std::ops::Bound;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::atomic::{AtomicU32, Ordering};
 
@@ -722,3 +723,24 @@ fn test_split_off_large_random_sorted() {
     assert!(set.into_iter().eq(data.clone().into_iter().filter(|x| *x < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| *x >= key)));
 }
+
+#[test]
+fn test_cursor() {
+    let mut set: BTreeSet<_> = (0..100).filter(|x| x % 3 != 0).collect();
+
+    let mut cursor = set.lower_bound(Bound::Included(&30));
+    assert_eq!(cursor.peek_prev(), Some(&29));
+    assert_eq!(cursor.next(), Some(&31));
+    assert_eq!(cursor.prev(), Some(&31));
+
+    // Fill in the multiples of three while walking the set from the front.
+    let mut cursor = set.lower_bound_mut(Bound::Unbounded);
+    for x in (0..100).step_by(3) {
+        cursor.insert_before(x);
+        cursor.next();
+        cursor.next();
+    }
+    assert_eq!(cursor.as_cursor().peek_prev(), Some(&99));
+    assert_eq!(cursor.remove_prev(), Some(99));
+    assert!(set.iter().copied().eq(0..99));
+}
@@ -1,4 +1,7 @@
-use std::alloc::{AllocRef, Global, Layout, System};
+use std::alloc::{AllocError, AllocRef, Global, Layout, System};
+use std::cell::Cell;
+use std::collections::{BTreeMap, VecDeque};
+use std::ptr::NonNull;
 
 /// Issue #45955 and #62251.
 #[test]
@@ -42,3 +45,51 @@ fn check_overalign_requests<T: AllocRef>(allocator: T) {
         }
     }
 }
+
+/// Forwards to `System`, keeping track of the number of live allocations.
+#[derive(Default)]
+struct Counting {
+    live: Cell<usize>,
+}
+
+unsafe impl AllocRef for Counting {
+    fn alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = System.alloc(layout)?;
+        self.live.set(self.live.get() + 1);
+        Ok(ptr)
+    }
+
+    unsafe fn dealloc(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[test]
+fn collections_in_custom_allocator() {
+    let counting = Counting::default();
+    {
+        let mut vec = Vec::with_capacity_in(2, &counting);
+        vec.extend_from_slice(&[1, 2, 3, 4]);
+        let boxed = Box::new_in([5, 6], &counting);
+        assert_eq!(counting.live.get(), 2);
+
+        let mut deque = VecDeque::new_in(&counting);
+        deque.extend(vec.drain(..));
+        deque.push_front(0);
+        assert_eq!(deque, [0, 1, 2, 3, 4]);
+
+        let mut map = BTreeMap::new_in(&counting);
+        for i in 0..100 {
+            map.insert(i, *boxed);
+        }
+        let mut right = map.split_off(&50);
+        for i in (1..50).step_by(2) {
+            map.remove(&i);
+        }
+        right.append(&mut map.clone());
+        assert_eq!(right.len(), 75);
+        assert_eq!(right.into_iter().next(), Some((0, [5, 6])));
+    }
+    assert_eq!(counting.live.get(), 0);
+}
@@ -269,23 +269,27 @@ pub use self::buffered::{BufReader, BufWriter, LineWriter};
 pub use self::cursor::Cursor;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::error::{Error, ErrorKind, Result};
+#[unstable(feature = "read_buf", issue = "none")]
+pub use self::readbuf::ReadBuf;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::stdio::{stderr, stdin, stdout, Stderr, Stdin, Stdout};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::stdio::{StderrLock, StdinLock, StdoutLock};
 #[unstable(feature = "print_internals", issue = "none")]
 pub use self::stdio::{_eprint, _print};
-#[unstable(feature = "libstd_io_internals", issue = "42788")]
+#[unstable(feature = "internal_output_capture", issue = "none")]
 #[doc(no_inline, hidden)]
-pub use self::stdio::{set_panic, set_print};
+pub use self::stdio::set_output_capture;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::util::{copy, empty, repeat, sink, Empty, Repeat, Sink};
+pub(crate) use self::util::generic_copy;
 
 mod buffered;
 mod cursor;
 mod error;
 mod impls;
 pub mod prelude;
+mod readbuf;
 mod stdio;
 mod util;
 
@@ -347,8 +351,10 @@ where
 // time is 4,500 times (!) slower than a default reservation size of 32 if the
 // reader has a very small amount of data to return.
 //
-// Because we're extending the buffer with uninitialized data for trusted
-// readers, we need to make sure to truncate that if any of this panics.
+// The spare capacity of the vector is handed to the reader as a `ReadBuf`, so
+// readers implementing `read_buf` never see it zeroed. For all others we keep
+// track of how much of it was initialized by earlier iterations, so that each
+// byte is zeroed at most once.
 fn read_to_end<R: Read + ?Sized>(r: &mut R, buf: &mut Vec<u8>) -> Result<usize> {
     read_to_end_with_reservation(r, buf, |_| 32)
 }
@@ -364,31 +370,34 @@ where
 {
     let start_len = buf.len();
     let mut g = Guard { len: buf.len(), buf };
+    // The number of bytes past `g.len` that are known to be initialized.
+    let mut initialized = 0;
     let ret;
     loop {
-        if g.len == g.buf.len() {
-            unsafe {
-                // FIXME(danielhenrymantilla): #42788
-                //
-                //   - This creates a (mut) reference to a slice of
-                //     _uninitialized_ integers, which is **undefined behavior**
-                //
-                //   - Only the standard library gets to soundly "ignore" this,
-                //     based on its privileged knowledge of unstable rustc
-                //     internals;
-                g.buf.reserve(reservation_size(r));
-                let capacity = g.buf.capacity();
-                g.buf.set_len(capacity);
-                r.initializer().initialize(&mut g.buf[g.len..]);
-            }
+        if g.len == g.buf.capacity() {
+            g.buf.reserve(reservation_size(r));
+        }
+
+        let mut read_buf = ReadBuf::uninit(g.buf.spare_capacity_mut());
+        // SAFETY: these bytes were initialized, but not filled, by a previous read.
+        unsafe {
+            read_buf.assume_init(initialized);
         }
 
-        match r.read(&mut g.buf[g.len..]) {
-            Ok(0) => {
+        match r.read_buf(&mut read_buf) {
+            Ok(()) if read_buf.filled_len() == 0 => {
                 ret = Ok(g.len - start_len);
                 break;
             }
-            Ok(n) => g.len += n,
+            Ok(()) => {
+                let filled = read_buf.filled_len();
+                initialized = read_buf.initialized_len() - filled;
+                // SAFETY: `ReadBuf` guarantees that its filled region is initialized.
+                unsafe {
+                    g.buf.set_len(g.len + filled);
+                }
+                g.len += filled;
+            }
             Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
             Err(e) => {
                 ret = Err(e);
@@ -400,6 +409,15 @@ where
     ret
 }
 
+pub(crate) fn default_read_buf<F>(read: F, buf: &mut ReadBuf<'_>) -> Result<()>
+where
+    F: FnOnce(&mut [u8]) -> Result<usize>,
+{
+    let n = read(buf.initialize_unfilled())?;
+    buf.add_filled(n);
+    Ok(())
+}
+
 pub(crate) fn default_read_vectored<F>(read: F, bufs: &mut [IoSliceMut<'_>]) -> Result<usize>
 where
     F: FnOnce(&mut [u8]) -> Result<usize>,
@@ -612,6 +630,50 @@ pub trait Read {
         Initializer::zeroing()
     }
 
+    /// Pull some bytes from this source into the specified buffer.
+    ///
+    /// This is equivalent to the [`read`](Read::read) method, except that it is passed a
+    /// [`ReadBuf`] rather than `[u8]` to allow use with uninitialized buffers. The new data
+    /// will be appended to any existing contents of `buf`.
+    ///
+    /// The default implementation delegates to `read`, initializing the unfilled part of
+    /// `buf` first if necessary.
+    ///
+    /// Implementations must only ever write to the unfilled part of `buf`, must not
+    /// de-initialize any part of it, and must mark the bytes they read as filled.
+    ///
+    /// # Errors
+    ///
+    /// If this function encounters any form of I/O or other error, an error
+    /// variant will be returned. If an error is returned then it must be
+    /// guaranteed that no bytes were read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(read_buf)]
+    /// use std::io::{self, ReadBuf};
+    /// use std::io::prelude::*;
+    /// use std::fs::File;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut f = File::open("foo.txt")?;
+    ///     let mut buffer = [MaybeUninit::uninit(); 10];
+    ///     let mut buf = ReadBuf::uninit(&mut buffer);
+    ///
+    ///     // read up to 10 bytes, without zeroing the buffer first
+    ///     f.read_buf(&mut buf)?;
+    ///
+    ///     println!("The bytes: {:?}", buf.filled());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "read_buf", issue = "none")]
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<()> {
+        default_read_buf(|b| self.read(b), buf)
+    }
+
     /// Read all bytes until EOF in this source, placing them into `buf`.
     ///
     /// All bytes read from this source will be appended to the specified buffer
@@ -2154,6 +2216,18 @@ impl<T: Read, U: Read> Read for Chain<T, U> {
         self.second.read(buf)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<()> {
+        if !self.done_first {
+            let prev_filled = buf.filled_len();
+            self.first.read_buf(buf)?;
+            if buf.filled_len() != prev_filled || buf.remaining() == 0 {
+                return Ok(());
+            }
+            self.done_first = true;
+        }
+        self.second.read_buf(buf)
+    }
+
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
         if !self.done_first {
             match self.first.read_vectored(bufs)? {
@@ -2355,6 +2429,47 @@ impl<T: Read> Read for Take<T> {
         Ok(n)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<()> {
+        // Don't call into inner reader at all at EOF because it may still block
+        if self.limit == 0 {
+            return Ok(());
+        }
+
+        if self.limit <= buf.remaining() as u64 {
+            // `limit` fits in a `usize` here, since it is at most `buf.remaining()`.
+            let limit = self.limit as usize;
+            let extra_init = cmp::min(limit, buf.initialized_len() - buf.filled_len());
+
+            // SAFETY: no uninit data is written to `ibuf`
+            let ibuf = unsafe { &mut buf.unfilled_mut()[..limit] };
+            let mut sliced_buf = ReadBuf::uninit(ibuf);
+
+            // SAFETY: `extra_init` bytes of `ibuf` are known to be initialized
+            unsafe {
+                sliced_buf.assume_init(extra_init);
+            }
+
+            self.inner.read_buf(&mut sliced_buf)?;
+
+            let new_init = sliced_buf.initialized_len();
+            let filled = sliced_buf.filled_len();
+
+            // SAFETY: `new_init` bytes of the unfilled part of `buf` have been initialized
+            unsafe {
+                buf.assume_init(new_init);
+            }
+            buf.add_filled(filled);
+
+            self.limit -= filled as u64;
+        } else {
+            let prev_filled = buf.filled_len();
+            self.inner.read_buf(buf)?;
+            self.limit -= buf.filled_len().saturating_sub(prev_filled) as u64;
+        }
+
+        Ok(())
+    }
+
     unsafe fn initializer(&self) -> Initializer {
         self.inner.initializer()
     }
@@ -1,7 +1,8 @@
 use super::{repeat, Cursor, SeekFrom};
 use crate::cmp::{self, min};
 use crate::io::prelude::*;
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
+use crate::mem::MaybeUninit;
 use crate::ops::Deref;
 
 #[test]
@@ -92,6 +93,83 @@ fn read_to_end() {
     assert_eq!(v, data);
 }
 
+#[test]
+fn read_buf_default() {
+    // A reader that only implements `read`, so it gets the default `read_buf`.
+    struct R(u8);
+
+    impl Read for R {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = cmp::min(buf.len(), 3);
+            for b in &mut buf[..n] {
+                *b = self.0;
+                self.0 += 1;
+            }
+            Ok(n)
+        }
+    }
+
+    let mut buf = [MaybeUninit::uninit(); 8];
+    let mut buf = ReadBuf::uninit(&mut buf);
+    let mut r = R(0);
+
+    r.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), [0, 1, 2]);
+    assert_eq!(buf.initialized_len(), 8);
+
+    r.read_buf(&mut buf).unwrap();
+    r.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), [0, 1, 2, 3, 4, 5, 6, 7]);
+
+    r.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled_len(), 8);
+}
+
+#[test]
+fn read_buf_leaves_memory_uninit() {
+    // Readers that don't need initialized memory fill `buf` without zeroing the rest of it.
+    let mut buf = [MaybeUninit::uninit(); 8];
+    let mut buf = ReadBuf::uninit(&mut buf);
+
+    Cursor::new(&[1u8, 2][..]).read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), [1, 2]);
+    assert_eq!(buf.initialized_len(), 2);
+
+    (&[3u8][..]).chain(&[4u8, 5][..]).read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), [1, 2, 3]);
+    assert_eq!(buf.initialized_len(), 3);
+
+    repeat(6).take(2).read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), [1, 2, 3, 6, 6]);
+    assert_eq!(buf.initialized_len(), 5);
+
+    io::empty().read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled_len(), 5);
+
+    repeat(7).read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), [1, 2, 3, 6, 6, 7, 7, 7]);
+}
+
+#[test]
+fn take_read_buf() {
+    let mut buf = [MaybeUninit::uninit(); 4];
+    let mut buf = ReadBuf::uninit(&mut buf);
+    let mut take = (&[1u8, 2, 3, 4, 5, 6][..]).take(5);
+
+    take.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), [1, 2, 3, 4]);
+    assert_eq!(take.limit(), 1);
+
+    buf.clear();
+    take.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), [5]);
+    assert_eq!(take.limit(), 0);
+
+    buf.clear();
+    take.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled_len(), 0);
+}
+
 #[test]
 fn read_to_string() {
     let mut c = Cursor::new(&b""[..]);
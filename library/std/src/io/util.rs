@@ -4,7 +4,7 @@
 mod tests;
 
 use crate::fmt;
-use crate::io::{self, BufRead, ErrorKind, Initializer, IoSlice, IoSliceMut, Read, Write};
+use crate::io::{self, BufRead, ErrorKind, Initializer, IoSlice, IoSliceMut, Read, ReadBuf, Write};
 use crate::mem::MaybeUninit;
 
 /// Copies the entire contents of a reader into a writer.
@@ -30,6 +30,26 @@ use crate::mem::MaybeUninit;
 /// [`read`]: Read::read
 /// [`write`]: Write::write
 ///
+/// # Platform-specific behavior
+///
+/// On Linux (including Android), this function uses `copy_file_range(2)`,
+/// `sendfile(2)` or `splice(2)` syscalls to move data directly between file
+/// descriptors if possible. This applies when both `reader` and `writer` are
+/// file-descriptor-backed standard library types such as [`File`],
+/// [`TcpStream`], `UnixStream`, child process pipes or locked standard
+/// streams, also when wrapped in [`BufReader`], [`BufWriter`] or [`Take`].
+/// If the syscalls are unavailable or not applicable it falls back to the
+/// generic read-write loop.
+///
+/// Note that platform-specific behavior [may change in the future][changes].
+///
+/// [`File`]: crate::fs::File
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`BufReader`]: crate::io::BufReader
+/// [`BufWriter`]: crate::io::BufWriter
+/// [`Take`]: crate::io::Take
+/// [changes]: crate::io#platform-specific-behavior
+///
 /// # Examples
 ///
 /// ```
@@ -51,29 +71,37 @@ where
     R: Read,
     W: Write,
 {
-    let mut buf = MaybeUninit::<[u8; super::DEFAULT_BUF_SIZE]>::uninit();
-    // FIXME: #42788
-    //
-    //   - This creates a (mut) reference to a slice of
-    //     _uninitialized_ integers, which is **undefined behavior**
-    //
-    //   - Only the standard library gets to soundly "ignore" this,
-    //     based on its privileged knowledge of unstable rustc
-    //     internals;
-    unsafe {
-        reader.initializer().initialize(buf.assume_init_mut());
+    cfg_if::cfg_if! {
+        if #[cfg(any(target_os = "linux", target_os = "android"))] {
+            crate::sys::kernel_copy::copy_spec(reader, writer)
+        } else {
+            generic_copy(reader, writer)
+        }
     }
+}
+
+/// The general read-write-loop implementation of `io::copy` that is used when
+/// specializations are not available or not applicable.
+pub(crate) fn generic_copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<u64>
+where
+    R: Read,
+    W: Write,
+{
+    let mut buf = [MaybeUninit::<u8>::uninit(); super::DEFAULT_BUF_SIZE];
+    // The buffer is only zeroed (once) if `reader` does not implement `read_buf`.
+    let mut buf = ReadBuf::uninit(&mut buf);
 
     let mut written = 0;
     loop {
-        let len = match reader.read(unsafe { buf.assume_init_mut() }) {
-            Ok(0) => return Ok(written),
-            Ok(len) => len,
+        match reader.read_buf(&mut buf) {
+            Ok(()) if buf.filled_len() == 0 => return Ok(written),
+            Ok(()) => {}
             Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
             Err(e) => return Err(e),
         };
-        writer.write_all(unsafe { &buf.assume_init_ref()[..len] })?;
-        written += len as u64;
+        writer.write_all(buf.filled())?;
+        written += buf.filled_len() as u64;
+        buf.clear();
     }
 }
 
@@ -113,6 +141,11 @@ impl Read for Empty {
         Ok(0)
     }
 
+    #[inline]
+    fn read_buf(&mut self, _buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        Ok(())
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -173,6 +206,25 @@ impl Read for Repeat {
         Ok(buf.len())
     }
 
+    #[inline]
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        // SAFETY: no uninit bytes are being written
+        for slot in unsafe { buf.unfilled_mut() } {
+            slot.write(self.byte);
+        }
+
+        let remaining = buf.remaining();
+
+        // SAFETY: the entire unfilled portion of buf has been initialized
+        unsafe {
+            buf.assume_init(remaining);
+        }
+
+        buf.add_filled(remaining);
+
+        Ok(())
+    }
+
     #[inline]
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         let mut nwritten = 0;
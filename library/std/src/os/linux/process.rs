@@ -0,0 +1,164 @@
+//! Linux-specific extensions to primitives in the `std::process` module.
+
+#![unstable(feature = "linux_pidfd", issue = "none")]
+
+use crate::io::{self, Error, ErrorKind};
+use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::process;
+use crate::sys::fd::FileDesc;
+use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+
+/// This type represents a file descriptor that refers to a process.
+///
+/// A `PidFd` can be obtained by setting the corresponding option on
+/// [`Command`] with [`create_pidfd`]. Subsequently, the created pidfd can be
+/// retrieved from the [`Child`] by calling [`pidfd`] or [`take_pidfd`].
+///
+/// Unlike a process ID, a pidfd keeps referring to the same process for as
+/// long as it is open, so it can be used to signal or wait on a child
+/// without racing against PID reuse. The descriptor becomes readable once
+/// the process has exited, which makes it usable with `poll` or `epoll`.
+///
+/// Pidfds require Linux 5.3 or later.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_pidfd)]
+/// use std::os::linux::process::{ChildExt, CommandExt};
+/// use std::process::Command;
+///
+/// let mut child = Command::new("echo")
+///     .create_pidfd(true)
+///     .spawn()
+///     .expect("failed to spawn echo");
+///
+/// let pidfd = child.take_pidfd().expect("failed to retrieve pidfd");
+/// ```
+///
+/// [`Command`]: process::Command
+/// [`create_pidfd`]: CommandExt::create_pidfd
+/// [`Child`]: process::Child
+/// [`pidfd`]: ChildExt::pidfd
+/// [`take_pidfd`]: ChildExt::take_pidfd
+#[unstable(feature = "linux_pidfd", issue = "none")]
+#[derive(Debug)]
+pub struct PidFd {
+    inner: FileDesc,
+}
+
+impl AsInner<FileDesc> for PidFd {
+    fn as_inner(&self) -> &FileDesc {
+        &self.inner
+    }
+}
+
+impl FromInner<FileDesc> for PidFd {
+    fn from_inner(inner: FileDesc) -> PidFd {
+        PidFd { inner }
+    }
+}
+
+impl IntoInner<FileDesc> for PidFd {
+    fn into_inner(self) -> FileDesc {
+        self.inner
+    }
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl AsRawFd for PidFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.raw()
+    }
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl FromRawFd for PidFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> PidFd {
+        PidFd { inner: FileDesc::new(fd) }
+    }
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl IntoRawFd for PidFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_raw()
+    }
+}
+
+/// Linux-specific extensions to [`process::Child`].
+#[unstable(feature = "linux_pidfd", issue = "none")]
+pub trait ChildExt {
+    /// Obtains a reference to the [`PidFd`] created for this [`Child`], if
+    /// available.
+    ///
+    /// A pidfd will only be available if its creation was requested with
+    /// [`create_pidfd`] when the corresponding [`Command`] was created.
+    ///
+    /// Even if requested, a pidfd may not be available due to an older
+    /// version of Linux being in use, or if some other error occurred.
+    ///
+    /// [`Command`]: process::Command
+    /// [`create_pidfd`]: CommandExt::create_pidfd
+    /// [`Child`]: process::Child
+    fn pidfd(&self) -> io::Result<&PidFd>;
+
+    /// Takes ownership of the [`PidFd`] created for this [`Child`], if
+    /// available.
+    ///
+    /// Once taken, the pidfd is no longer used by [`Child::wait_timeout`],
+    /// which falls back to polling the child.
+    ///
+    /// [`Child`]: process::Child
+    /// [`Child::wait_timeout`]: process::Child::wait_timeout
+    fn take_pidfd(&mut self) -> io::Result<PidFd>;
+}
+
+/// Linux-specific extensions to the [`process::Command`] builder.
+#[unstable(feature = "linux_pidfd", issue = "none")]
+pub trait CommandExt {
+    /// Sets whether a [`PidFd`] should be created for the [`Child`]
+    /// spawned by this [`Command`].
+    /// By default, no pidfd will be created.
+    ///
+    /// The pidfd can be retrieved from the child with [`pidfd`] or
+    /// [`take_pidfd`].
+    ///
+    /// The pidfd is created atomically with the child by the `clone3`
+    /// syscall. If `clone3` is unavailable, e.g. because it is blocked by a
+    /// seccomp filter, the pidfd is opened with `pidfd_open` right after the
+    /// child has been spawned instead. If the child exits and is reaped in
+    /// between, because `SIGCHLD` is ignored or another thread waits on any
+    /// child, its process ID may be reused and the pidfd may refer to another
+    /// process.
+    ///
+    /// [`Command`]: process::Command
+    /// [`Command::spawn`]: process::Command::spawn
+    /// [`Child`]: process::Child
+    /// [`pidfd`]: ChildExt::pidfd
+    /// [`take_pidfd`]: ChildExt::take_pidfd
+    fn create_pidfd(&mut self, val: bool) -> &mut process::Command;
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl CommandExt for process::Command {
+    fn create_pidfd(&mut self, val: bool) -> &mut process::Command {
+        self.as_inner_mut().create_pidfd(val);
+        self
+    }
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl ChildExt for process::Child {
+    fn pidfd(&self) -> io::Result<&PidFd> {
+        self.as_inner().pidfd().ok_or_else(no_pidfd)
+    }
+
+    fn take_pidfd(&mut self) -> io::Result<PidFd> {
+        self.as_inner_mut().take_pidfd().ok_or_else(no_pidfd)
+    }
+}
+
+fn no_pidfd() -> Error {
+    Error::new(ErrorKind::Other, "no pidfd was created for this child")
+}
@@ -57,6 +57,77 @@ fn signal_reported_right() {
     }
 }
 
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks", target_os = "android"), ignore)]
+fn wait_timeout_reports_running_and_exited_children() {
+    use crate::time::Duration;
+
+    let mut p =
+        Command::new("/bin/sh").arg("-c").arg("read a").stdin(Stdio::piped()).spawn().unwrap();
+    assert!(p.wait_timeout(Duration::from_millis(20)).unwrap().is_none());
+    p.kill().unwrap();
+    let status = p.wait_timeout(Duration::from_secs(60)).unwrap();
+    assert!(status.is_some());
+    assert_eq!(p.wait().unwrap(), status.unwrap());
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks", target_os = "android"), ignore)]
+fn process_group_puts_child_in_new_group() {
+    use crate::os::unix::process::CommandExt;
+
+    let mut p = Command::new("/bin/sh")
+        .arg("-c")
+        .arg("read a")
+        .stdin(Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .unwrap();
+    let pid = p.id() as libc::pid_t;
+    let pgid = unsafe { libc::getpgid(pid) };
+    p.kill().unwrap();
+    p.wait().unwrap();
+    assert_eq!(pgid, pid);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn pidfd_is_created_and_waited_on() {
+    use crate::os::linux::process::{ChildExt, CommandExt};
+    use crate::os::unix::io::AsRawFd;
+    use crate::time::Duration;
+
+    let mut p = Command::new("/bin/sh")
+        .arg("-c")
+        .arg("read a")
+        .stdin(Stdio::piped())
+        .create_pidfd(true)
+        .spawn()
+        .unwrap();
+    match p.pidfd() {
+        Ok(pidfd) => assert!(pidfd.as_raw_fd() >= 0),
+        // pidfd_open is only available on Linux 5.3+
+        Err(_) => {
+            p.kill().unwrap();
+            p.wait().unwrap();
+            return;
+        }
+    }
+    assert!(p.wait_timeout(Duration::from_millis(20)).unwrap().is_none());
+    p.kill().unwrap();
+    assert!(p.wait_timeout(Duration::from_secs(60)).unwrap().is_some());
+
+    let pidfd = p.take_pidfd().unwrap();
+    assert!(p.pidfd().is_err());
+    drop(pidfd);
+
+    let mut p = Command::new("true").spawn().unwrap();
+    assert!(p.pidfd().is_err());
+    p.wait().unwrap();
+}
+
 pub fn run_output(mut cmd: Command) -> String {
     let p = cmd.spawn();
     assert!(p.is_ok());
@@ -15,6 +15,8 @@ pub struct RWLock {
     lock: AtomicU32,
 }
 
+pub type MovableRWLock = RWLock;
+
 pub unsafe fn raw(r: &RWLock) -> &AtomicU32 {
     &r.lock
 }
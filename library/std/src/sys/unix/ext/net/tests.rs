@@ -1,5 +1,5 @@
 use crate::io::prelude::*;
-use crate::io::{self, ErrorKind};
+use crate::io::{self, ErrorKind, IoSlice, IoSliceMut};
 use crate::sys_common::io::test::tmpdir;
 use crate::thread;
 use crate::time::Duration;
@@ -452,3 +452,330 @@ fn test_unix_datagram_peek_from() {
     assert_eq!(size, 11);
     assert_eq!(msg, &buf[..]);
 }
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "emscripten",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[test]
+fn test_send_vectored_fds_unix_stream() {
+    let (s1, s2) = or_panic!(UnixStream::pair());
+
+    let buf1 = [1; 8];
+    let bufs_send = &[IoSlice::new(&buf1[..])][..];
+
+    let mut ancillary1_buffer = [0; 128];
+    let mut ancillary1 = SocketAncillary::new(&mut ancillary1_buffer[..]);
+    assert!(ancillary1.add_fds(&[s1.as_raw_fd()][..]));
+
+    let usize = or_panic!(s1.send_vectored_with_ancillary(bufs_send, &mut ancillary1));
+    assert_eq!(usize, 8);
+
+    let mut buf2 = [0; 8];
+    let bufs_recv = &mut [IoSliceMut::new(&mut buf2[..])][..];
+
+    let mut ancillary2_buffer = [0; 128];
+    let mut ancillary2 = SocketAncillary::new(&mut ancillary2_buffer[..]);
+
+    let usize = or_panic!(s2.recv_vectored_with_ancillary(bufs_recv, &mut ancillary2));
+    assert_eq!(usize, 8);
+    assert_eq!(buf1, buf2);
+    assert!(!ancillary2.truncated());
+
+    let mut ancillary_data_vec = ancillary2.messages().collect::<Vec<_>>();
+    assert_eq!(ancillary_data_vec.len(), 1);
+    if let AncillaryData::ScmRights(scm_rights) = ancillary_data_vec.pop().unwrap().unwrap() {
+        let fd_vec = scm_rights.collect::<Vec<_>>();
+        assert_eq!(fd_vec.len(), 1);
+        // the received descriptor is a new one referring to the same socket
+        let received = unsafe { UnixStream::from_raw_fd(fd_vec[0]) };
+        assert!(or_panic!(received.local_addr()).is_unnamed());
+        assert_ne!(fd_vec[0], s1.as_raw_fd());
+    } else {
+        unreachable!("must be ScmRights");
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_send_vectored_with_ancillary_to_unix_datagram() {
+    fn getpid() -> libc::pid_t {
+        unsafe { libc::getpid() }
+    }
+
+    fn getuid() -> libc::uid_t {
+        unsafe { libc::getuid() }
+    }
+
+    fn getgid() -> libc::gid_t {
+        unsafe { libc::getgid() }
+    }
+
+    let dir = tmpdir();
+    let path1 = dir.path().join("sock1");
+    let path2 = dir.path().join("sock2");
+
+    let bsock1 = or_panic!(UnixDatagram::bind(&path1));
+    let bsock2 = or_panic!(UnixDatagram::bind(&path2));
+
+    or_panic!(bsock2.set_passcred(true));
+    assert!(or_panic!(bsock2.passcred()));
+
+    let buf1 = [1; 8];
+    let bufs_send = &[IoSlice::new(&buf1[..])][..];
+
+    let mut ancillary1_buffer = [0; 128];
+    let mut ancillary1 = SocketAncillary::new(&mut ancillary1_buffer[..]);
+    let mut cred1 = SocketCred::new();
+    cred1.set_pid(getpid());
+    cred1.set_uid(getuid());
+    cred1.set_gid(getgid());
+    assert!(ancillary1.add_creds(&[cred1.clone()][..]));
+
+    let usize =
+        or_panic!(bsock1.send_vectored_with_ancillary_to(bufs_send, &mut ancillary1, &path2));
+    assert_eq!(usize, 8);
+
+    let mut buf2 = [0; 8];
+    let bufs_recv = &mut [IoSliceMut::new(&mut buf2[..])][..];
+
+    let mut ancillary2_buffer = [0; 128];
+    let mut ancillary2 = SocketAncillary::new(&mut ancillary2_buffer[..]);
+
+    let (usize, truncated, addr) =
+        or_panic!(bsock2.recv_vectored_with_ancillary_from(bufs_recv, &mut ancillary2));
+    assert_eq!(ancillary2.truncated(), false);
+    assert_eq!(usize, 8);
+    assert_eq!(truncated, false);
+    assert_eq!(addr.as_pathname(), Some(&*path1));
+    assert_eq!(buf1, buf2);
+
+    let mut ancillary_data_vec = ancillary2.messages().collect::<Vec<_>>();
+    assert_eq!(ancillary_data_vec.len(), 1);
+    if let AncillaryData::ScmCredentials(scm_credentials) =
+        ancillary_data_vec.pop().unwrap().unwrap()
+    {
+        let cred_vec = scm_credentials.collect::<Vec<_>>();
+        assert_eq!(cred_vec.len(), 1);
+        assert_eq!(cred1.get_pid(), cred_vec[0].get_pid());
+        assert_eq!(cred1.get_uid(), cred_vec[0].get_uid());
+        assert_eq!(cred1.get_gid(), cred_vec[0].get_gid());
+    } else {
+        unreachable!("must be ScmCredentials");
+    }
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "emscripten",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[test]
+fn test_send_vectored_with_ancillary_unix_datagram() {
+    let dir = tmpdir();
+    let path1 = dir.path().join("sock1");
+    let path2 = dir.path().join("sock2");
+
+    let bsock1 = or_panic!(UnixDatagram::bind(&path1));
+    let bsock2 = or_panic!(UnixDatagram::bind(&path2));
+
+    let buf1 = [1; 8];
+    let bufs_send = &[IoSlice::new(&buf1[..])][..];
+
+    let mut ancillary1_buffer = [0; 128];
+    let mut ancillary1 = SocketAncillary::new(&mut ancillary1_buffer[..]);
+    assert!(ancillary1.add_fds(&[bsock1.as_raw_fd()][..]));
+
+    or_panic!(bsock1.connect(&path2));
+    let usize = or_panic!(bsock1.send_vectored_with_ancillary(bufs_send, &mut ancillary1));
+    assert_eq!(usize, 8);
+
+    let mut buf2 = [0; 8];
+    let bufs_recv = &mut [IoSliceMut::new(&mut buf2[..])][..];
+
+    let mut ancillary2_buffer = [0; 128];
+    let mut ancillary2 = SocketAncillary::new(&mut ancillary2_buffer[..]);
+
+    let (usize, truncated) =
+        or_panic!(bsock2.recv_vectored_with_ancillary(bufs_recv, &mut ancillary2));
+    assert_eq!(usize, 8);
+    assert_eq!(truncated, false);
+    assert_eq!(buf1, buf2);
+
+    let mut ancillary_data_vec = ancillary2.messages().collect::<Vec<_>>();
+    assert_eq!(ancillary_data_vec.len(), 1);
+    if let AncillaryData::ScmRights(scm_rights) = ancillary_data_vec.pop().unwrap().unwrap() {
+        let fd_vec = scm_rights.collect::<Vec<_>>();
+        assert_eq!(fd_vec.len(), 1);
+        unsafe {
+            libc::close(fd_vec[0]);
+        }
+    } else {
+        unreachable!("must be ScmRights");
+    }
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "emscripten",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[test]
+fn test_socket_ancillary_capacity() {
+    let mut buffer = [0; 8];
+    let mut ancillary = SocketAncillary::new(&mut buffer[..]);
+    assert_eq!(ancillary.capacity(), 8);
+    assert!(ancillary.is_empty());
+
+    // a control message header does not even fit into the buffer
+    assert!(!ancillary.add_fds(&[0][..]));
+    assert!(ancillary.is_empty());
+    assert_eq!(ancillary.messages().count(), 0);
+
+    let mut buffer = [0; 128];
+    let mut ancillary = SocketAncillary::new(&mut buffer[..]);
+    assert!(ancillary.add_fds(&[0, 1][..]));
+    assert!(ancillary.add_fds(&[2][..]));
+    assert!(!ancillary.is_empty());
+    assert_eq!(ancillary.messages().count(), 2);
+
+    ancillary.clear();
+    assert!(ancillary.is_empty());
+    assert_eq!(ancillary.len(), 0);
+}
+
+// Abstract socket names are shared by the whole network namespace, so include
+// the pid to keep concurrent test runs from clashing.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn abstract_name(name: &str) -> Vec<u8> {
+    format!("rust-std-test-{}-{}", crate::process::id(), name).into_bytes()
+}
+
+#[test]
+fn test_socket_addr_from_pathname() {
+    let addr = or_panic!(SocketAddr::from_pathname("/tmp/sock"));
+    assert_eq!(addr.as_pathname(), Some(crate::path::Path::new("/tmp/sock")));
+    assert!(!addr.is_unnamed());
+
+    assert!(SocketAddr::from_pathname("/tmp/\0sock").is_err());
+    assert!(SocketAddr::from_pathname("a".repeat(256)).is_err());
+}
+
+#[test]
+fn test_unix_stream_connect_addr() {
+    let dir = tmpdir();
+    let socket_path = dir.path().join("sock");
+    let msg = b"hello";
+
+    let socket_addr = or_panic!(SocketAddr::from_pathname(&socket_path));
+    let listener = or_panic!(UnixListener::bind_addr(&socket_addr));
+    let addr = or_panic!(listener.local_addr());
+    assert_eq!(addr.as_pathname(), Some(&*socket_path));
+
+    let thread = thread::spawn(move || {
+        let mut stream = or_panic!(listener.accept()).0;
+        or_panic!(stream.write_all(msg));
+    });
+
+    let mut stream = or_panic!(UnixStream::connect_addr(&addr));
+    let mut buf = vec![];
+    or_panic!(stream.read_to_end(&mut buf));
+    assert_eq!(&msg[..], &buf[..]);
+    thread.join().unwrap();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_abstract_stream_connect() {
+    let msg1 = b"hello";
+    let msg2 = b"world";
+
+    let name = abstract_name("stream");
+    let socket_addr = or_panic!(SocketAddr::from_abstract_namespace(&name));
+    let listener = or_panic!(UnixListener::bind_addr(&socket_addr));
+
+    let thread = thread::spawn(move || {
+        let mut stream = or_panic!(listener.accept()).0;
+        let mut buf = [0; 5];
+        or_panic!(stream.read(&mut buf));
+        assert_eq!(&msg1[..], &buf[..]);
+        or_panic!(stream.write_all(msg2));
+    });
+
+    let mut stream = or_panic!(UnixStream::connect_addr(&socket_addr));
+
+    let peer = or_panic!(stream.peer_addr());
+    assert_eq!(peer.as_abstract_namespace(), Some(&name[..]));
+    assert_eq!(peer.as_pathname(), None);
+    assert!(!peer.is_unnamed());
+
+    or_panic!(stream.write_all(msg1));
+    let mut buf = vec![];
+    or_panic!(stream.read_to_end(&mut buf));
+    assert_eq!(&msg2[..], &buf[..]);
+    drop(stream);
+
+    thread.join().unwrap();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_abstract_datagram_bind_send_to_addr() {
+    let name1 = abstract_name("datagram1");
+    let addr1 = or_panic!(SocketAddr::from_abstract_namespace(&name1));
+    let sock1 = or_panic!(UnixDatagram::bind_addr(&addr1));
+
+    let local = or_panic!(sock1.local_addr());
+    assert_eq!(local.as_abstract_namespace(), Some(&name1[..]));
+
+    let name2 = abstract_name("datagram2");
+    let addr2 = or_panic!(SocketAddr::from_abstract_namespace(&name2));
+    let sock2 = or_panic!(UnixDatagram::bind_addr(&addr2));
+
+    let msg = b"hello world";
+    or_panic!(sock1.send_to_addr(msg, &addr2));
+    let mut buf = [0; 11];
+    let (len, addr) = or_panic!(sock2.recv_from(&mut buf));
+    assert_eq!(msg, &buf[..]);
+    assert_eq!(len, 11);
+    assert_eq!(addr.as_abstract_namespace(), Some(&name1[..]));
+
+    or_panic!(sock2.connect_addr(&addr1));
+    or_panic!(sock2.send(msg));
+    let mut buf = [0; 11];
+    or_panic!(sock1.recv(&mut buf));
+    assert_eq!(msg, &buf[..]);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_abstract_namespace_in_use() {
+    // Abstract sockets disappear with their last file descriptor, so the name
+    // can be rebound right away without any cleanup.
+    let name = abstract_name("in-use");
+    let addr = or_panic!(SocketAddr::from_abstract_namespace(&name));
+    let listener = or_panic!(UnixListener::bind_addr(&addr));
+    assert!(UnixListener::bind_addr(&addr).is_err());
+    drop(listener);
+    or_panic!(UnixListener::bind_addr(&addr));
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_abstract_namespace_too_long() {
+    assert!(SocketAddr::from_abstract_namespace(&[b'a'; 107][..]).is_ok());
+    assert!(SocketAddr::from_abstract_namespace(&[b'a'; 108][..]).is_err());
+}
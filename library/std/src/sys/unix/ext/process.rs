@@ -119,6 +119,33 @@ pub trait CommandExt {
     fn arg0<S>(&mut self, arg: S) -> &mut process::Command
     where
         S: AsRef<OsStr>;
+
+    /// Sets the process group ID of the child process. This translates to a
+    /// `setpgid` call in the child process, or to `POSIX_SPAWN_SETPGROUP`
+    /// when the child is created with `posix_spawn`.
+    ///
+    /// A `pgroup` of 0 puts the child into a new process group whose ID is
+    /// the child's process ID, so that the child and all of its descendants
+    /// can later be signalled at once with `kill(-pid, sig)`. Any other value
+    /// moves the child into that existing process group.
+    ///
+    /// Failure in the `setpgid` call will cause the spawn to fail.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_set_process_group)]
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// Command::new("sleep")
+    ///     .arg("10")
+    ///     .process_group(0)
+    ///     .spawn()
+    ///     .expect("failed to spawn sleep");
+    /// ```
+    #[unstable(feature = "process_set_process_group", issue = "none")]
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -160,6 +187,11 @@ impl CommandExt for process::Command {
         self.as_inner_mut().set_arg_0(arg.as_ref());
         self
     }
+
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
+        self.as_inner_mut().pgroup(pgroup);
+        self
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`].
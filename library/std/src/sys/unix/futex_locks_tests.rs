@@ -0,0 +1,150 @@
+use super::{condvar, mutex, pthread_mutex, pthread_rwlock, rwlock};
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering::Relaxed};
+use crate::thread;
+
+const THREADS: usize = 4;
+const ITERATIONS: usize = 1000;
+
+// Runs `f` `ITERATIONS` times on each of `THREADS` threads at the same time.
+fn contend(f: impl Fn() + Sync) {
+    thread::scope(|s| {
+        for _ in 0..THREADS {
+            s.spawn(|| {
+                for _ in 0..ITERATIONS {
+                    f();
+                }
+            });
+        }
+    });
+}
+
+// Not an atomic increment, so increments get lost without mutual exclusion.
+fn increment(count: &AtomicUsize) {
+    count.store(count.load(Relaxed) + 1, Relaxed);
+}
+
+#[test]
+fn futex_mutex_is_exclusive() {
+    let m = mutex::Mutex::new();
+    let count = AtomicUsize::new(0);
+    contend(|| unsafe {
+        m.lock();
+        increment(&count);
+        m.unlock();
+    });
+    assert_eq!(count.load(Relaxed), THREADS * ITERATIONS);
+}
+
+#[test]
+fn futex_mutex_try_lock() {
+    let m = mutex::Mutex::new();
+    unsafe {
+        assert!(m.try_lock());
+        assert!(!m.try_lock());
+        m.unlock();
+        assert!(m.try_lock());
+        m.unlock();
+    }
+}
+
+#[test]
+fn futex_condvar_notify_one() {
+    let m = mutex::Mutex::new();
+    let c = condvar::Condvar::new();
+    let ready = AtomicBool::new(false);
+    thread::scope(|s| unsafe {
+        m.lock();
+        s.spawn(|| {
+            m.lock();
+            ready.store(true, Relaxed);
+            c.notify_one();
+            m.unlock();
+        });
+        while !ready.load(Relaxed) {
+            c.wait(&m);
+        }
+        m.unlock();
+    });
+}
+
+#[test]
+fn futex_rwlock_is_exclusive() {
+    let l = rwlock::RWLock::new();
+    let count = AtomicUsize::new(0);
+    contend(|| unsafe {
+        l.write();
+        increment(&count);
+        l.write_unlock();
+        l.read();
+        assert!(count.load(Relaxed) > 0);
+        l.read_unlock();
+    });
+    assert_eq!(count.load(Relaxed), THREADS * ITERATIONS);
+}
+
+#[test]
+fn futex_rwlock_try_read_and_write() {
+    let l = rwlock::RWLock::new();
+    unsafe {
+        assert!(l.try_read());
+        assert!(l.try_read());
+        assert!(!l.try_write());
+        l.read_unlock();
+        l.read_unlock();
+        assert!(l.try_write());
+        assert!(!l.try_read());
+        assert!(!l.try_write());
+        l.write_unlock();
+    }
+}
+
+#[bench]
+fn bench_futex_mutex_contended(b: &mut test::Bencher) {
+    let m = mutex::Mutex::new();
+    b.iter(|| {
+        contend(|| unsafe {
+            m.lock();
+            m.unlock();
+        })
+    });
+}
+
+#[bench]
+fn bench_pthread_mutex_contended(b: &mut test::Bencher) {
+    let mut m = Box::new(pthread_mutex::Mutex::new());
+    unsafe { m.init() };
+    b.iter(|| {
+        contend(|| unsafe {
+            m.lock();
+            m.unlock();
+        })
+    });
+    unsafe { m.destroy() };
+}
+
+#[bench]
+fn bench_futex_rwlock_contended(b: &mut test::Bencher) {
+    let l = rwlock::RWLock::new();
+    b.iter(|| {
+        contend(|| unsafe {
+            l.read();
+            l.read_unlock();
+            l.write();
+            l.write_unlock();
+        })
+    });
+}
+
+#[bench]
+fn bench_pthread_rwlock_contended(b: &mut test::Bencher) {
+    let l = Box::new(pthread_rwlock::RWLock::new());
+    b.iter(|| {
+        contend(|| unsafe {
+            l.read();
+            l.read_unlock();
+            l.write();
+            l.write_unlock();
+        })
+    });
+    unsafe { l.destroy() };
+}
@@ -0,0 +1,151 @@
+use crate::fs::OpenOptions;
+use crate::io;
+use crate::io::{BufRead, BufReader, BufWriter, Read, Result, Seek, SeekFrom, Write};
+use crate::os::unix::net::UnixStream;
+use crate::sys_common::io::test::tmpdir;
+use crate::thread;
+
+fn create_file(path: &crate::path::Path, data: &[u8]) {
+    OpenOptions::new().create(true).write(true).open(path).unwrap().write_all(data).unwrap();
+}
+
+#[test]
+fn copy_specialization() -> Result<()> {
+    let tmp_path = tmpdir();
+    let source_path = tmp_path.join("copy-spec.source");
+    let sink_path = tmp_path.join("copy-spec.sink");
+
+    create_file(&source_path, b"abcdefghiklmnopqr");
+    create_file(&sink_path, b"000000");
+
+    let mut source = OpenOptions::new().read(true).open(&source_path)?;
+    let mut sink = OpenOptions::new().read(true).write(true).open(&sink_path)?;
+
+    // buffered data in the readers and writers has to end up in front of the bytes
+    // copied by the syscalls
+    source.seek(SeekFrom::Start(1))?;
+    let mut source = BufReader::with_capacity(8, source.take(5));
+    source.fill_buf()?;
+    let mut sink = BufWriter::with_capacity(8, sink);
+    sink.write_all(b"123")?;
+
+    let copied = io::copy(&mut source, &mut sink)?;
+    assert_eq!(copied, 5);
+
+    let mut sink = sink.into_inner()?;
+    let take = source.into_inner();
+    assert_eq!(take.limit(), 0);
+    let mut source = take.into_inner();
+    // the source offset must account for the bytes that were copied
+    assert_eq!(source.seek(SeekFrom::Current(0))?, 6);
+
+    sink.seek(SeekFrom::Start(0))?;
+    let mut contents = Vec::new();
+    sink.read_to_end(&mut contents)?;
+    assert_eq!(contents, b"123bcdef");
+
+    Ok(())
+}
+
+#[test]
+fn copy_updates_take_limit() -> Result<()> {
+    let tmp_path = tmpdir();
+    let source_path = tmp_path.join("copy-take.source");
+    let sink_path = tmp_path.join("copy-take.sink");
+
+    create_file(&source_path, b"abcdefghiklmnopqr");
+    create_file(&sink_path, b"");
+
+    let source = OpenOptions::new().read(true).open(&source_path)?;
+    let mut sink = OpenOptions::new().read(true).write(true).open(&sink_path)?;
+
+    // the bytes copied by the syscalls bypass `Take::read`, the limit has to be updated anyway
+    let mut source = source.take(10);
+    assert_eq!(io::copy(&mut source, &mut sink)?, 10);
+    assert_eq!(source.limit(), 0);
+    assert_eq!(io::copy(&mut source, &mut sink)?, 0);
+
+    sink.seek(SeekFrom::Start(0))?;
+    let mut contents = Vec::new();
+    sink.read_to_end(&mut contents)?;
+    assert_eq!(contents, b"abcdefghik");
+
+    Ok(())
+}
+
+#[test]
+fn copies_append_mode_sink() -> Result<()> {
+    let tmp_path = tmpdir();
+    let source_path = tmp_path.join("copies_append_mode.source");
+    let sink_path = tmp_path.join("copies_append_mode.sink");
+
+    create_file(&source_path, b"not empty");
+    create_file(&sink_path, b"already ");
+
+    let mut source = OpenOptions::new().read(true).open(&source_path)?;
+    // copy_file_range rejects sinks opened with O_APPEND, the fallback has to take over
+    let mut sink = OpenOptions::new().append(true).open(&sink_path)?;
+
+    let copied = io::copy(&mut source, &mut sink)?;
+    assert_eq!(copied, 9);
+    assert_eq!(crate::fs::read(&sink_path)?, b"already not empty");
+
+    Ok(())
+}
+
+#[test]
+fn copies_file_to_socket_and_socket_to_file() -> Result<()> {
+    let tmp_path = tmpdir();
+    let source_path = tmp_path.join("socket.source");
+    let sink_path = tmp_path.join("socket.sink");
+
+    let data: Vec<u8> = (0..256 * 1024).map(|i| (i % 251) as u8).collect();
+    create_file(&source_path, &data);
+
+    let (mut tx, mut rx) = UnixStream::pair()?;
+    let mut source = OpenOptions::new().read(true).open(&source_path)?;
+    let mut sink = OpenOptions::new().create(true).write(true).open(&sink_path)?;
+
+    // file -> socket goes through sendfile, socket -> file has to fall back to the copy loop
+    let reader = thread::spawn(move || io::copy(&mut rx, &mut sink));
+    let copied = io::copy(&mut source, &mut tx)?;
+    drop(tx);
+    assert_eq!(copied, data.len() as u64);
+    assert_eq!(reader.join().unwrap()?, data.len() as u64);
+    assert_eq!(crate::fs::read(&sink_path)?, data);
+
+    Ok(())
+}
+
+#[test]
+fn copies_from_procfs() -> Result<()> {
+    // procfs reports a size of 0 for non-empty files, which must not be mistaken for EOF
+    let mut source = crate::fs::File::open("/proc/self/status")?;
+    let mut sink = Vec::new();
+    let copied = io::copy(&mut source, &mut sink)?;
+    assert!(copied > 0);
+    assert_eq!(copied, sink.len() as u64);
+
+    Ok(())
+}
+
+#[bench]
+fn bench_file_to_socket_copy(b: &mut test::Bencher) {
+    const BYTES: usize = 128 * 1024;
+    let temp_path = tmpdir();
+    let src_path = temp_path.join("file-copy-bench-src");
+    create_file(&src_path, &vec![0u8; BYTES]);
+    let mut src = OpenOptions::new().read(true).open(&src_path).unwrap();
+
+    let (mut tx, mut rx) = UnixStream::pair().unwrap();
+    let reader = thread::spawn(move || io::copy(&mut rx, &mut io::sink()).unwrap());
+
+    b.bytes = BYTES as u64;
+    b.iter(|| {
+        src.seek(SeekFrom::Start(0)).unwrap();
+        assert_eq!(BYTES as u64, io::copy(&mut src, &mut tx).unwrap());
+    });
+
+    drop(tx);
+    reader.join().unwrap();
+}
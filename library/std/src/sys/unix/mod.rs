@@ -44,17 +44,17 @@ pub mod alloc;
 pub mod android;
 pub mod args;
 pub mod cmath;
-pub mod condvar;
 pub mod env;
 pub mod ext;
 pub mod fd;
 pub mod fs;
 pub mod futex;
 pub mod io;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod kernel_copy;
 #[cfg(target_os = "l4re")]
 mod l4re;
 pub mod memchr;
-pub mod mutex;
 #[cfg(not(target_os = "l4re"))]
 pub mod net;
 #[cfg(target_os = "l4re")]
@@ -64,7 +64,6 @@ pub mod path;
 pub mod pipe;
 pub mod process;
 pub mod rand;
-pub mod rwlock;
 pub mod stack_overflow;
 pub mod stdio;
 pub mod thread;
@@ -72,6 +71,34 @@ pub mod thread_local_dtor;
 pub mod thread_local_key;
 pub mod time;
 
+cfg_if::cfg_if! {
+    if #[cfg(any(target_os = "linux", target_os = "android"))] {
+        // The futex based locks are a single atomic integer (or two for the
+        // rwlock), so unlike the pthread locks they don't need to be boxed.
+        #[path = "futex_condvar.rs"]
+        pub mod condvar;
+        #[path = "futex_mutex.rs"]
+        pub mod mutex;
+        #[path = "futex_rwlock.rs"]
+        pub mod rwlock;
+        // The reentrant mutex is still a pthread one, and the benchmarks
+        // compare the futex locks against the pthread locks.
+        #[allow(dead_code)]
+        #[path = "mutex.rs"]
+        mod pthread_mutex;
+        #[cfg(test)]
+        #[allow(dead_code)]
+        #[path = "rwlock.rs"]
+        mod pthread_rwlock;
+        #[cfg(test)]
+        mod futex_locks_tests;
+    } else {
+        pub mod condvar;
+        pub mod mutex;
+        pub mod rwlock;
+    }
+}
+
 pub use crate::sys_common::os_str_bytes as os_str;
 
 #[cfg(not(test))]
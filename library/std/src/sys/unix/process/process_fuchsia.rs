@@ -3,6 +3,7 @@ use crate::fmt;
 use crate::io;
 use crate::mem;
 use crate::ptr;
+use crate::time::Duration;
 
 use crate::sys::process::process_common::*;
 use crate::sys::process::zircon::{zx_handle_t, Handle};
@@ -28,6 +29,13 @@ impl Command {
             ));
         }
 
+        if self.get_pgroup().is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "process groups are not supported on this platform",
+            ));
+        }
+
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
         let process_handle = unsafe { self.do_exec(theirs, envp.as_ref())? };
@@ -227,6 +235,32 @@ impl Process {
         }
         Ok(Some(ExitStatus(proc_info.return_code)))
     }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        use crate::sys::process::zircon::*;
+
+        let nanos = dur.as_nanos().try_into().unwrap_or(zx_duration_t::MAX);
+        unsafe {
+            // `zx_deadline_after` saturates at `ZX_TIME_INFINITE`.
+            let deadline = zx_deadline_after(nanos);
+            let status = zx_object_wait_one(
+                self.handle.raw(),
+                ZX_TASK_TERMINATED,
+                deadline,
+                ptr::null_mut(),
+            );
+            match status {
+                0 => {} // Success
+                x if x == ERR_TIMED_OUT => {
+                    return Ok(None);
+                }
+                _ => {
+                    zx_cvt(status)?;
+                }
+            }
+        }
+        self.try_wait()
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -1,3 +1,4 @@
+use crate::cmp;
 use crate::convert::TryInto;
 use crate::fmt;
 use crate::io::{self, Error, ErrorKind};
@@ -5,6 +6,17 @@ use crate::ptr;
 use crate::sys;
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+#[cfg(target_os = "linux")]
+use crate::os::linux::process::PidFd;
+#[cfg(target_os = "linux")]
+use crate::os::unix::io::AsRawFd;
+#[cfg(target_os = "linux")]
+use crate::sys::fd::FileDesc;
+#[cfg(target_os = "linux")]
+use crate::sys_common::FromInner;
 
 #[cfg(target_os = "vxworks")]
 use libc::RTP_ID as pid_t;
@@ -46,9 +58,9 @@ impl Command {
         // Note that as soon as we're done with the fork there's no need to hold
         // a lock any more because the parent won't do anything and the child is
         // in its own process.
-        let result = unsafe {
+        let (result, pidfd) = unsafe {
             let _env_lock = sys::os::env_lock();
-            cvt(libc::fork())?
+            self.do_fork()?
         };
 
         let pid = unsafe {
@@ -78,7 +90,8 @@ impl Command {
             }
         };
 
-        let mut p = Process { pid, status: None };
+        let mut p = Process::new(pid);
+        self.attach_pidfd(&mut p, pidfd);
         drop(output);
         let mut bytes = [0; 8];
 
@@ -111,6 +124,68 @@ impl Command {
         }
     }
 
+    // Forks the process, returning 0 in the child and the child's pid in the
+    // parent, along with a pidfd for the child if one was requested and could be
+    // created atomically with `clone3`, or -1 otherwise.
+    #[cfg(target_os = "linux")]
+    unsafe fn do_fork(&mut self) -> io::Result<(pid_t, c_int)> {
+        use crate::sync::atomic::{AtomicBool, Ordering};
+
+        static HAS_CLONE3: AtomicBool = AtomicBool::new(true);
+        const CLONE_PIDFD: u64 = 0x00001000;
+
+        #[repr(C)]
+        #[allow(non_camel_case_types)]
+        struct clone_args {
+            flags: u64,
+            pidfd: u64,
+            child_tid: u64,
+            parent_tid: u64,
+            exit_signal: u64,
+            stack: u64,
+            stack_size: u64,
+            tls: u64,
+        }
+
+        syscall! {
+            fn clone3(cl_args: *mut clone_args, len: libc::size_t) -> libc::c_long
+        }
+
+        // `clone3` skips the `pthread_atfork` handlers that `fork` runs, so only
+        // use it when it is needed for the pidfd.
+        if self.get_create_pidfd() && HAS_CLONE3.load(Ordering::Relaxed) {
+            let mut pidfd: c_int = -1;
+            let mut args = clone_args {
+                flags: CLONE_PIDFD,
+                pidfd: &mut pidfd as *mut c_int as u64,
+                child_tid: 0,
+                parent_tid: 0,
+                exit_signal: libc::SIGCHLD as u64,
+                stack: 0,
+                stack_size: 0,
+                tls: 0,
+            };
+            match cvt(clone3(&mut args, crate::mem::size_of::<clone_args>())) {
+                Ok(pid) => return Ok((pid as pid_t, pidfd)),
+                Err(e) => match e.raw_os_error() {
+                    // Kernels older than 5.3 lack `clone3`. Several threads may
+                    // race to store this, which is harmless.
+                    Some(libc::ENOSYS) => HAS_CLONE3.store(false, Ordering::Relaxed),
+                    // `clone3` may be blocked by a seccomp filter.
+                    Some(libc::EPERM) => {}
+                    _ => return Err(e),
+                },
+            }
+        }
+
+        cvt(libc::fork()).map(|pid| (pid, -1))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    unsafe fn do_fork(&mut self) -> io::Result<(pid_t, c_int)> {
+        cvt(libc::fork()).map(|pid| (pid, -1))
+    }
+
     pub fn exec(&mut self, default: Stdio) -> io::Error {
         let envp = self.capture_env();
 
@@ -204,6 +279,10 @@ impl Command {
             cvt(libc::chdir(cwd.as_ptr()))?;
         }
 
+        if let Some(pgroup) = self.get_pgroup() {
+            cvt(libc::setpgid(0, pgroup))?;
+        }
+
         // emscripten has no signal support.
         #[cfg(not(target_os = "emscripten"))]
         {
@@ -291,6 +370,14 @@ impl Command {
             return Ok(None);
         }
 
+        // The pidfd can only be created atomically by `clone3`.
+        #[cfg(target_os = "linux")]
+        {
+            if self.get_create_pidfd() {
+                return Ok(None);
+            }
+        }
+
         // Only glibc 2.24+ posix_spawn() supports returning ENOENT directly.
         #[cfg(all(target_os = "linux", target_env = "gnu"))]
         {
@@ -321,7 +408,7 @@ impl Command {
             None => None,
         };
 
-        let mut p = Process { pid: 0, status: None };
+        let mut p = Process::new(0);
 
         struct PosixSpawnFileActions<'a>(&'a mut MaybeUninit<libc::posix_spawn_file_actions_t>);
 
@@ -387,7 +474,11 @@ impl Command {
             cvt(sigaddset(set.as_mut_ptr(), libc::SIGPIPE))?;
             cvt_nz(libc::posix_spawnattr_setsigdefault(attrs.0.as_mut_ptr(), set.as_ptr()))?;
 
-            let flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            let mut flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            if let Some(pgroup) = self.get_pgroup() {
+                flags |= libc::POSIX_SPAWN_SETPGROUP;
+                cvt_nz(libc::posix_spawnattr_setpgroup(attrs.0.as_mut_ptr(), pgroup))?;
+            }
             cvt_nz(libc::posix_spawnattr_setflags(attrs.0.as_mut_ptr(), flags as _))?;
 
             // Make sure we synchronize access to the global `environ` resource
@@ -404,6 +495,30 @@ impl Command {
             Ok(Some(p))
         }
     }
+
+    // Stores the pidfd created by `clone3`. Without `clone3` the pidfd is opened
+    // with `pidfd_open` after the fork, which races with pid reuse if the child
+    // exits and is reaped in the meantime, e.g. because `SIGCHLD` is ignored or
+    // another thread calls `waitpid(-1)`. The pidfd then refers to the wrong
+    // process, or to none at all. Kernels older than 5.3 lack both syscalls, in
+    // which case the child simply doesn't get a pidfd.
+    #[cfg(target_os = "linux")]
+    fn attach_pidfd(&self, p: &mut Process, pidfd: c_int) {
+        if !self.get_create_pidfd() {
+            return;
+        }
+        let fd = if pidfd >= 0 {
+            pidfd
+        } else {
+            unsafe { libc::syscall(libc::SYS_pidfd_open, p.pid, 0) as c_int }
+        };
+        if fd >= 0 {
+            p.pidfd = Some(PidFd::from_inner(FileDesc::new(fd)));
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn attach_pidfd(&self, _: &mut Process, _: c_int) {}
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -414,9 +529,20 @@ impl Command {
 pub struct Process {
     pid: pid_t,
     status: Option<ExitStatus>,
+    #[cfg(target_os = "linux")]
+    pidfd: Option<PidFd>,
 }
 
 impl Process {
+    fn new(pid: pid_t) -> Process {
+        Process {
+            pid,
+            status: None,
+            #[cfg(target_os = "linux")]
+            pidfd: None,
+        }
+    }
+
     pub fn id(&self) -> u32 {
         self.pid as u32
     }
@@ -459,6 +585,67 @@ impl Process {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.try_wait()? {
+            return Ok(Some(status));
+        }
+        let deadline = match Instant::now().checked_add(dur) {
+            Some(deadline) => deadline,
+            None => return self.wait().map(Some),
+        };
+
+        #[cfg(target_os = "linux")]
+        {
+            if let Some(fd) = self.pidfd.as_ref().map(|pidfd| pidfd.as_raw_fd()) {
+                return self.wait_pidfd(fd, deadline);
+            }
+        }
+
+        // Without a descriptor to block on, poll the child with an increasing
+        // delay so that short-lived children are still noticed quickly.
+        let mut delay = Duration::from_millis(1);
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            thread::sleep(cmp::min(delay, deadline - now));
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            delay = cmp::min(delay * 2, Duration::from_millis(50));
+        }
+    }
+
+    // A pidfd becomes readable once the child has exited.
+    #[cfg(target_os = "linux")]
+    fn wait_pidfd(&mut self, fd: c_int, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            // Round up so that we never wake up before the deadline.
+            let millis = (remaining.as_nanos() + 999_999) / 1_000_000;
+            let timeout = cmp::min(millis, c_int::MAX as u128) as c_int;
+            let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+            match cvt(unsafe { libc::poll(&mut pollfd, 1, timeout) }) {
+                Ok(0) if Instant::now() >= deadline => return Ok(None),
+                Ok(0) => {}
+                Ok(_) => return self.try_wait(),
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn pidfd(&self) -> Option<&PidFd> {
+        self.pidfd.as_ref()
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn take_pidfd(&mut self) -> Option<PidFd> {
+        self.pidfd.take()
+    }
 }
 
 /// Unix exit statuses
@@ -18,6 +18,8 @@ pub const ZX_HANDLE_INVALID: zx_handle_t = 0;
 pub type zx_time_t = i64;
 pub const ZX_TIME_INFINITE: zx_time_t = i64::MAX;
 
+pub type zx_duration_t = i64;
+
 pub type zx_signals_t = u32;
 
 pub const ZX_OBJECT_SIGNAL_3: zx_signals_t = 1 << 3;
@@ -79,6 +81,8 @@ extern "C" {
 
     pub fn zx_task_kill(handle: zx_handle_t) -> zx_status_t;
 
+    pub fn zx_deadline_after(nanoseconds: zx_duration_t) -> zx_time_t;
+
     pub fn zx_handle_close(handle: zx_handle_t) -> zx_status_t;
 
     pub fn zx_handle_duplicate(
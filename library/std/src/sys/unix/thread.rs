@@ -2,10 +2,14 @@ use crate::cmp;
 use crate::ffi::CStr;
 use crate::io;
 use crate::mem;
+use crate::num::NonZeroUsize;
 use crate::ptr;
 use crate::sys::{os, stack_overflow};
 use crate::time::Duration;
 
+#[cfg(all(test, target_os = "linux"))]
+mod tests;
+
 #[cfg(not(any(target_os = "l4re", target_os = "vxworks")))]
 pub const DEFAULT_MIN_STACK_SIZE: usize = 2 * 1024 * 1024;
 #[cfg(target_os = "l4re")]
@@ -215,6 +219,235 @@ impl Drop for Thread {
     }
 }
 
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    cfg_if::cfg_if! {
+        if #[cfg(any(
+            target_os = "android",
+            target_os = "emscripten",
+            target_os = "fuchsia",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "solaris",
+            target_os = "illumos",
+        ))] {
+            #[cfg(target_os = "linux")]
+            let quota = cgroups::quota().max(1);
+            #[cfg(not(target_os = "linux"))]
+            let quota = usize::MAX;
+
+            #[cfg(target_os = "linux")]
+            {
+                let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+                if unsafe { libc::sched_getaffinity(0, mem::size_of_val(&set), &mut set) } == 0 {
+                    let count = (0..libc::CPU_SETSIZE as usize)
+                        .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+                        .count();
+                    if let Some(count) = NonZeroUsize::new(cmp::min(count, quota)) {
+                        return Ok(count);
+                    }
+                }
+            }
+            match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
+                -1 => Err(io::Error::last_os_error()),
+                cpus => NonZeroUsize::new(cmp::min(cpus as usize, quota))
+                    .ok_or_else(unknown_parallelism),
+            }
+        } else if #[cfg(any(
+            target_os = "freebsd",
+            target_os = "dragonfly",
+            target_os = "netbsd",
+        ))] {
+            let cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) };
+            if cpus > 0 {
+                return Ok(unsafe { NonZeroUsize::new_unchecked(cpus as usize) });
+            }
+            sysctl_hw_ncpu()
+        } else if #[cfg(target_os = "openbsd")] {
+            sysctl_hw_ncpu()
+        } else {
+            // FIXME: implement on vxWorks, Redox, Haiku, l4re
+            Err(unknown_parallelism())
+        }
+    }
+}
+
+fn unknown_parallelism() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "the number of hardware threads is not known for the target platform",
+    )
+}
+
+#[cfg(any(
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn sysctl_hw_ncpu() -> io::Result<NonZeroUsize> {
+    let mut cpus: libc::c_uint = 0;
+    let mut cpus_size = mem::size_of_val(&cpus);
+    let mut mib = [libc::CTL_HW, libc::HW_NCPU, 0, 0];
+    let ret = unsafe {
+        libc::sysctl(
+            mib.as_mut_ptr(),
+            2,
+            &mut cpus as *mut _ as *mut _,
+            &mut cpus_size as *mut _ as *mut _,
+            ptr::null_mut(),
+            0,
+        )
+    };
+    if ret == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    NonZeroUsize::new(cpus as usize).ok_or_else(unknown_parallelism)
+}
+
+#[cfg(target_os = "linux")]
+mod cgroups {
+    //! The CPU quota of the cgroup this process belongs to.
+    //!
+    //! cgroup v2 hierarchies are only found at their standard mount point,
+    //! `/sys/fs/cgroup`. Paths containing characters that procfs escapes
+    //! (such as spaces) are not unescaped.
+
+    use crate::fs;
+    use crate::path::{Path, PathBuf};
+
+    /// The cgroup of this process, as listed in `/proc/self/cgroup`.
+    #[derive(Debug, PartialEq)]
+    pub(super) enum Cgroup<'a> {
+        /// The path of the cgroup in the v1 hierarchy with the `cpu` controller.
+        V1(&'a str),
+        /// The path of the cgroup in the unified v2 hierarchy.
+        V2(&'a str),
+    }
+
+    /// Returns the CPU quota of the current cgroup in whole cores, rounded down,
+    /// or `usize::MAX` if no quota is set or it cannot be determined.
+    pub(super) fn quota() -> usize {
+        let cgroups = match fs::read_to_string("/proc/self/cgroup") {
+            Ok(cgroups) => cgroups,
+            Err(_) => return usize::MAX,
+        };
+        match parse_cgroup(&cgroups) {
+            Some(Cgroup::V1(path)) => quota_v1(path),
+            Some(Cgroup::V2(path)) => {
+                let mount_point = Path::new("/sys/fs/cgroup");
+                let dir = mount_point.join(path.trim_start_matches('/'));
+                min_quota(dir, mount_point, read_quota_v2)
+            }
+            None => usize::MAX,
+        }
+    }
+
+    /// Finds the cgroup of this process in the contents of `/proc/self/cgroup`.
+    pub(super) fn parse_cgroup(cgroups: &str) -> Option<Cgroup<'_>> {
+        let mut v2_path = None;
+        for line in cgroups.lines() {
+            // Each line is `hierarchy-ID:controller-list:cgroup-path`.
+            let mut fields = line.splitn(3, ':');
+            let (id, controllers, path) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(id), Some(controllers), Some(path)) => (id, controllers, path),
+                _ => continue,
+            };
+            // A v1 `cpu` controller takes precedence in hybrid setups.
+            if controllers.split(',').any(|controller| controller == "cpu") {
+                return Some(Cgroup::V1(path));
+            }
+            if id == "0" && controllers.is_empty() {
+                v2_path = Some(path);
+            }
+        }
+        v2_path.map(Cgroup::V2)
+    }
+
+    fn quota_v1(path: &str) -> usize {
+        let mountinfo = match fs::read_to_string("/proc/self/mountinfo") {
+            Ok(mountinfo) => mountinfo,
+            Err(_) => return usize::MAX,
+        };
+        let (root, mount_point) = match find_v1_mount(&mountinfo) {
+            Some(mount) => mount,
+            None => return usize::MAX,
+        };
+        // The cgroup path is relative to the root of the hierarchy, which is
+        // not necessarily the directory mounted at `mount_point`.
+        let path = match path.strip_prefix(root) {
+            Some(path) => path,
+            None => return usize::MAX,
+        };
+        let mount_point = Path::new(mount_point);
+        let dir = mount_point.join(path.trim_start_matches('/'));
+        min_quota(dir, mount_point, read_quota_v1)
+    }
+
+    /// Finds the root and the mount point of the cgroup v1 hierarchy with the
+    /// `cpu` controller in the contents of `/proc/self/mountinfo`.
+    pub(super) fn find_v1_mount(mountinfo: &str) -> Option<(&str, &str)> {
+        mountinfo.lines().find_map(|line| {
+            // The fields are `ID PARENT-ID MAJOR:MINOR ROOT MOUNT-POINT OPTIONS`,
+            // followed by optional fields, a `-` separator, and then
+            // `FS-TYPE SOURCE SUPER-OPTIONS`.
+            let mut fields = line.split(' ');
+            let root = fields.nth(3)?;
+            let mount_point = fields.next()?;
+            let mut fields = fields.skip_while(|&field| field != "-").skip(1);
+            let fs_type = fields.next()?;
+            let super_options = fields.nth(1)?;
+            if fs_type == "cgroup" && super_options.split(',').any(|option| option == "cpu") {
+                Some((root, mount_point))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the smallest quota reported by `read_quota` for `dir` and its
+    /// ancestors up to `mount_point`, as the limits of parent groups apply too.
+    fn min_quota(
+        mut dir: PathBuf,
+        mount_point: &Path,
+        read_quota: fn(&Path) -> Option<usize>,
+    ) -> usize {
+        let mut quota = usize::MAX;
+        loop {
+            if let Some(dir_quota) = read_quota(&dir) {
+                quota = quota.min(dir_quota);
+            }
+            if dir == mount_point || !dir.pop() {
+                return quota;
+            }
+        }
+    }
+
+    fn read_quota_v2(dir: &Path) -> Option<usize> {
+        parse_quota_v2(&fs::read_to_string(dir.join("cpu.max")).ok()?)
+    }
+
+    fn read_quota_v1(dir: &Path) -> Option<usize> {
+        let read = |file: &str| fs::read_to_string(dir.join(file)).ok();
+        parse_quota_v1(&read("cpu.cfs_quota_us")?, &read("cpu.cfs_period_us")?)
+    }
+
+    /// `cpu.max` holds `$MAX $PERIOD`, where `$MAX` is `max` if there is no limit.
+    pub(super) fn parse_quota_v2(max: &str) -> Option<usize> {
+        let mut fields = max.split_whitespace();
+        let limit: usize = fields.next()?.parse().ok()?;
+        let period: usize = fields.next()?.parse().ok()?;
+        limit.checked_div(period)
+    }
+
+    /// There is no limit if `cpu.cfs_quota_us` is -1.
+    pub(super) fn parse_quota_v1(quota: &str, period: &str) -> Option<usize> {
+        let limit: usize = quota.trim().parse().ok()?;
+        let period: usize = period.trim().parse().ok()?;
+        limit.checked_div(period)
+    }
+}
+
 #[cfg(all(
     not(target_os = "linux"),
     not(target_os = "freebsd"),
@@ -0,0 +1,48 @@
+use super::cgroups::{find_v1_mount, parse_cgroup, parse_quota_v1, parse_quota_v2, Cgroup};
+
+#[test]
+fn test_parse_cgroup() {
+    let v1 = "12:pids:/user.slice\n4:cpu,cpuacct:/docker/abc\n0::/user.slice\n";
+    assert_eq!(parse_cgroup(v1), Some(Cgroup::V1("/docker/abc")));
+    let v2 = "0::/system.slice/foo.service\n";
+    assert_eq!(parse_cgroup(v2), Some(Cgroup::V2("/system.slice/foo.service")));
+    assert_eq!(parse_cgroup("3:cpuacct:/a\n5:memory:/b\n"), None);
+    assert_eq!(parse_cgroup("garbage\n1:cpu\n"), None);
+    assert_eq!(parse_cgroup(""), None);
+}
+
+#[test]
+fn test_find_v1_mount() {
+    let mountinfo = "\
+        22 1 0:21 / /proc rw,nosuid shared:12 - proc proc rw\n\
+        30 25 0:26 / /sys/fs/cgroup/memory rw shared:9 - cgroup cgroup rw,memory\n\
+        31 25 0:27 /docker /sys/fs/cgroup/cpu,cpuacct rw - cgroup cgroup rw,cpu,cpuacct\n";
+    assert_eq!(find_v1_mount(mountinfo), Some(("/docker", "/sys/fs/cgroup/cpu,cpuacct")));
+
+    // The cpu controller is not mounted, or only the v2 hierarchy is.
+    let mountinfo = "30 25 0:26 / /sys/fs/cgroup rw - cgroup2 cgroup2 rw,nsdelegate\n";
+    assert_eq!(find_v1_mount(mountinfo), None);
+
+    // Truncated lines are skipped.
+    assert_eq!(find_v1_mount("31 25 0:27 / /sys/fs/cgroup/cpu rw -\n31 25"), None);
+}
+
+#[test]
+fn test_parse_quota_v2() {
+    assert_eq!(parse_quota_v2("200000 100000\n"), Some(2));
+    assert_eq!(parse_quota_v2("150000 100000\n"), Some(1));
+    assert_eq!(parse_quota_v2("50000 100000\n"), Some(0));
+    assert_eq!(parse_quota_v2("max 100000\n"), None);
+    assert_eq!(parse_quota_v2("200000 0\n"), None);
+    assert_eq!(parse_quota_v2("200000\n"), None);
+    assert_eq!(parse_quota_v2(""), None);
+}
+
+#[test]
+fn test_parse_quota_v1() {
+    assert_eq!(parse_quota_v1("400000\n", "100000\n"), Some(4));
+    assert_eq!(parse_quota_v1("-1\n", "100000\n"), None);
+    assert_eq!(parse_quota_v1("400000\n", "0\n"), None);
+    assert_eq!(parse_quota_v1("many\n", "100000\n"), None);
+    assert_eq!(parse_quota_v1("", ""), None);
+}
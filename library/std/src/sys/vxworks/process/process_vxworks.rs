@@ -1,9 +1,11 @@
+use crate::cmp;
 use crate::fmt;
 use crate::io::{self, Error, ErrorKind};
 use crate::sys;
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
 use crate::sys_common::thread;
+use crate::time::{Duration, Instant};
 use libc::RTP_ID;
 use libc::{self, c_char, c_int};
 
@@ -24,6 +26,12 @@ impl Command {
         if self.saw_nul() {
             return Err(io::Error::new(ErrorKind::InvalidInput, "nul byte found in provided data"));
         }
+        if self.get_pgroup().is_some() {
+            return Err(io::Error::new(
+                ErrorKind::Other,
+                "process groups are not supported on this platform",
+            ));
+        }
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
         let mut p = Process { pid: 0, status: None };
 
@@ -167,6 +175,25 @@ impl Process {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        let deadline = match Instant::now().checked_add(dur) {
+            Some(deadline) => deadline,
+            None => return self.wait().map(Some),
+        };
+        let mut delay = Duration::from_millis(1);
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            crate::thread::sleep(cmp::min(delay, deadline - now));
+            delay = cmp::min(delay * 2, Duration::from_millis(50));
+        }
+    }
 }
 
 /// Unix exit statuses
@@ -1,7 +1,9 @@
 use super::Builder;
 use crate::any::Any;
 use crate::mem;
+use crate::panic::{self, AssertUnwindSafe};
 use crate::result;
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use crate::sync::mpsc::{channel, Sender};
 use crate::thread::{self, ThreadId};
 use crate::time::Duration;
@@ -258,5 +260,89 @@ fn test_thread_id_not_equal() {
     assert!(thread::current().id() != spawned_id);
 }
 
+#[test]
+fn test_scoped_threads_borrow_and_join() {
+    let mut a = vec![1, 2, 3];
+    let mut x = 0;
+
+    let sum = thread::scope(|s| {
+        let sum = s.spawn(|| a.iter().sum::<i32>());
+        s.spawn(|| x += a[0] + a[2]);
+        sum.join().unwrap()
+    });
+
+    a.push(4);
+    assert_eq!(sum, 6);
+    assert_eq!(x, a.len() as i32);
+}
+
+#[test]
+fn test_scoped_threads_are_joined() {
+    let counter = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..10 {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(1));
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+    });
+    assert_eq!(counter.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+fn test_scoped_thread_builder() {
+    let name = thread::scope(|s| {
+        Builder::new()
+            .name("scoped".to_string())
+            .stack_size(64 * 1024)
+            .spawn_scoped(s, || thread::current().name().map(str::to_owned))
+            .unwrap()
+            .join()
+            .unwrap()
+    });
+    assert_eq!(name.as_deref(), Some("scoped"));
+}
+
+#[test]
+fn test_scoped_thread_joined_panic_is_handled() {
+    let result = thread::scope(|s| s.spawn(|| panic!()).join());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_scoped_thread_unjoined_panic_is_propagated() {
+    let result = panic::catch_unwind(|| {
+        thread::scope(|s| {
+            s.spawn(|| panic!());
+        })
+    });
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"a scoped thread panicked"));
+}
+
+#[test]
+fn test_scope_waits_for_threads_when_closure_panics() {
+    let finished = AtomicBool::new(false);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        thread::scope(|s| {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(10));
+                finished.store(true, Ordering::SeqCst);
+            });
+            panic!("scope closure panicked");
+        })
+    }));
+    assert!(result.is_err());
+    assert!(finished.load(Ordering::SeqCst));
+}
+
 // NOTE: the corresponding test for stderr is in ui/thread-stderr, due
 // to the test harness apparently interfering with stderr configuration.
+
+#[test]
+#[cfg(any(target_os = "linux", windows, target_os = "macos"))]
+fn test_available_parallelism() {
+    let n = thread::available_parallelism().unwrap();
+    assert!(n.get() >= 1);
+}
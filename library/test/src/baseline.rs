@@ -0,0 +1,253 @@
+//! Module for saving benchmark results as a named baseline and comparing later runs to it.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::f64::consts::PI;
+use std::fmt;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use super::bench::{BenchSamples, SAMPLE_COUNT};
+use super::stats::Summary;
+
+/// Environment variable naming the directory baselines are stored in.
+/// Baselines are stored in the current directory if it is not set.
+pub const BASELINE_DIR_ENV: &str = "RUST_TEST_BASELINE_DIR";
+
+// First line of every baseline file, bump the version if the format changes.
+const HEADER: &str = "# libtest benchmark baseline v1";
+
+// Number of values stored per benchmark: the fields of `Summary` (with the
+// three quartiles stored separately) followed by the throughput.
+const VALUE_COUNT: usize = 15;
+
+/// Critical value of the standard normal distribution for a two-sided test at
+/// the 95% confidence level.
+const Z_CRITICAL: f64 = 1.959964;
+
+/// Benchmark results saved under a name, keyed by the name of the benchmark.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Baseline(BTreeMap<String, BenchSamples>);
+
+impl Baseline {
+    pub fn new() -> Baseline {
+        Baseline(BTreeMap::new())
+    }
+
+    /// Returns the path of the file the baseline called `name` is stored in.
+    pub fn path(name: &str) -> PathBuf {
+        let dir = env::var_os(BASELINE_DIR_ENV).map(PathBuf::from).unwrap_or_default();
+        dir.join(format!("{}.baseline", name))
+    }
+
+    /// Loads the baseline called `name` that was saved by an earlier run.
+    pub fn load(name: &str) -> io::Result<Baseline> {
+        let path = Baseline::path(name);
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("failed to read baseline `{}` from {}: {}", name, path.display(), e),
+            )
+        })?;
+        Baseline::parse(&contents).map_err(|line| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid baseline file {} (at line {})", path.display(), line),
+            )
+        })
+    }
+
+    /// Saves the results under `name`. Benchmarks that were already part of
+    /// the saved baseline, but did not run this time, keep their old results.
+    pub fn save(&self, name: &str) -> io::Result<()> {
+        let mut baseline = match Baseline::load(name) {
+            Ok(baseline) => baseline,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Baseline::new(),
+            Err(e) => return Err(e),
+        };
+        baseline.0.extend(self.0.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        let path = Baseline::path(name);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, baseline.serialize())
+    }
+
+    pub fn insert(&mut self, name: &str, bs: BenchSamples) {
+        self.0.insert(name.to_owned(), bs);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&BenchSamples> {
+        self.0.get(name)
+    }
+
+    // Each benchmark is stored on a line of its own: the values, separated by
+    // spaces, followed by a tab and the name of the benchmark.
+    fn serialize(&self) -> String {
+        let mut output = String::new();
+        writeln!(output, "{}", HEADER).unwrap();
+        for (name, bs) in &self.0 {
+            let s = &bs.ns_iter_summ;
+            let (q1, q2, q3) = s.quartiles;
+            writeln!(
+                output,
+                "{} {} {} {} {} {} {} {} {} {} {} {} {} {} {}\t{}",
+                s.sum,
+                s.min,
+                s.max,
+                s.mean,
+                s.median,
+                s.var,
+                s.std_dev,
+                s.std_dev_pct,
+                s.median_abs_dev,
+                s.median_abs_dev_pct,
+                q1,
+                q2,
+                q3,
+                s.iqr,
+                bs.mb_s,
+                name
+            )
+            .unwrap();
+        }
+        output
+    }
+
+    // Returns the number of the first invalid line on error.
+    fn parse(contents: &str) -> Result<Baseline, usize> {
+        let mut lines = contents.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(1);
+        }
+
+        let mut baseline = Baseline::new();
+        for (i, line) in lines.enumerate() {
+            let (name, bs) = parse_entry(line).ok_or(i + 2)?;
+            baseline.0.insert(name, bs);
+        }
+        Ok(baseline)
+    }
+}
+
+fn parse_entry(line: &str) -> Option<(String, BenchSamples)> {
+    let mut parts = line.splitn(2, '\t');
+    let values = parts.next()?;
+    let name = parts.next()?;
+
+    let v = values.split(' ').map(|v| v.parse::<f64>().ok()).collect::<Option<Vec<_>>>()?;
+    if v.len() != VALUE_COUNT {
+        return None;
+    }
+
+    let ns_iter_summ = Summary {
+        sum: v[0],
+        min: v[1],
+        max: v[2],
+        mean: v[3],
+        median: v[4],
+        var: v[5],
+        std_dev: v[6],
+        std_dev_pct: v[7],
+        median_abs_dev: v[8],
+        median_abs_dev_pct: v[9],
+        quartiles: (v[10], v[11], v[12]),
+        iqr: v[13],
+    };
+    Some((name.to_owned(), BenchSamples { ns_iter_summ, mb_s: v[14] as usize }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchChange {
+    Improved,
+    Regressed,
+    /// The difference is not statistically significant.
+    Unchanged,
+}
+
+impl BenchChange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BenchChange::Improved => "improved",
+            BenchChange::Regressed => "regressed",
+            BenchChange::Unchanged => "no change",
+        }
+    }
+}
+
+/// Result of comparing a benchmark to its result in the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchComparison {
+    /// Median of the baseline in ns/iter.
+    pub baseline_median: f64,
+    /// Change of the median in percent, positive values mean the benchmark got slower.
+    pub change_pct: f64,
+    pub change: BenchChange,
+}
+
+impl BenchComparison {
+    /// Compares the medians of two results with Welch's t-test, a change is
+    /// only reported if they differ at the 95% confidence level.
+    pub fn new(old: &Summary, new: &Summary) -> BenchComparison {
+        let change_pct =
+            if old.median == 0.0 { 0.0 } else { (new.median - old.median) / old.median * 100.0 };
+
+        let (old_var, new_var) = (median_variance(old), median_variance(new));
+        let std_err = (old_var + new_var).sqrt();
+        let significant = if std_err == 0.0 {
+            new.median != old.median
+        } else {
+            // The Welch-Satterthwaite approximation of the degrees of freedom.
+            let n = SAMPLE_COUNT as f64;
+            let df = (old_var + new_var).powi(2)
+                / ((old_var.powi(2) + new_var.powi(2)) / (n - 1.0));
+            ((new.median - old.median) / std_err).abs() > t_critical(df)
+        };
+
+        let change = if !significant {
+            BenchChange::Unchanged
+        } else if new.median > old.median {
+            BenchChange::Regressed
+        } else {
+            BenchChange::Improved
+        };
+
+        BenchComparison { baseline_median: old.median, change_pct, change }
+    }
+}
+
+// Estimates the variance of the median of `SAMPLE_COUNT` samples. For normally
+// distributed samples it is `PI / 2` times the variance of the mean, with the
+// standard deviation estimated from the median absolute deviation, which is
+// robust against outliers like the median itself.
+fn median_variance(s: &Summary) -> f64 {
+    PI / 2.0 * s.median_abs_dev.powi(2) / SAMPLE_COUNT as f64
+}
+
+/// Critical value of Student's t-distribution for a two-sided test at the 95%
+/// confidence level with `df` degrees of freedom. This uses the Cornish-Fisher
+/// expansion around the normal distribution, which is accurate to three decimal
+/// places for the `SAMPLE_COUNT - 1` or more degrees of freedom of a comparison.
+pub(crate) fn t_critical(df: f64) -> f64 {
+    let z = Z_CRITICAL;
+    let terms = [
+        z,
+        (z.powi(3) + z) / 4.0,
+        (5.0 * z.powi(5) + 16.0 * z.powi(3) + 3.0 * z) / 96.0,
+        (3.0 * z.powi(7) + 19.0 * z.powi(5) + 17.0 * z.powi(3) - 15.0 * z) / 384.0,
+        (79.0 * z.powi(9) + 776.0 * z.powi(7) + 1482.0 * z.powi(5)
+            - 1920.0 * z.powi(3)
+            - 945.0 * z)
+            / 92160.0,
+    ];
+    terms.iter().rev().fold(0.0, |acc, term| acc / df + term)
+}
+
+impl fmt::Display for BenchComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "change: {:+.2}% ({})", self.change_pct, self.change.as_str())
+    }
+}
@@ -2,8 +2,12 @@
 pub use std::hint::black_box;
 
 use super::{
-    event::CompletedTest, helpers::sink::Sink, options::BenchMode, test_result::TestResult,
-    types::TestDesc, Sender,
+    event::CompletedTest,
+    options::BenchMode,
+    run_fixtures,
+    test_result::TestResult,
+    types::{TestDesc, TestFixtures},
+    Sender,
 };
 
 use crate::stats;
@@ -13,6 +17,9 @@ use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Number of samples the statistics of a benchmark are computed from.
+pub const SAMPLE_COUNT: usize = 50;
+
 /// Manager of the benchmarking runs.
 ///
 /// This is fed into functions marked with `#[bench]` to allow for
@@ -129,7 +136,7 @@ where
     n = cmp::max(1, n);
 
     let mut total_run = Duration::new(0, 0);
-    let samples: &mut [f64] = &mut [0.0_f64; 50];
+    let samples: &mut [f64] = &mut [0.0_f64; SAMPLE_COUNT];
     loop {
         let loop_start = Instant::now();
 
@@ -181,43 +188,60 @@ where
 pub fn benchmark<F>(desc: TestDesc, monitor_ch: Sender<CompletedTest>, nocapture: bool, f: F)
 where
     F: FnMut(&mut Bencher),
+{
+    benchmark_with_fixtures(desc, monitor_ch, nocapture, &TestFixtures::default(), f)
+}
+
+/// Like `benchmark`, but runs the fixtures that run around every test around the
+/// benchmark too.
+pub fn benchmark_with_fixtures<F>(
+    desc: TestDesc,
+    monitor_ch: Sender<CompletedTest>,
+    nocapture: bool,
+    fixtures: &TestFixtures,
+    f: F,
+) where
+    F: FnMut(&mut Bencher),
 {
     let mut bs = Bencher { mode: BenchMode::Auto, summary: None, bytes: 0 };
 
     let data = Arc::new(Mutex::new(Vec::new()));
-    let oldio = if !nocapture {
-        Some((
-            io::set_print(Some(Sink::new_boxed(&data))),
-            io::set_panic(Some(Sink::new_boxed(&data))),
-        ))
-    } else {
-        None
-    };
+    let oldio = if !nocapture { Some(io::set_output_capture(Some(data.clone()))) } else { None };
 
-    let result = catch_unwind(AssertUnwindSafe(|| bs.bench(f)));
+    // A panicking fixture fails the benchmark like it fails a test.
+    let setup = catch_unwind(AssertUnwindSafe(|| run_fixtures(fixtures.setup_each)));
+    let result = match setup {
+        Ok(()) => Some(catch_unwind(AssertUnwindSafe(|| bs.bench(f)))),
+        Err(_) => None,
+    };
+    let teardown_failed = result.is_some()
+        && catch_unwind(AssertUnwindSafe(|| run_fixtures(fixtures.teardown_each))).is_err();
 
-    if let Some((printio, panicio)) = oldio {
-        io::set_print(printio);
-        io::set_panic(panicio);
+    if let Some(oldio) = oldio {
+        io::set_output_capture(oldio);
     }
 
     let test_result = match result {
+        None => TestResult::TrFailedMsg("test setup failed".to_string()),
+        Some(Ok(_)) if teardown_failed => {
+            TestResult::TrFailedMsg("test teardown failed".to_string())
+        }
         //bs.bench(f) {
-        Ok(Some(ns_iter_summ)) => {
+        Some(Ok(Some(ns_iter_summ))) => {
             let ns_iter = cmp::max(ns_iter_summ.median as u64, 1);
             let mb_s = bs.bytes * 1000 / ns_iter;
 
             let bs = BenchSamples { ns_iter_summ, mb_s: mb_s as usize };
             TestResult::TrBench(bs)
         }
-        Ok(None) => {
+        Some(Ok(None)) => {
             // iter not called, so no data.
             // FIXME: error in this case?
             let samples: &mut [f64] = &mut [0.0_f64; 1];
             let bs = BenchSamples { ns_iter_summ: stats::Summary::new(samples), mb_s: 0 };
             TestResult::TrBench(bs)
         }
-        Err(_) => TestResult::TrFailed,
+        Some(Err(_)) => TestResult::TrFailed,
     };
 
     let stdout = data.lock().unwrap().to_vec();
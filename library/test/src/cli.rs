@@ -1,17 +1,20 @@
 //! Module converting command-line arguments into test configuration.
 
+use std::collections::BTreeSet;
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use super::helpers::isatty;
-use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
+use super::helpers::pattern::Pattern;
+use super::helpers::{failed_list, isatty};
+use super::options::{ColorConfig, FilterMode, Options, OutputFormat, Partition, RunIgnored};
 use super::time::TestTimeOptions;
 
 #[derive(Debug)]
 pub struct TestOpts {
     pub list: bool,
-    pub filter: Option<String>,
-    pub filter_exact: bool,
+    pub filters: Vec<String>,
+    pub filter_mode: FilterMode,
     pub force_run_in_process: bool,
     pub exclude_should_panic: bool,
     pub run_ignored: RunIgnored,
@@ -23,6 +26,14 @@ pub struct TestOpts {
     pub format: OutputFormat,
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
+    pub shuffle: bool,
+    pub shuffle_seed: Option<u64>,
+    pub partition: Option<Partition>,
+    pub test_timeout: Option<Duration>,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
+    pub failed_list: Option<PathBuf>,
+    pub rerun_failed: Option<BTreeSet<String>>,
     pub time_options: Option<TestTimeOptions>,
     pub options: Options,
 }
@@ -75,7 +86,7 @@ fn optgroups() -> getopts::Options {
         .optmulti(
             "",
             "skip",
-            "Skip tests whose names contain FILTER (this flag can \
+            "Skip tests whose names match FILTER (this flag can \
              be used multiple times)",
             "FILTER",
         )
@@ -86,6 +97,17 @@ fn optgroups() -> getopts::Options {
              Alias to --format=terse",
         )
         .optflag("", "exact", "Exactly match filters rather than by substring")
+        .optopt(
+            "",
+            "filter-mode",
+            "Configure how test names are matched against the filters and skip filters:
+            substring = the name contains the filter (default);
+            exact     = the name is equal to the filter, same as --exact;
+            glob      = the whole name matches the filter as a glob;
+            regex     = the name matches the filter as a regex;
+            path      = the filter is the name or the path of a module containing the test",
+            "substring|exact|glob|regex|path",
+        )
         .optopt(
             "",
             "color",
@@ -101,8 +123,9 @@ fn optgroups() -> getopts::Options {
             "Configure formatting of output:
             pretty = Print verbose output;
             terse  = Display one character per test;
-            json   = Output a json document",
-            "pretty|terse|json",
+            json   = Output a json document;
+            junit  = Output a JUnit document",
+            "pretty|terse|json|junit",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -143,22 +166,96 @@ fn optgroups() -> getopts::Options {
 
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
+        )
+        .optflag("", "shuffle", "Run tests in random order")
+        .optopt(
+            "",
+            "shuffle-seed",
+            "Run tests in random order; seed the random number generator with SEED",
+            "SEED",
+        )
+        .optopt(
+            "",
+            "partition",
+            "Only run the K-th of N disjoint partitions of the filtered tests, \
+             e.g. 2/3 to run the second third",
+            "K/N",
+        )
+        .optopt(
+            "",
+            "test-timeout",
+            "Kill tests that are still running after SECS seconds and report them
+            as failed. Tests are run in a separate process to make this possible.
+            A `#[test_timeout]` attribute on a test takes precedence.",
+            "SECS",
+        )
+        .optopt(
+            "",
+            "save-baseline",
+            "Save the benchmark results as the baseline NAME",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "baseline",
+            "Compare the benchmark results to the baseline NAME saved by an earlier run",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "failed-list",
+            "Record the names of the failed tests in PATH, and read the tests to
+            run from PATH with --rerun-failed",
+            "PATH",
+        )
+        .optflag(
+            "",
+            "record-failed",
+            "Record the names of the failed tests in a file next to the test binary",
+        )
+        .optflag(
+            "",
+            "rerun-failed",
+            "Only run the tests that failed in the recorded run, and record the
+            tests that still fail. The tests are read from the file named by
+            --failed-list, or else from the file next to the test binary.",
         );
     opts
 }
 
 fn usage(binary: &str, options: &getopts::Options) {
-    let message = format!("Usage: {} [OPTIONS] [FILTER]", binary);
+    let message = format!("Usage: {} [OPTIONS] [FILTERS...]", binary);
     println!(
         r#"{usage}
 
 The FILTER string is tested against the name of all tests, and only those
-tests whose names contain the filter are run.
+tests whose names contain the filter are run. Multiple filter strings may be
+passed, which will run all tests matching any of the filters. Use --filter-mode
+to match the names against globs or regexes, or to select all tests of a module
+and its submodules by passing the module path, e.g. `io::buffered`.
 
 By default, all tests are run in parallel. This can be altered with the
 --test-threads flag or the RUST_TEST_THREADS environment variable when running
 tests (set it to 1).
 
+By default, the tests are run in alphabetical order. Use --shuffle or set
+RUST_TEST_SHUFFLE to run the tests in random order. Pass the generated
+"shuffle seed" to --shuffle-seed (or set RUST_TEST_SHUFFLE_SEED) to run the
+tests in the same order again. Note that --shuffle and --shuffle-seed do not
+affect whether the tests are run in parallel.
+
+Benchmark results can be saved with --save-baseline NAME and compared to by
+later runs with --baseline NAME, which reports the change of every benchmark
+and whether it is statistically significant. Baselines are stored in the file
+NAME.baseline in the directory named by RUST_TEST_BASELINE_DIR, or in the
+current directory if it is not set. If the baseline passed to --baseline does
+not exist yet, the benchmarks are run without being compared.
+
+To iterate on the tests that failed, record them with --record-failed and run
+only those tests again with --rerun-failed. Both flags use a file next to the
+test binary. To use another file, pass --failed-list PATH instead of
+--record-failed, and along with --rerun-failed.
+
 All tests have their standard output and standard error captured by default.
 This can be overridden with the --nocapture flag or setting RUST_TEST_NOCAPTURE
 environment variable to a value other than "0". Logging is not captured by default.
@@ -232,9 +329,20 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let include_ignored = unstable_optflag!(matches, allow_unstable, "include-ignored");
     let time_options = get_time_options(&matches, allow_unstable)?;
+    let shuffle = get_shuffle(&matches, allow_unstable)?;
+    let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let partition = get_partition(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    if test_timeout.is_some() && force_run_in_process {
+        return Err("--test-timeout can't be combined with --force-run-in-process, since \
+                    only tests that run in a subprocess can be killed"
+            .into());
+    }
+    let save_baseline = get_baseline(&matches, allow_unstable, "save-baseline")?;
+    let baseline = get_baseline(&matches, allow_unstable, "baseline")?;
+    let (failed_list, rerun_failed) = get_failed_list(&matches, allow_unstable)?;
 
     let quiet = matches.opt_present("quiet");
-    let exact = matches.opt_present("exact");
     let list = matches.opt_present("list");
     let skip = matches.opt_strs("skip");
 
@@ -243,7 +351,9 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
 
     let logfile = get_log_file(&matches)?;
     let run_ignored = get_run_ignored(&matches, include_ignored)?;
-    let filter = get_filter(&matches)?;
+    let filter_mode = get_filter_mode(&matches, allow_unstable)?;
+    let filters = get_filters(&matches, filter_mode)?;
+    check_filters(&skip, filter_mode)?;
     let nocapture = get_nocapture(&matches)?;
     let test_threads = get_test_threads(&matches)?;
     let color = get_color_config(&matches)?;
@@ -253,8 +363,8 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
 
     let test_opts = TestOpts {
         list,
-        filter,
-        filter_exact: exact,
+        filters,
+        filter_mode,
         force_run_in_process,
         exclude_should_panic,
         run_ignored,
@@ -266,6 +376,14 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         format,
         test_threads,
         skip,
+        shuffle,
+        shuffle_seed,
+        partition,
+        test_timeout,
+        save_baseline,
+        baseline,
+        failed_list,
+        rerun_failed,
         time_options,
         options,
     };
@@ -307,6 +425,171 @@ fn get_time_options(
     Ok(options)
 }
 
+fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
+    let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
+    if !shuffle && allow_unstable {
+        shuffle = match env::var("RUST_TEST_SHUFFLE") {
+            Ok(val) => &val != "0",
+            Err(_) => false,
+        };
+    }
+
+    Ok(shuffle)
+}
+
+fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<u64>> {
+    let mut shuffle_seed = match matches.opt_str("shuffle-seed") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(n) => Some(n),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --shuffle-seed must be a number \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => None,
+    };
+
+    if shuffle_seed.is_none() && allow_unstable {
+        shuffle_seed = match env::var("RUST_TEST_SHUFFLE_SEED") {
+            Ok(val) => match val.parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => panic!("RUST_TEST_SHUFFLE_SEED is `{}`, should be a number.", val),
+            },
+            Err(_) => None,
+        };
+    }
+
+    if shuffle_seed.is_some() && !allow_unstable {
+        return Err("The \"shuffle-seed\" flag is only accepted on the nightly compiler with \
+                    -Z unstable-options"
+            .into());
+    }
+
+    Ok(shuffle_seed)
+}
+
+fn get_partition(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Partition>> {
+    let partition_str = match matches.opt_str("partition") {
+        Some(s) => s,
+        None => return Ok(None),
+    };
+
+    if !allow_unstable {
+        return Err("The \"partition\" flag is only accepted on the nightly compiler with \
+                    -Z unstable-options"
+            .into());
+    }
+
+    let mut parts = partition_str.splitn(2, '/');
+    let index = parts.next().and_then(|s| s.parse::<usize>().ok());
+    let count = parts.next().and_then(|s| s.parse::<usize>().ok());
+    match index.zip(count).map(|(index, count)| Partition { index, count }) {
+        Some(partition) if partition.index >= 1 && partition.index <= partition.count => {
+            Ok(Some(partition))
+        }
+        _ => Err(format!(
+            "argument for --partition must be of the form K/N with 1 <= K <= N (was {})",
+            partition_str
+        )),
+    }
+}
+
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let secs_str = match matches.opt_str("test-timeout") {
+        Some(s) => s,
+        None => return Ok(None),
+    };
+
+    if !allow_unstable {
+        return Err("The \"test-timeout\" flag is only accepted on the nightly compiler with \
+                    -Z unstable-options"
+            .into());
+    }
+
+    match secs_str.parse::<u64>() {
+        Ok(0) => Err("argument for --test-timeout must not be 0".to_string()),
+        Ok(secs) => Ok(Some(Duration::from_secs(secs))),
+        Err(e) => Err(format!(
+            "argument for --test-timeout must be a number of seconds > 0 (error: {})",
+            e
+        )),
+    }
+}
+
+fn get_baseline(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+    option_name: &str,
+) -> OptPartRes<Option<String>> {
+    let name = match matches.opt_str(option_name) {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    if !allow_unstable {
+        return Err(format!(
+            "The \"{}\" flag is only accepted on the nightly compiler with -Z unstable-options",
+            option_name
+        ));
+    }
+
+    if name.is_empty() {
+        return Err(format!("argument for --{} must not be empty", option_name));
+    }
+
+    Ok(Some(name))
+}
+
+// Returns where to record the failed tests and, with `--rerun-failed`, the
+// tests that failed in the recorded run.
+fn get_failed_list(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<(Option<PathBuf>, Option<BTreeSet<String>>)> {
+    let failed_list = unstable_optflag!(matches, allow_unstable, "failed-list");
+    let record_failed = unstable_optflag!(matches, allow_unstable, "record-failed");
+    let rerun_failed = unstable_optflag!(matches, allow_unstable, "rerun-failed");
+    if failed_list && record_failed {
+        return Err("--record-failed records the failed tests in the default file, \
+                    it can't be combined with --failed-list"
+            .into());
+    }
+    if !failed_list && !record_failed && !rerun_failed {
+        return Ok((None, None));
+    }
+
+    let path = match matches.opt_str("failed-list") {
+        Some(path) => PathBuf::from(path),
+        None => failed_list::default_path(),
+    };
+
+    let failed_tests = if rerun_failed {
+        match failed_list::read(&path) {
+            Ok(names) => Some(names),
+            Err(e) => {
+                return Err(format!(
+                    "failed to read the failed tests recorded in {} (error: {})",
+                    path.display(),
+                    e
+                ));
+            }
+        }
+    } else {
+        None
+    };
+
+    Ok((Some(path), failed_tests))
+}
+
 fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
@@ -341,10 +624,16 @@ fn get_format(
             }
             OutputFormat::Json
         }
+        Some("junit") => {
+            if !allow_unstable {
+                return Err("The \"junit\" format is only accepted on the nightly compiler".into());
+            }
+            OutputFormat::Junit
+        }
 
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, or json (was \
+                "argument for --format must be pretty, terse, json or junit (was \
                  {})",
                 v
             ));
@@ -397,10 +686,64 @@ fn get_run_ignored(matches: &getopts::Matches, include_ignored: bool) -> OptPart
     Ok(run_ignored)
 }
 
-fn get_filter(matches: &getopts::Matches) -> OptPartRes<Option<String>> {
-    let filter = if !matches.free.is_empty() { Some(matches.free[0].clone()) } else { None };
+fn get_filter_mode(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<FilterMode> {
+    let exact = matches.opt_present("exact");
+    let mode_str = match matches.opt_str("filter-mode") {
+        Some(s) => s,
+        None if exact => return Ok(FilterMode::Exact),
+        None => return Ok(FilterMode::Substring),
+    };
+
+    if !allow_unstable {
+        return Err("The \"filter-mode\" flag is only accepted on the nightly compiler with \
+                    -Z unstable-options"
+            .into());
+    }
+
+    let filter_mode = match &*mode_str {
+        "substring" => FilterMode::Substring,
+        "exact" => FilterMode::Exact,
+        "glob" => FilterMode::Glob,
+        "regex" => FilterMode::Regex,
+        "path" => FilterMode::Path,
+        v => {
+            return Err(format!(
+                "argument for --filter-mode must be substring, exact, glob, regex or path \
+                 (was {})",
+                v
+            ));
+        }
+    };
+
+    if exact && filter_mode != FilterMode::Exact {
+        return Err("the options --exact and --filter-mode are mutually exclusive".into());
+    }
+
+    Ok(filter_mode)
+}
+
+fn get_filters(matches: &getopts::Matches, filter_mode: FilterMode) -> OptPartRes<Vec<String>> {
+    let filters = matches.free.clone();
+    check_filters(&filters, filter_mode)?;
+
+    Ok(filters)
+}
+
+// Rejects the globs and regexes that don't compile, instead of silently
+// not matching any test.
+fn check_filters(filters: &[String], filter_mode: FilterMode) -> OptPartRes<()> {
+    for filter in filters {
+        let pattern = match filter_mode {
+            FilterMode::Glob => Pattern::glob(filter),
+            FilterMode::Regex => Pattern::regex(filter),
+            FilterMode::Substring | FilterMode::Exact | FilterMode::Path => continue,
+        };
+        if let Err(e) = pattern {
+            return Err(format!("invalid filter `{}`: {}", filter, e));
+        }
+    }
 
-    Ok(filter)
+    Ok(())
 }
 
 fn get_allow_unstable(matches: &getopts::Matches) -> OptPartRes<bool> {
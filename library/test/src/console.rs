@@ -5,12 +5,13 @@ use std::io;
 use std::io::prelude::Write;
 
 use super::{
-    bench::fmt_bench_samples,
+    baseline::{Baseline, BenchComparison},
+    bench::{fmt_bench_samples, BenchSamples},
     cli::TestOpts,
-    event::{CompletedTest, TestEvent},
+    event::{CompletedTest, SuiteError, TestEvent},
     filter_tests,
-    formatters::{JsonFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
-    helpers::{concurrency::get_concurrency, metrics::MetricMap},
+    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
+    helpers::{concurrency::get_concurrency, failed_list, metrics::MetricMap},
     options::{Options, OutputFormat},
     run_tests,
     test_result::TestResult,
@@ -53,6 +54,9 @@ pub struct ConsoleTestState {
     pub failures: Vec<(TestDesc, Vec<u8>)>,
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub suite_errors: Vec<SuiteError>,
+    pub baseline: Option<Baseline>,
+    pub bench_results: Baseline,
     pub options: Options,
 }
 
@@ -62,6 +66,18 @@ impl ConsoleTestState {
             Some(ref path) => Some(File::create(path)?),
             None => None,
         };
+        let baseline = match opts.baseline {
+            // A missing baseline, e.g. on the first run, shouldn't fail the tests.
+            Some(ref name) => match Baseline::load(name) {
+                Ok(baseline) => Some(baseline),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    eprintln!("warning: {}, not comparing the benchmarks", e);
+                    None
+                }
+                Err(e) => return Err(e),
+            },
+            None => None,
+        };
 
         Ok(ConsoleTestState {
             log_out,
@@ -76,6 +92,9 @@ impl ConsoleTestState {
             failures: Vec::new(),
             not_failures: Vec::new(),
             time_failures: Vec::new(),
+            suite_errors: Vec::new(),
+            baseline,
+            bench_results: Baseline::new(),
             options: opts.options,
         })
     }
@@ -112,6 +131,7 @@ impl ConsoleTestState {
                     TestResult::TrAllowedFail => "failed (allowed)".to_owned(),
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrTimedOut => "failed (timeout)".to_owned(),
                 },
                 test.name,
             )
@@ -122,6 +142,22 @@ impl ConsoleTestState {
         self.write_log(|| "\n")
     }
 
+    /// Compares the result of a benchmark to the baseline passed with `--baseline`.
+    /// Returns `None` if there is no baseline or the benchmark is not part of it.
+    pub fn compare_to_baseline(
+        &self,
+        test: &TestDesc,
+        bs: &BenchSamples,
+    ) -> Option<BenchComparison> {
+        let old = self.baseline.as_ref()?.get(test.name.as_slice())?;
+        Some(BenchComparison::new(&old.ns_iter_summ, &bs.ns_iter_summ))
+    }
+
+    /// Whether all tests passed and the suite fixtures didn't fail.
+    pub fn is_success(&self) -> bool {
+        self.failed == 0 && self.suite_errors.is_empty()
+    }
+
     fn current_test_count(&self) -> usize {
         self.passed + self.failed + self.ignored + self.measured + self.allowed_fail
     }
@@ -137,13 +173,17 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
     let quiet = opts.format == OutputFormat::Terse;
     let mut st = ConsoleTestState::new(opts)?;
 
+    if opts.format == OutputFormat::Json {
+        return list_tests_json(JsonFormatter::new(output), &mut st, opts, tests);
+    }
+
     let mut ntest = 0;
     let mut nbench = 0;
 
     for test in filter_tests(&opts, tests) {
         use crate::TestFn::*;
 
-        let TestDescAndFn { desc: TestDesc { name, .. }, testfn } = test;
+        let TestDescAndFn { desc, testfn } = test;
 
         let fntype = match testfn {
             StaticTestFn(..) | DynTestFn(..) => {
@@ -156,8 +196,8 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
             }
         };
 
-        writeln!(output, "{}: {}", name, fntype)?;
-        st.write_log(|| format!("{} {}\n", fntype, name))?;
+        writeln!(output, "{}", list_line(&desc, fntype))?;
+        st.write_log(|| format!("{} {}\n", fntype, desc.name))?;
     }
 
     fn plural(count: u32, s: &str) -> String {
@@ -178,6 +218,22 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
     Ok(())
 }
 
+// Describes a test in the human-readable `--list` output, with its location and
+// why it is ignored when known.
+pub(crate) fn list_line(desc: &TestDesc, fntype: &str) -> String {
+    let mut line = format!("{}: {}", desc.name, fntype);
+    if let Some(location) = desc.location() {
+        line.push_str(&format!(" at {}", location));
+    }
+    if desc.ignore {
+        line.push_str(", ignored");
+        if let Some(message) = desc.ignore_message() {
+            line.push_str(&format!(", {}", message));
+        }
+    }
+    line
+}
+
 // Updates `ConsoleTestState` depending on result of the test execution.
 fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest) {
     let test = completed_test.desc;
@@ -195,6 +251,7 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
                 bs.ns_iter_summ.median,
                 bs.ns_iter_summ.max - bs.ns_iter_summ.min,
             );
+            st.bench_results.insert(test.name.as_slice(), bs);
             st.measured += 1
         }
         TestResult::TrFailed => {
@@ -211,6 +268,12 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.failed += 1;
             st.time_failures.push((test, stdout));
         }
+        TestResult::TrTimedOut => {
+            st.failed += 1;
+            let mut stdout = stdout;
+            writeln!(stdout, "note: test did not finish in time and was killed").unwrap();
+            st.failures.push((test, stdout));
+        }
     }
 }
 
@@ -222,9 +285,9 @@ fn on_test_event(
     out: &mut dyn OutputFormatter,
 ) -> io::Result<()> {
     match (*event).clone() {
-        TestEvent::TeFiltered(ref filtered_tests) => {
+        TestEvent::TeFiltered(ref filtered_tests, shuffle_seed) => {
             st.total = filtered_tests.len();
-            out.write_run_start(filtered_tests.len())?;
+            out.write_run_start(filtered_tests.len(), shuffle_seed)?;
         }
         TestEvent::TeFilteredOut(filtered_out) => {
             st.filtered_out = filtered_out;
@@ -241,6 +304,11 @@ fn on_test_event(
             out.write_result(test, result, exec_time.as_ref(), &*stdout, st)?;
             handle_test_result(st, completed_test);
         }
+        TestEvent::TeSuiteError(error) => {
+            st.write_log(|| format!("suite {} failed: {}\n", error.fixture, error.message))?;
+            out.write_suite_error(&error)?;
+            st.suite_errors.push(error);
+        }
     }
 
     Ok(())
@@ -274,16 +342,49 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
             Box::new(TerseFormatter::new(output, opts.use_color(), max_name_len, is_multithreaded))
         }
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
+        OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
 
     run_tests(opts, tests, |x| on_test_event(&x, &mut st, &mut *out))?;
 
-    assert!(st.current_test_count() == st.total);
+    // No tests are run if the suite setup failed.
+    assert!(st.current_test_count() == st.total || !st.suite_errors.is_empty());
+
+    if let Some(ref name) = opts.save_baseline {
+        st.bench_results.save(name)?;
+    }
+
+    if let Some(ref path) = opts.failed_list {
+        let failures = st.failures.iter().chain(&st.time_failures);
+        failed_list::write(path, failures.map(|(desc, _)| desc.name.as_slice()))?;
+    }
 
     out.write_run_finish(&st)
 }
 
+// Lists the tests as JSON objects, one per line, including their source location.
+fn list_tests_json<T: Write>(
+    mut out: JsonFormatter<T>,
+    st: &mut ConsoleTestState,
+    opts: &TestOpts,
+    tests: Vec<TestDescAndFn>,
+) -> io::Result<()> {
+    for test in filter_tests(opts, tests) {
+        use crate::TestFn::*;
+
+        let (fntype, ty) = match test.testfn {
+            StaticTestFn(..) | DynTestFn(..) => ("test", "test"),
+            StaticBenchFn(..) | DynBenchFn(..) => ("benchmark", "bench"),
+        };
+
+        out.write_test_discovered(&test.desc, ty)?;
+        st.write_log(|| format!("{} {}\n", fntype, test.desc.name))?;
+    }
+
+    Ok(())
+}
+
 // Calculates padding for given test description.
 fn len_if_padded(t: &TestDescAndFn) -> usize {
     match t.testfn.padding() {
@@ -26,11 +26,28 @@ impl CompletedTest {
 
 unsafe impl Send for CompletedTest {}
 
+/// A failure of the `#[test_setup]` or `#[test_teardown]` functions that run once for the
+/// whole suite, so it can't be attributed to a single test.
+#[derive(Debug, Clone)]
+pub struct SuiteError {
+    /// Either `setup` or `teardown`.
+    pub fixture: &'static str,
+    pub message: String,
+    pub stdout: Vec<u8>,
+}
+
+impl SuiteError {
+    pub fn new(fixture: &'static str, message: String, stdout: Vec<u8>) -> Self {
+        Self { fixture, message, stdout }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum TestEvent {
-    TeFiltered(Vec<TestDesc>),
+    TeFiltered(Vec<TestDesc>, Option<u64>),
     TeWait(TestDesc),
     TeResult(CompletedTest),
     TeTimeout(TestDesc),
     TeFilteredOut(usize),
+    TeSuiteError(SuiteError),
 }
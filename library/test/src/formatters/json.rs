@@ -3,6 +3,7 @@ use std::{borrow::Cow, io, io::prelude::Write};
 use super::OutputFormatter;
 use crate::{
     console::{ConsoleTestState, OutputLocation},
+    event::SuiteError,
     test_result::TestResult,
     time,
     types::TestDesc,
@@ -17,6 +18,11 @@ impl<T: Write> JsonFormatter<T> {
         Self { out }
     }
 
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
     fn writeln_message(&mut self, s: &str) -> io::Result<()> {
         assert!(!s.contains('\n'));
 
@@ -33,15 +39,18 @@ impl<T: Write> JsonFormatter<T> {
     fn write_event(
         &mut self,
         ty: &str,
-        name: &str,
+        desc: &TestDesc,
         evt: &str,
         exec_time: Option<&time::TestExecTime>,
         stdout: Option<Cow<'_, str>>,
         extra: Option<&str>,
     ) -> io::Result<()> {
         self.write_message(&*format!(
-            r#"{{ "type": "{}", "name": "{}", "event": "{}""#,
-            ty, name, evt
+            r#"{{ "type": "{}", "name": "{}", "event": "{}"{}"#,
+            ty,
+            desc.name,
+            evt,
+            LocationFields(desc)
         ))?;
         if let Some(exec_time) = exec_time {
             self.write_message(&*format!(r#", "exec_time": "{}""#, exec_time))?;
@@ -54,20 +63,42 @@ impl<T: Write> JsonFormatter<T> {
         }
         self.writeln_message(" }")
     }
+
+    /// Describes a test found by `--list`, `ty` is either `test` or `bench`.
+    pub fn write_test_discovered(&mut self, desc: &TestDesc, ty: &str) -> io::Result<()> {
+        let ignore_message = match desc.ignore_message() {
+            Some(msg) => format!(r#", "message": "{}""#, EscapedString(msg)),
+            None => String::new(),
+        };
+        self.writeln_message(&*format!(
+            r#"{{ "type": "{}", "event": "discovered", "name": "{}", "ignore": {}{}{} }}"#,
+            ty,
+            desc.name,
+            desc.ignore,
+            ignore_message,
+            LocationFields(desc)
+        ))
+    }
 }
 
 impl<T: Write> OutputFormatter for JsonFormatter<T> {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        let shuffle_seed_json = if let Some(shuffle_seed) = shuffle_seed {
+            format!(r#", "shuffle_seed": {}"#, shuffle_seed)
+        } else {
+            String::new()
+        };
         self.writeln_message(&*format!(
-            r#"{{ "type": "suite", "event": "started", "test_count": {} }}"#,
-            test_count
+            r#"{{ "type": "suite", "event": "started", "test_count": {}{} }}"#,
+            test_count, shuffle_seed_json
         ))
     }
 
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()> {
         self.writeln_message(&*format!(
-            r#"{{ "type": "test", "event": "started", "name": "{}" }}"#,
-            desc.name
+            r#"{{ "type": "test", "event": "started", "name": "{}"{} }}"#,
+            desc.name,
+            LocationFields(desc)
         ))
     }
 
@@ -87,38 +118,54 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
         };
         match *result {
             TestResult::TrOk => {
-                self.write_event("test", desc.name.as_slice(), "ok", exec_time, stdout, None)
+                self.write_event("test", desc, "ok", exec_time, stdout, None)
             }
 
             TestResult::TrFailed => {
-                self.write_event("test", desc.name.as_slice(), "failed", exec_time, stdout, None)
+                self.write_event("test", desc, "failed", exec_time, stdout, None)
             }
 
             TestResult::TrTimedFail => self.write_event(
                 "test",
-                desc.name.as_slice(),
+                desc,
                 "failed",
                 exec_time,
                 stdout,
                 Some(r#""reason": "time limit exceeded""#),
             ),
 
+            TestResult::TrTimedOut => self.write_event(
+                "test",
+                desc,
+                "failed",
+                exec_time,
+                stdout,
+                Some(r#""reason": "timeout""#),
+            ),
+
             TestResult::TrFailedMsg(ref m) => self.write_event(
                 "test",
-                desc.name.as_slice(),
+                desc,
                 "failed",
                 exec_time,
                 stdout,
                 Some(&*format!(r#""message": "{}""#, EscapedString(m))),
             ),
 
-            TestResult::TrIgnored => {
-                self.write_event("test", desc.name.as_slice(), "ignored", exec_time, stdout, None)
-            }
+            TestResult::TrIgnored => self.write_event(
+                "test",
+                desc,
+                "ignored",
+                exec_time,
+                stdout,
+                desc.ignore_message()
+                    .map(|msg| format!(r#""message": "{}""#, EscapedString(msg)))
+                    .as_deref(),
+            ),
 
             TestResult::TrAllowedFail => self.write_event(
                 "test",
-                desc.name.as_slice(),
+                desc,
                 "allowed_failure",
                 exec_time,
                 stdout,
@@ -135,12 +182,37 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
                     format!(r#", "mib_per_second": {}"#, bs.mb_s)
                 };
 
+                let summ = &bs.ns_iter_summ;
+                let baseline = match state.compare_to_baseline(desc, bs) {
+                    Some(comparison) => format!(
+                        r#", "baseline_median": {}, "change_pct": {}, "change": "{}""#,
+                        comparison.baseline_median,
+                        comparison.change_pct,
+                        comparison.change.as_str()
+                    ),
+                    None => String::new(),
+                };
+
                 let line = format!(
                     "{{ \"type\": \"bench\", \
                      \"name\": \"{}\", \
                      \"median\": {}, \
-                     \"deviation\": {}{} }}",
-                    desc.name, median, deviation, mbps
+                     \"deviation\": {}{}, \
+                     \"mean\": {}, \
+                     \"min\": {}, \
+                     \"max\": {}, \
+                     \"std_dev\": {}, \
+                     \"median_abs_dev\": {}{} }}",
+                    desc.name,
+                    median,
+                    deviation,
+                    mbps,
+                    summ.mean,
+                    summ.min,
+                    summ.max,
+                    summ.std_dev,
+                    summ.median_abs_dev,
+                    baseline
                 );
 
                 self.writeln_message(&*line)
@@ -155,6 +227,21 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
         ))
     }
 
+    fn write_suite_error(&mut self, error: &SuiteError) -> io::Result<()> {
+        let stdout = if error.stdout.is_empty() {
+            String::new()
+        } else {
+            let stdout = String::from_utf8_lossy(&error.stdout);
+            format!(r#", "stdout": "{}""#, EscapedString(stdout))
+        };
+        self.writeln_message(&*format!(
+            r#"{{ "type": "suite", "event": "error", "fixture": "{}", "message": "{}"{} }}"#,
+            error.fixture,
+            EscapedString(&error.message),
+            stdout
+        ))
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         self.writeln_message(&*format!(
             "{{ \"type\": \"suite\", \
@@ -165,7 +252,7 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
              \"ignored\": {}, \
              \"measured\": {}, \
              \"filtered_out\": {} }}",
-            if state.failed == 0 { "ok" } else { "failed" },
+            if state.is_success() { "ok" } else { "failed" },
             state.passed,
             state.failed + state.allowed_fail,
             state.allowed_fail,
@@ -174,7 +261,26 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
             state.filtered_out
         ))?;
 
-        Ok(state.failed == 0)
+        Ok(state.is_success())
+    }
+}
+
+/// A formatting utility used to print the source location of a test as additional fields
+/// of a JSON object. Prints nothing if the location is unknown.
+struct LocationFields<'a>(&'a TestDesc);
+
+impl std::fmt::Display for LocationFields<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0.location() {
+            Some(loc) => write!(
+                f,
+                r#", "source_file": "{}", "line": {}, "column": {}"#,
+                EscapedString(loc.file),
+                loc.line,
+                loc.col
+            ),
+            None => Ok(()),
+        }
     }
 }
 
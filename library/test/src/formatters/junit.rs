@@ -0,0 +1,233 @@
+use std::io::{self, prelude::Write};
+use std::time::Duration;
+
+use super::OutputFormatter;
+use crate::{
+    console::{ConsoleTestState, OutputLocation},
+    event::SuiteError,
+    test_result::TestResult,
+    time,
+    types::{TestDesc, TestType},
+};
+
+pub(crate) struct JunitFormatter<T> {
+    out: OutputLocation<T>,
+    results: Vec<(TestDesc, TestResult, Duration, Vec<u8>)>,
+}
+
+impl<T: Write> JunitFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out, results: Vec::new() }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn writeln_message(&mut self, s: &str) -> io::Result<()> {
+        self.out.write_all(s.as_ref())?;
+        self.out.write_all(b"\n")
+    }
+}
+
+impl<T: Write> OutputFormatter for JunitFormatter<T> {
+    fn write_run_start(
+        &mut self,
+        _test_count: usize,
+        _shuffle_seed: Option<u64>,
+    ) -> io::Result<()> {
+        // We write the xml header on run start, everything else has to wait until the run
+        // finishes as the `testsuite` node holds the totals as attributes.
+        self.writeln_message(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // We do not output anything on test start.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // We do not output anything on test timeout.
+        Ok(())
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        _state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        // Execution times are only measured with `--report-time`, report zero otherwise.
+        let duration = exec_time.map(|t| t.0).unwrap_or_default();
+        self.results.push((desc.clone(), result.clone(), duration, stdout.to_vec()));
+        Ok(())
+    }
+
+    fn write_suite_error(&mut self, _error: &SuiteError) -> io::Result<()> {
+        // Suite errors are reported as `error` elements when the run finishes.
+        Ok(())
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let total_time: Duration = self.results.iter().map(|(_, _, duration, _)| *duration).sum();
+
+        self.writeln_message("<testsuites>")?;
+        self.writeln_message(&*format!(
+            "<testsuite name=\"test\" package=\"test\" id=\"0\" \
+             errors=\"{}\" \
+             failures=\"{}\" \
+             tests=\"{}\" \
+             skipped=\"{}\" \
+             time=\"{}\">",
+            state.suite_errors.len(),
+            state.failed,
+            state.total,
+            state.ignored,
+            total_time.as_secs_f64()
+        ))?;
+
+        for (desc, result, duration, stdout) in std::mem::take(&mut self.results) {
+            let (class_name, test_name) = parse_class_name(&desc);
+            let class_name = EscapedString(&class_name);
+            let test_name = EscapedString(&test_name);
+            let time = duration.as_secs_f64();
+
+            let body = match result {
+                TestResult::TrOk | TestResult::TrAllowedFail => None,
+                TestResult::TrIgnored => Some("<skipped/>".to_owned()),
+                TestResult::TrFailed => Some(r#"<failure type="assert"/>"#.to_owned()),
+                TestResult::TrFailedMsg(ref m) => {
+                    Some(format!(r#"<failure type="assert" message="{}"/>"#, EscapedString(m)))
+                }
+                TestResult::TrTimedFail => Some(
+                    r#"<failure type="timeout" message="time limit exceeded"/>"#.to_owned(),
+                ),
+                TestResult::TrTimedOut => Some(
+                    r#"<failure type="timeout" message="test was killed after its timeout"/>"#
+                        .to_owned(),
+                ),
+                TestResult::TrBench(ref bs) => {
+                    // Benchmarks report the median time of a single iteration.
+                    let median = Duration::from_nanos(bs.ns_iter_summ.median as u64);
+                    self.writeln_message(&*format!(
+                        r#"<testcase classname="benchmark::{}" name="{}" time="{}"/>"#,
+                        class_name,
+                        test_name,
+                        median.as_secs_f64()
+                    ))?;
+                    continue;
+                }
+            };
+
+            if body.is_none() && stdout.is_empty() {
+                self.writeln_message(&*format!(
+                    r#"<testcase classname="{}" name="{}" time="{}"/>"#,
+                    class_name, test_name, time
+                ))?;
+                continue;
+            }
+
+            self.writeln_message(&*format!(
+                r#"<testcase classname="{}" name="{}" time="{}">"#,
+                class_name, test_name, time
+            ))?;
+            if let Some(body) = body {
+                self.writeln_message(&body)?;
+            }
+            if !stdout.is_empty() {
+                let stdout = String::from_utf8_lossy(&stdout);
+                self.writeln_message(&*format!(
+                    "<system-out>{}</system-out>",
+                    EscapedString(&*stdout)
+                ))?;
+            }
+            self.writeln_message("</testcase>")?;
+        }
+
+        // The failed suite fixtures aren't tests, so they are listed as errors of
+        // pseudo test cases named after the fixture.
+        for error in &state.suite_errors {
+            self.writeln_message(&*format!(
+                r#"<testcase classname="suite" name="{}" time="0">"#,
+                error.fixture
+            ))?;
+            self.writeln_message(&*format!(
+                r#"<error type="{}" message="{}"/>"#,
+                error.fixture,
+                EscapedString(&error.message)
+            ))?;
+            if !error.stdout.is_empty() {
+                let stdout = String::from_utf8_lossy(&error.stdout);
+                self.writeln_message(&*format!(
+                    "<system-out>{}</system-out>",
+                    EscapedString(&*stdout)
+                ))?;
+            }
+            self.writeln_message("</testcase>")?;
+        }
+
+        self.writeln_message("<system-out/>")?;
+        self.writeln_message("<system-err/>")?;
+        self.writeln_message("</testsuite>")?;
+        self.writeln_message("</testsuites>")?;
+
+        Ok(state.is_success())
+    }
+}
+
+/// Splits a test name into the JUnit class name, i.e. the module path, and the name of the test
+/// itself.
+fn parse_class_name(desc: &TestDesc) -> (String, String) {
+    let name = desc.name.as_slice();
+    match desc.test_type {
+        // Doctest names look like `src/lib.rs - foo::bar (line 12)`, keep them whole.
+        TestType::DocTest => ("doctest".to_owned(), name.to_owned()),
+        TestType::UnitTest | TestType::IntegrationTest | TestType::Unknown => {
+            match name.rfind("::") {
+                Some(pos) => (name[..pos].to_owned(), name[pos + 2..].to_owned()),
+                None => ("crate".to_owned(), name.to_owned()),
+            }
+        }
+    }
+}
+
+/// A formatting utility used to print strings with characters that are special in XML escaped.
+/// Control characters that XML 1.0 cannot represent are replaced with U+FFFD.
+struct EscapedString<S: AsRef<str>>(S);
+
+impl<S: AsRef<str>> std::fmt::Display for EscapedString<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = self.0.as_ref();
+        let mut start = 0;
+
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '"' => "&quot;",
+                '\'' => "&apos;",
+                '\t' | '\n' | '\r' => continue,
+                '\0'..='\u{1f}' => "\u{FFFD}",
+                _ => continue,
+            };
+
+            if start < i {
+                f.write_str(&s[start..i])?;
+            }
+
+            f.write_str(escaped)?;
+
+            start = i + c.len_utf8();
+        }
+
+        if start != s.len() {
+            f.write_str(&s[start..])?;
+        }
+
+        Ok(())
+    }
+}
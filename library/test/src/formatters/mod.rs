@@ -2,21 +2,24 @@ use std::{io, io::prelude::Write};
 
 use crate::{
     console::ConsoleTestState,
+    event::SuiteError,
     test_result::TestResult,
     time,
     types::{TestDesc, TestName},
 };
 
 mod json;
+mod junit;
 mod pretty;
 mod terse;
 
 pub(crate) use self::json::JsonFormatter;
+pub(crate) use self::junit::JunitFormatter;
 pub(crate) use self::pretty::PrettyFormatter;
 pub(crate) use self::terse::TerseFormatter;
 
 pub(crate) trait OutputFormatter {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()>;
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()>;
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()>;
     fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()>;
     fn write_result(
@@ -27,9 +30,28 @@ pub(crate) trait OutputFormatter {
         stdout: &[u8],
         state: &ConsoleTestState,
     ) -> io::Result<()>;
+    fn write_suite_error(&mut self, error: &SuiteError) -> io::Result<()>;
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool>;
 }
 
+/// Formats the failed suite fixtures like the failed tests, with their output followed by a
+/// summary.
+pub(crate) fn fmt_suite_errors(errors: &[SuiteError]) -> String {
+    let mut out = String::from("\nsuite errors:\n\n");
+    for error in errors {
+        if !error.stdout.is_empty() {
+            out.push_str(&format!("---- suite {} stdout ----\n", error.fixture));
+            out.push_str(&String::from_utf8_lossy(&error.stdout));
+            out.push('\n');
+        }
+    }
+    out.push_str("\nsuite errors:\n");
+    for error in errors {
+        out.push_str(&format!("    {}: {}\n", error.fixture, error.message));
+    }
+    out
+}
+
 pub(crate) fn write_stderr_delimiter(test_output: &mut Vec<u8>, test_name: &TestName) {
     match test_output.last() {
         Some(b'\n') => (),
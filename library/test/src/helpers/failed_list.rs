@@ -0,0 +1,37 @@
+//! Helper module for recording the names of failed tests, so that they can be
+//! run again with `--rerun-failed`.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Returns the file used by `--record-failed` and `--rerun-failed` when no path
+/// is given. It is placed next to the test binary, which is usually inside of the
+/// target directory.
+pub fn default_path() -> PathBuf {
+    let exe = env::current_exe()
+        .unwrap_or_else(|_| PathBuf::from(env::args_os().next().unwrap_or_default()));
+    let mut path = exe.into_os_string();
+    path.push(".failed");
+    PathBuf::from(path)
+}
+
+/// Reads the names of the tests recorded by `write`.
+pub fn read(path: &Path) -> io::Result<BTreeSet<String>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(contents.lines().filter(|line| !line.is_empty()).map(str::to_owned).collect())
+}
+
+/// Records the names of the failed tests, one per line. An earlier record is
+/// replaced, so the file is empty after a successful run.
+pub fn write<'a>(path: &Path, names: impl Iterator<Item = &'a str>) -> io::Result<()> {
+    let names: BTreeSet<&str> = names.collect();
+    let mut contents = String::new();
+    for name in names {
+        contents.push_str(name);
+        contents.push('\n');
+    }
+    fs::write(path, contents)
+}
@@ -3,6 +3,9 @@
 
 pub mod concurrency;
 pub mod exit_code;
+pub mod failed_list;
 pub mod isatty;
 pub mod metrics;
-pub mod sink;
+pub mod pattern;
+pub mod process_group;
+pub mod shuffle;
@@ -0,0 +1,471 @@
+//! Helper module for matching test names against glob and regex filters.
+//!
+//! libtest can't depend on the `regex` crate, so this is a small matcher
+//! supporting the commonly used subset of the regex syntax: literals, `.`,
+//! character classes (`[a-z]`, `[^_]`, `\d`, `\w`, `\s`), the anchors `^` and
+//! `$`, groups, alternation and the repetitions `*`, `+`, `?` and `{n,m}`.
+//! Globs are translated to anchored regexes.
+//!
+//! Patterns are compiled to a program for a Pike VM, which simulates all
+//! possible matches at once instead of backtracking. Matching a name therefore
+//! takes time linear in its length, whatever the pattern looks like.
+
+// The largest count allowed in a `{n,m}` repetition.
+const MAX_REPETITION: usize = 1000;
+
+// The largest number of instructions a compiled pattern may have. Repetitions
+// are compiled by copying the repeated item, so nesting them multiplies sizes.
+const MAX_PROGRAM_LEN: usize = 10_000;
+
+/// A compiled glob or regex pattern.
+#[derive(Debug)]
+pub struct Pattern {
+    program: Vec<Inst>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    Group(Vec<Vec<Node>>),
+    Repeat(Box<Node>, usize, Option<usize>),
+}
+
+#[derive(Clone, Debug)]
+struct Class {
+    ranges: Vec<(char, char)>,
+    negated: bool,
+}
+
+impl Class {
+    fn new(ranges: Vec<(char, char)>, negated: bool) -> Class {
+        Class { ranges, negated }
+    }
+
+    fn matches(&self, c: char) -> bool {
+        self.ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != self.negated
+    }
+}
+
+impl Pattern {
+    /// Compiles a regex. Like `Regex::is_match`, the pattern may match anywhere
+    /// in the name unless it is anchored with `^` or `$`.
+    pub fn regex(pattern: &str) -> Result<Pattern, String> {
+        let mut parser = Parser { chars: pattern.chars().collect(), pos: 0 };
+        let alternatives = parser.parse_alternatives()?;
+        match parser.peek() {
+            None => {
+                let mut compiler = Compiler { program: Vec::new() };
+                compiler.compile_alternatives(&alternatives)?;
+                compiler.push(Inst::Match)?;
+                Ok(Pattern { program: compiler.program })
+            }
+            Some(c) => {
+                Err(format!("unexpected `{}` at offset {} of regex `{}`", c, parser.pos, pattern))
+            }
+        }
+    }
+
+    /// Compiles a glob, which has to match the whole name. `*` matches any
+    /// sequence of characters, `?` matches a single character and `[...]`
+    /// (or `[!...]`) matches a character of (or not of) a set.
+    pub fn glob(pattern: &str) -> Result<Pattern, String> {
+        let mut regex = String::from("^");
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' => regex.push_str(".*"),
+                '?' => regex.push('.'),
+                '[' => {
+                    regex.push('[');
+                    let mut first = true;
+                    loop {
+                        match chars.next() {
+                            Some('!') if first => regex.push('^'),
+                            Some(']') if !first => break,
+                            Some(c @ '\\') | Some(c @ '[') | Some(c @ '^') => {
+                                regex.push('\\');
+                                regex.push(c);
+                            }
+                            Some(c) => regex.push(c),
+                            None => return Err(format!("unclosed `[` in glob `{}`", pattern)),
+                        }
+                        first = false;
+                    }
+                    regex.push(']');
+                }
+                c => {
+                    if is_special(c) {
+                        regex.push('\\');
+                    }
+                    regex.push(c);
+                }
+            }
+        }
+        regex.push('$');
+        Pattern::regex(&regex)
+    }
+
+    /// Whether the pattern matches `name`.
+    pub fn is_match(&self, name: &str) -> bool {
+        let input: Vec<char> = name.chars().collect();
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        for pos in 0..=input.len() {
+            // The pattern isn't anchored, so a match may start at any position.
+            if current.add(&self.program, 0, pos, input.len()) {
+                return true;
+            }
+            let c = match input.get(pos) {
+                Some(&c) => c,
+                None => break,
+            };
+            for &pc in &current.pcs {
+                let matches = match &self.program[pc] {
+                    Inst::Char(expected) => c == *expected,
+                    Inst::Any => true,
+                    Inst::Class(class) => class.matches(c),
+                    _ => false,
+                };
+                if matches && next.add(&self.program, pc + 1, pos + 1, input.len()) {
+                    return true;
+                }
+            }
+            std::mem::swap(&mut current, &mut next);
+            next.clear();
+        }
+        false
+    }
+}
+
+fn is_special(c: char) -> bool {
+    "\\.+*?()|[]{}^$".contains(c)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_alternatives(&mut self) -> Result<Vec<Vec<Node>>, String> {
+        let mut alternatives = vec![self.parse_sequence()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn parse_sequence(&mut self) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_repetition(atom)?);
+        }
+        Ok(nodes)
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, String> {
+        let c = self.next().unwrap();
+        let node = match c {
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '(' => {
+                // Groups never capture, so `(?:...)` is the same as `(...)`.
+                if self.eat('?') && !self.eat(':') {
+                    return Err(format!("unsupported group flags at offset {}", self.pos));
+                }
+                let alternatives = self.parse_alternatives()?;
+                if !self.eat(')') {
+                    return Err("unclosed group".to_string());
+                }
+                Node::Group(alternatives)
+            }
+            '[' => Node::Class(self.parse_class()?),
+            '\\' => match self.parse_escape()? {
+                Escape::Char(c) => Node::Char(c),
+                Escape::Class(class) => Node::Class(class),
+            },
+            '*' | '+' | '?' | '{' => {
+                return Err(format!("repetition `{}` without a preceding item", c));
+            }
+            ']' | '}' => return Err(format!("unopened `{}`", c)),
+            c => Node::Char(c),
+        };
+        Ok(node)
+    }
+
+    fn parse_repetition(&mut self, atom: Node) -> Result<Node, String> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.pos += 1;
+                let min = self.parse_number()?;
+                let max = if self.eat(',') {
+                    if self.peek() == Some('}') { None } else { Some(self.parse_number()?) }
+                } else {
+                    Some(min)
+                };
+                if !self.eat('}') {
+                    return Err("unclosed `{`".to_string());
+                }
+                if max.unwrap_or(min) > MAX_REPETITION {
+                    return Err(format!(
+                        "repetition count exceeds the maximum of {}",
+                        MAX_REPETITION
+                    ));
+                }
+                if max.map_or(false, |max| max < min) {
+                    return Err(format!("invalid repetition `{{{},{}}}`", min, max.unwrap()));
+                }
+                return self.parse_repetition(Node::Repeat(Box::new(atom), min, max));
+            }
+            _ => return Ok(atom),
+        };
+        self.pos += 1;
+        self.parse_repetition(Node::Repeat(Box::new(atom), min, max))
+    }
+
+    fn parse_number(&mut self) -> Result<usize, String> {
+        let start = self.pos;
+        while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        digits.parse().map_err(|_| format!("expected a number at offset {}", start))
+    }
+
+    fn parse_class(&mut self) -> Result<Class, String> {
+        let negated = self.eat('^');
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let lo = match self.next() {
+                None => return Err("unclosed `[`".to_string()),
+                Some(']') if !first => break,
+                Some('\\') => match self.parse_escape()? {
+                    Escape::Char(c) => c,
+                    Escape::Class(class) if !class.negated => {
+                        ranges.extend(class.ranges);
+                        first = false;
+                        continue;
+                    }
+                    Escape::Class(_) => {
+                        return Err("negated classes are not supported inside `[]`".to_string());
+                    }
+                },
+                Some(c) => c,
+            };
+            first = false;
+            let hi = if self.peek() == Some('-') && self.chars.get(self.pos + 1) != Some(&']') {
+                self.pos += 1;
+                match self.next() {
+                    None => return Err("unclosed `[`".to_string()),
+                    Some('\\') => match self.parse_escape()? {
+                        Escape::Char(c) => c,
+                        Escape::Class(_) => return Err("invalid range in `[]`".to_string()),
+                    },
+                    Some(c) => c,
+                }
+            } else {
+                lo
+            };
+            if hi < lo {
+                return Err(format!("invalid range `{}-{}`", lo, hi));
+            }
+            ranges.push((lo, hi));
+        }
+        Ok(Class::new(ranges, negated))
+    }
+
+    fn parse_escape(&mut self) -> Result<Escape, String> {
+        let digit = vec![('0', '9')];
+        let word = vec![('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+        let space = vec![('\t', '\r'), (' ', ' ')];
+        let escape = match self.next() {
+            None => return Err("incomplete escape sequence".to_string()),
+            Some('d') => Escape::Class(Class::new(digit, false)),
+            Some('D') => Escape::Class(Class::new(digit, true)),
+            Some('w') => Escape::Class(Class::new(word, false)),
+            Some('W') => Escape::Class(Class::new(word, true)),
+            Some('s') => Escape::Class(Class::new(space, false)),
+            Some('S') => Escape::Class(Class::new(space, true)),
+            Some('t') => Escape::Char('\t'),
+            Some('n') => Escape::Char('\n'),
+            Some(c) if is_special(c) || c == '-' || c == ':' => Escape::Char(c),
+            Some(c) => return Err(format!("unsupported escape sequence `\\{}`", c)),
+        };
+        Ok(escape)
+    }
+}
+
+enum Escape {
+    Char(char),
+    Class(Class),
+}
+
+#[derive(Debug)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    // Continues at both targets, preferring the first.
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+struct Compiler {
+    program: Vec<Inst>,
+}
+
+impl Compiler {
+    fn push(&mut self, inst: Inst) -> Result<usize, String> {
+        if self.program.len() == MAX_PROGRAM_LEN {
+            return Err("pattern is too large".to_string());
+        }
+        self.program.push(inst);
+        Ok(self.program.len() - 1)
+    }
+
+    fn compile_alternatives(&mut self, alternatives: &[Vec<Node>]) -> Result<(), String> {
+        let (last, rest) = alternatives.split_last().unwrap();
+        let mut jumps = Vec::new();
+        for seq in rest {
+            let split = self.push(Inst::Split(0, 0))?;
+            self.compile_seq(seq)?;
+            jumps.push(self.push(Inst::Jump(0))?);
+            self.program[split] = Inst::Split(split + 1, self.program.len());
+        }
+        self.compile_seq(last)?;
+        for jump in jumps {
+            self.program[jump] = Inst::Jump(self.program.len());
+        }
+        Ok(())
+    }
+
+    fn compile_seq(&mut self, nodes: &[Node]) -> Result<(), String> {
+        nodes.iter().try_for_each(|node| self.compile_node(node))
+    }
+
+    fn compile_node(&mut self, node: &Node) -> Result<(), String> {
+        match node {
+            Node::Char(c) => self.push(Inst::Char(*c)).map(drop),
+            Node::Any => self.push(Inst::Any).map(drop),
+            Node::Class(class) => self.push(Inst::Class(class.clone())).map(drop),
+            Node::Start => self.push(Inst::Start).map(drop),
+            Node::End => self.push(Inst::End).map(drop),
+            Node::Group(alternatives) => self.compile_alternatives(alternatives),
+            Node::Repeat(node, min, max) => {
+                for _ in 0..*min {
+                    self.compile_node(node)?;
+                }
+                match max {
+                    None => {
+                        let split = self.push(Inst::Split(0, 0))?;
+                        self.compile_node(node)?;
+                        self.push(Inst::Jump(split))?;
+                        self.program[split] = Inst::Split(split + 1, self.program.len());
+                    }
+                    Some(max) => {
+                        for _ in *min..*max {
+                            let split = self.push(Inst::Split(0, 0))?;
+                            self.compile_node(node)?;
+                            self.program[split] = Inst::Split(split + 1, self.program.len());
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// The VM threads at one position of the input.
+struct Threads {
+    // The threads waiting on a character.
+    pcs: Vec<usize>,
+    // Every instruction visited at this position, so that each is followed at
+    // most once and empty loops terminate.
+    visited: Vec<usize>,
+    contains: Vec<bool>,
+    stack: Vec<usize>,
+}
+
+impl Threads {
+    fn new(len: usize) -> Threads {
+        Threads {
+            pcs: Vec::new(),
+            visited: Vec::new(),
+            contains: vec![false; len],
+            stack: Vec::new(),
+        }
+    }
+
+    fn clear(&mut self) {
+        for &pc in &self.visited {
+            self.contains[pc] = false;
+        }
+        self.pcs.clear();
+        self.visited.clear();
+    }
+
+    // Adds a thread at `pc` and everything reachable from it without consuming
+    // input. Returns whether a `Match` is reachable.
+    fn add(&mut self, program: &[Inst], pc: usize, pos: usize, len: usize) -> bool {
+        self.stack.clear();
+        self.stack.push(pc);
+        while let Some(pc) = self.stack.pop() {
+            if self.contains[pc] {
+                continue;
+            }
+            self.contains[pc] = true;
+            self.visited.push(pc);
+            match program[pc] {
+                Inst::Char(_) | Inst::Any | Inst::Class(_) => self.pcs.push(pc),
+                Inst::Start if pos == 0 => self.stack.push(pc + 1),
+                Inst::End if pos == len => self.stack.push(pc + 1),
+                Inst::Start | Inst::End => {}
+                Inst::Split(first, second) => {
+                    self.stack.push(second);
+                    self.stack.push(first);
+                }
+                Inst::Jump(target) => self.stack.push(target),
+                Inst::Match => return true,
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,44 @@
+//! Helper module to kill a test subprocess along with the processes it started.
+
+use std::io;
+use std::process::{Child, Command};
+
+/// Spawns `command` in a process group of its own, so that `kill_process_group`
+/// also reaches the processes it starts.
+#[cfg(unix)]
+pub fn spawn_in_process_group(command: &mut Command) -> io::Result<Child> {
+    use std::os::unix::process::CommandExt;
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setpgid(0, 0) == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        });
+    }
+    command.spawn()
+}
+
+#[cfg(not(unix))]
+pub fn spawn_in_process_group(command: &mut Command) -> io::Result<Child> {
+    command.spawn()
+}
+
+/// Kills `child` and, where process groups are supported, every process in its
+/// group. The child still has to be waited for afterwards.
+#[cfg(unix)]
+pub fn kill_process_group(child: &mut Child) -> io::Result<()> {
+    // The child has not been waited for yet, so its group still exists even if it
+    // has already exited.
+    if unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+pub fn kill_process_group(child: &mut Child) -> io::Result<()> {
+    child.kill()
+}
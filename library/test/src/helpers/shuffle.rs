@@ -0,0 +1,69 @@
+//! Helper module for running tests in a random, but reproducible order.
+
+use crate::cli::TestOpts;
+use crate::types::TestDescAndFn;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the seed the tests should be shuffled with, if shuffling was requested.
+/// An explicit `--shuffle-seed` wins, `--shuffle` alone picks a seed from the current time.
+pub fn get_shuffle_seed(opts: &TestOpts) -> Option<u64> {
+    opts.shuffle_seed.or_else(|| {
+        if opts.shuffle {
+            Some(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Failed to get system time")
+                    .as_nanos() as u64,
+            )
+        } else {
+            None
+        }
+    })
+}
+
+/// Shuffles the tests in place. The resulting order only depends on the seed and the set of
+/// test names, so the same seed reproduces the same order for the same test binary.
+pub fn shuffle_tests(shuffle_seed: u64, tests: &mut [TestDescAndFn]) {
+    let test_names: Vec<&str> = tests.iter().map(|test| test.desc.name.as_slice()).collect();
+    let test_names_hash = calculate_hash(&test_names);
+    let mut rng = Rng::new(shuffle_seed, test_names_hash);
+    shuffle(&mut rng, tests);
+}
+
+// A Fisher-Yates shuffle.
+fn shuffle<T>(rng: &mut Rng, slice: &mut [T]) {
+    for i in 0..slice.len() {
+        let idx = i + rng.rand_below((slice.len() - i) as u64) as usize;
+        slice.swap(i, idx);
+    }
+}
+
+/// A tiny pseudo random number generator. `DefaultHasher::new` uses fixed keys, so the
+/// produced sequence is stable for a given seed.
+struct Rng {
+    state: u64,
+    extra: u64,
+}
+
+impl Rng {
+    fn new(seed: u64, extra: u64) -> Self {
+        Self { state: seed, extra }
+    }
+
+    fn rand_below(&mut self, bound: u64) -> u64 {
+        self.rand_u64() % bound
+    }
+
+    fn rand_u64(&mut self) -> u64 {
+        self.state = calculate_hash(&(self.state, self.extra));
+        self.state
+    }
+}
+
+fn calculate_hash<T: Hash>(t: &T) -> u64 {
+    let mut s = DefaultHasher::new();
+    t.hash(&mut s);
+    s.finish()
+}
@@ -24,7 +24,9 @@
 #![feature(rustc_private)]
 #![feature(nll)]
 #![feature(bool_to_option)]
-#![feature(set_stdio)]
+#![feature(child_wait_timeout)]
+#![feature(available_parallelism)]
+#![feature(internal_output_capture)]
 #![feature(panic_unwind)]
 #![feature(staged_api)]
 #![feature(termination_trait_lib)]
@@ -34,7 +36,9 @@
 // Public reexports
 pub use self::bench::{black_box, Bencher};
 pub use self::console::run_tests_console;
-pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
+pub use self::options::{
+    ColorConfig, FilterMode, Options, OutputFormat, Partition, RunIgnored, ShouldPanic,
+};
 pub use self::types::TestName::*;
 pub use self::types::*;
 pub use self::ColorConfig::*;
@@ -48,13 +52,13 @@ pub mod test {
         cli::{parse_opts, TestOpts},
         filter_tests,
         helpers::metrics::{Metric, MetricMap},
-        options::{Options, RunIgnored, RunStrategy, ShouldPanic},
+        options::{FilterMode, Options, RunIgnored, RunStrategy, ShouldPanic},
         run_test, test_main, test_main_static,
         test_result::{TestResult, TrFailed, TrFailedMsg, TrIgnored, TrOk},
         time::{TestExecTime, TestTimeOptions},
         types::{
             DynTestFn, DynTestName, StaticBenchFn, StaticTestFn, StaticTestName, TestDesc,
-            TestDescAndFn, TestName, TestType,
+            TestDescAndFn, TestLocation, TestName, TestType,
         },
     };
 }
@@ -64,12 +68,13 @@ use std::{
     io::prelude::Write,
     panic::{self, catch_unwind, AssertUnwindSafe, PanicInfo},
     process::{self, Command, Termination},
-    sync::mpsc::{channel, Sender},
+    sync::mpsc::{channel, Receiver, Sender},
     sync::{Arc, Mutex},
     thread,
     time::{Duration, Instant},
 };
 
+mod baseline;
 pub mod bench;
 mod cli;
 mod console;
@@ -85,10 +90,12 @@ mod types;
 #[cfg(test)]
 mod tests;
 
-use event::{CompletedTest, TestEvent};
+use event::{CompletedTest, SuiteError, TestEvent};
 use helpers::concurrency::get_concurrency;
 use helpers::exit_code::get_exit_code;
-use helpers::sink::Sink;
+use helpers::pattern::Pattern;
+use helpers::process_group::{kill_process_group, spawn_in_process_group};
+use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use options::{Concurrent, RunStrategy};
 use test_result::*;
 use time::TestExecTime;
@@ -97,6 +104,7 @@ use time::TestExecTime;
 const ERROR_EXIT_CODE: i32 = 101;
 
 const SECONDARY_TEST_INVOKER_VAR: &str = "__RUST_TEST_INVOKE";
+const SECONDARY_SUITE_FIXTURE_VAR: &str = "__RUST_TEST_INVOKE_SUITE_FIXTURE";
 
 // The default console test runner. It accepts the command line
 // arguments and a vector of test_descs.
@@ -135,11 +143,27 @@ pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Opt
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=unwind.
 pub fn test_main_static(tests: &[&TestDescAndFn]) {
+    // Tests with a timeout are run in a subprocess, see `test_main_static_abort`.
+    run_test_if_spawned_secondary(tests, &TestFixtures::default());
+
     let args = env::args().collect::<Vec<_>>();
     let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
     test_main(&args, owned_tests, None)
 }
 
+/// Like `test_main_static`, but runs the given fixtures around the tests.
+///
+/// This is the entry point for the main function generated by `rustc --test`
+/// when panic=unwind and the crate has `#[test_setup]` or `#[test_teardown]`
+/// functions.
+pub fn test_main_static_with_fixtures(tests: &[&TestDescAndFn], fixtures: &TestFixtures) {
+    run_test_if_spawned_secondary(tests, fixtures);
+
+    let args = env::args().collect::<Vec<_>>();
+    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
+    test_main(&args, owned_tests, Some(Options::new().fixtures(*fixtures)))
+}
+
 /// A variant optimized for invocation with a static test vector.
 /// This will panic (intentionally) when fed any dynamic tests.
 ///
@@ -149,8 +173,38 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=abort.
 pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
-    // If we're being run in SpawnedSecondary mode, run the test here. run_test
-    // will then exit the process.
+    test_main_static_abort_with_fixtures(tests, &TestFixtures::default())
+}
+
+/// Like `test_main_static_abort`, but runs the given fixtures around the tests.
+///
+/// This is the entry point for the main function generated by `rustc --test`
+/// when panic=abort and the crate has `#[test_setup]` or `#[test_teardown]`
+/// functions.
+pub fn test_main_static_abort_with_fixtures(tests: &[&TestDescAndFn], fixtures: &TestFixtures) {
+    run_test_if_spawned_secondary(tests, fixtures);
+
+    let args = env::args().collect::<Vec<_>>();
+    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
+    let options = Options::new().panic_abort(true).fixtures(*fixtures);
+    test_main(&args, owned_tests, Some(options))
+}
+
+/// If we're being run in SpawnedSecondary mode, run the requested test or suite
+/// fixtures here. They will then exit the process.
+///
+/// A test subprocess only runs the fixtures around that test, the suite fixtures
+/// are run in subprocesses of their own.
+fn run_test_if_spawned_secondary(tests: &[&TestDescAndFn], fixtures: &TestFixtures) {
+    if let Ok(fixture) = env::var(SECONDARY_SUITE_FIXTURE_VAR) {
+        env::remove_var(SECONDARY_SUITE_FIXTURE_VAR);
+        let suite_fixtures = match &*fixture {
+            "setup" => fixtures.setup,
+            "teardown" => fixtures.teardown,
+            _ => panic!("unknown suite fixture '{}'", fixture),
+        };
+        run_suite_fixtures_in_spawned_subprocess(suite_fixtures);
+    }
     if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
         env::remove_var(SECONDARY_TEST_INVOKER_VAR);
         let test = tests
@@ -164,12 +218,8 @@ pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
             StaticTestFn(f) => f,
             _ => panic!("only static tests are supported"),
         };
-        run_test_in_spawned_subprocess(desc, Box::new(testfn));
+        run_test_in_spawned_subprocess(desc, Box::new(testfn), fixtures);
     }
-
-    let args = env::args().collect::<Vec<_>>();
-    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
-    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
 }
 
 /// Clones static values for putting into a dynamic vector, which test_main()
@@ -219,6 +269,11 @@ where
         filtered_tests = convert_benchmarks_to_tests(filtered_tests);
     }
 
+    let shuffle_seed = get_shuffle_seed(opts);
+    if let Some(shuffle_seed) = shuffle_seed {
+        shuffle_tests(shuffle_seed, &mut filtered_tests);
+    }
+
     let filtered_tests = {
         let mut filtered_tests = filtered_tests;
         for test in filtered_tests.iter_mut() {
@@ -234,13 +289,49 @@ where
 
     let filtered_descs = filtered_tests.iter().map(|t| t.desc.clone()).collect();
 
-    let event = TestEvent::TeFiltered(filtered_descs);
+    let event = TestEvent::TeFiltered(filtered_descs, shuffle_seed);
     notify_about_test_event(event)?;
 
+    let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
+        RunStrategy::SpawnPrimary
+    } else {
+        RunStrategy::InProcess
+    };
+
+    // The suite fixtures only run if there is anything to run, and a failed setup
+    // skips all tests.
+    let fixtures = opts.options.fixtures;
+    let run_suite_fixtures = !filtered_tests.is_empty();
+    if run_suite_fixtures {
+        let setup = fixtures.setup;
+        if let Some(error) = run_suite_fixture("setup", setup, opts.nocapture, run_strategy) {
+            notify_about_test_event(TestEvent::TeSuiteError(error))?;
+            return Ok(());
+        }
+    }
+
     let (filtered_tests, filtered_benchs): (Vec<_>, _) = filtered_tests
         .into_iter()
         .partition(|e| matches!(e.testfn, StaticTestFn(_) | DynTestFn(_)));
 
+    // The tests that can't be moved out of process can't be killed, so their timeouts are
+    // ignored.
+    let unenforced_timeouts = filtered_tests
+        .iter()
+        .filter(|test| {
+            let (timeout, strategy) =
+                get_timeout_and_strategy(opts, &test.desc, &test.testfn, run_strategy);
+            timeout.is_some() && matches!(strategy, RunStrategy::InProcess)
+        })
+        .count();
+    if unenforced_timeouts > 0 {
+        let noun = if unenforced_timeouts == 1 { "test" } else { "tests" };
+        eprintln!(
+            "warning: not enforcing the timeout of {} {} that can't run in a subprocess",
+            unenforced_timeouts, noun
+        );
+    }
+
     let concurrency = opts.test_threads.unwrap_or_else(get_concurrency);
 
     let mut remaining = filtered_tests;
@@ -248,11 +339,6 @@ where
     let mut pending = 0;
 
     let (tx, rx) = channel::<CompletedTest>();
-    let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
-        RunStrategy::SpawnPrimary
-    } else {
-        RunStrategy::InProcess
-    };
 
     let mut running_tests: TestMap = HashMap::default();
 
@@ -344,27 +430,152 @@ where
             notify_about_test_event(event)?;
         }
     }
+
+    if run_suite_fixtures {
+        let teardown = fixtures.teardown;
+        if let Some(error) = run_suite_fixture("teardown", teardown, opts.nocapture, run_strategy) {
+            notify_about_test_event(TestEvent::TeSuiteError(error))?;
+        }
+    }
     Ok(())
 }
 
+/// Runs the suite fixtures `fixture` (either `setup` or `teardown`) in order and
+/// captures their output like a test's. Stops at the first fixture that panics.
+///
+/// Like the tests, the fixtures run in a subprocess under panic=abort, so that a
+/// panicking fixture is reported instead of aborting the test harness.
+fn run_suite_fixture(
+    fixture: &'static str,
+    fixtures: &[fn()],
+    nocapture: bool,
+    strategy: RunStrategy,
+) -> Option<SuiteError> {
+    if fixtures.is_empty() {
+        return None;
+    }
+    match strategy {
+        RunStrategy::InProcess => run_suite_fixtures_in_process(fixture, fixtures, nocapture),
+        RunStrategy::SpawnPrimary => spawn_suite_fixtures_subprocess(fixture, nocapture),
+    }
+}
+
+fn run_suite_fixtures_in_process(
+    fixture: &'static str,
+    fixtures: &[fn()],
+    nocapture: bool,
+) -> Option<SuiteError> {
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let oldio = if !nocapture { Some(io::set_output_capture(Some(data.clone()))) } else { None };
+
+    let result = catch_unwind(AssertUnwindSafe(|| run_fixtures(fixtures)));
+
+    if let Some(oldio) = oldio {
+        io::set_output_capture(oldio);
+    }
+
+    let err = result.err()?;
+    let message = err
+        .downcast_ref::<String>()
+        .map(|e| &**e)
+        .or_else(|| err.downcast_ref::<&'static str>().copied())
+        .unwrap_or("the fixture panicked");
+    let stdout = data.lock().unwrap().to_vec();
+    Some(SuiteError::new(fixture, message.to_owned(), stdout))
+}
+
+fn spawn_suite_fixtures_subprocess(fixture: &'static str, nocapture: bool) -> Option<SuiteError> {
+    let args = env::args().collect::<Vec<_>>();
+    let current_exe = &args[0];
+
+    let mut command = Command::new(current_exe);
+    command.env(SECONDARY_SUITE_FIXTURE_VAR, fixture).env_remove(SECONDARY_TEST_INVOKER_VAR);
+    if nocapture {
+        command.stdout(process::Stdio::inherit());
+        command.stderr(process::Stdio::inherit());
+    } else {
+        command.stdout(process::Stdio::piped());
+        command.stderr(process::Stdio::piped());
+    }
+
+    let std::process::Output { stdout, stderr, status } = match command.output() {
+        Ok(output) => output,
+        Err(e) => {
+            let message =
+                format!("Failed to spawn {} as child for suite {}: {:?}", args[0], fixture, e);
+            return Some(SuiteError::new(fixture, message, Vec::new()));
+        }
+    };
+    let message = match get_exit_code(status) {
+        Ok(TR_OK) => return None,
+        Ok(_) => "the fixture panicked".to_owned(),
+        Err(e) => e,
+    };
+
+    let mut output = stdout;
+    let name = DynTestName(format!("suite {}", fixture));
+    formatters::write_stderr_delimiter(&mut output, &name);
+    output.extend_from_slice(&stderr);
+    Some(SuiteError::new(fixture, message, output))
+}
+
+fn run_suite_fixtures_in_spawned_subprocess(fixtures: &[fn()]) -> ! {
+    let builtin_panic_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        builtin_panic_hook(info);
+        process::exit(test_result::TR_FAILED);
+    }));
+    run_fixtures(fixtures);
+    process::exit(test_result::TR_OK)
+}
+
+fn run_fixtures(fixtures: &[fn()]) {
+    for fixture in fixtures {
+        fixture();
+    }
+}
+
 pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescAndFn> {
     let mut filtered = tests;
-    let matches_filter = |test: &TestDescAndFn, filter: &str| {
+
+    // Invalid patterns are rejected when parsing the options, so a pattern that
+    // doesn't compile here simply doesn't match anything.
+    let compile = |filter: &str| match opts.filter_mode {
+        FilterMode::Glob => Pattern::glob(filter).ok(),
+        FilterMode::Regex => Pattern::regex(filter).ok(),
+        FilterMode::Substring | FilterMode::Exact | FilterMode::Path => None,
+    };
+    let filters: Vec<_> = opts.filters.iter().map(|f| (f.as_str(), compile(f))).collect();
+    let skip: Vec<_> = opts.skip.iter().map(|f| (f.as_str(), compile(f))).collect();
+
+    let matches_filter = |test: &TestDescAndFn, (filter, pattern): &(&str, Option<Pattern>)| {
         let test_name = test.desc.name.as_slice();
 
-        match opts.filter_exact {
-            true => test_name == filter,
-            false => test_name.contains(filter),
+        match opts.filter_mode {
+            FilterMode::Substring => test_name.contains(*filter),
+            FilterMode::Exact => test_name == *filter,
+            FilterMode::Glob | FilterMode::Regex => {
+                pattern.as_ref().map_or(false, |pattern| pattern.is_match(test_name))
+            }
+            FilterMode::Path => match test_name.strip_prefix(*filter) {
+                Some(rest) => rest.is_empty() || rest.starts_with("::"),
+                None => false,
+            },
         }
     };
 
-    // Remove tests that don't match the test filter
-    if let Some(ref filter) = opts.filter {
-        filtered.retain(|test| matches_filter(test, filter));
+    // Remove tests that don't match any of the test filters
+    if !filters.is_empty() {
+        filtered.retain(|test| filters.iter().any(|filter| matches_filter(test, filter)));
+    }
+
+    // Only keep the tests that failed in the recorded run
+    if let Some(ref failed_tests) = opts.rerun_failed {
+        filtered.retain(|test| failed_tests.contains(test.desc.name.as_slice()));
     }
 
     // Skip tests that match any of the skip filters
-    filtered.retain(|test| !opts.skip.iter().any(|sf| matches_filter(test, sf)));
+    filtered.retain(|test| !skip.iter().any(|sf| matches_filter(test, sf)));
 
     // Excludes #[should_panic] tests
     if opts.exclude_should_panic {
@@ -386,6 +597,16 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
     // Sort the tests alphabetically
     filtered.sort_by(|t1, t2| t1.desc.name.as_slice().cmp(t2.desc.name.as_slice()));
 
+    // Only keep the tests of the requested partition
+    if let Some(partition) = opts.partition {
+        filtered = filtered
+            .into_iter()
+            .enumerate()
+            .filter(|(position, _)| partition.contains(*position))
+            .map(|(_, test)| test)
+            .collect();
+    }
+
     filtered
 }
 
@@ -434,6 +655,8 @@ pub fn run_test(
         pub nocapture: bool,
         pub concurrency: Concurrent,
         pub time: Option<time::TestTimeOptions>,
+        pub timeout: Option<Duration>,
+        pub fixtures: TestFixtures,
     }
 
     fn run_test_inner(
@@ -453,6 +676,7 @@ pub fn run_test(
                 testfn,
                 monitor_ch,
                 opts.time,
+                opts.fixtures,
             ),
             RunStrategy::SpawnPrimary => spawn_test_subprocess(
                 desc,
@@ -460,6 +684,7 @@ pub fn run_test(
                 opts.time.is_some(),
                 monitor_ch,
                 opts.time,
+                opts.timeout,
             ),
         };
 
@@ -475,19 +700,37 @@ pub fn run_test(
         }
     }
 
-    let test_run_opts =
-        TestRunOpts { strategy, nocapture: opts.nocapture, concurrency, time: opts.time_options };
+    let (timeout, strategy) = get_timeout_and_strategy(opts, &desc, &testfn, strategy);
+
+    let test_run_opts = TestRunOpts {
+        strategy,
+        nocapture: opts.nocapture,
+        concurrency,
+        time: opts.time_options,
+        timeout,
+        fixtures: opts.options.fixtures,
+    };
 
     match testfn {
         DynBenchFn(bencher) => {
             // Benchmarks aren't expected to panic, so we run them all in-process.
-            crate::bench::benchmark(desc, monitor_ch, opts.nocapture, |harness| {
-                bencher.run(harness)
-            });
+            crate::bench::benchmark_with_fixtures(
+                desc,
+                monitor_ch,
+                opts.nocapture,
+                &opts.options.fixtures,
+                |harness| bencher.run(harness),
+            );
         }
         StaticBenchFn(benchfn) => {
             // Benchmarks aren't expected to panic, so we run them all in-process.
-            crate::bench::benchmark(desc, monitor_ch, opts.nocapture, benchfn);
+            crate::bench::benchmark_with_fixtures(
+                desc,
+                monitor_ch,
+                opts.nocapture,
+                &opts.options.fixtures,
+                benchfn,
+            );
         }
         DynTestFn(f) => {
             match strategy {
@@ -510,6 +753,31 @@ pub fn run_test(
     }
 }
 
+/// Returns the timeout of a test and the strategy to run it with.
+///
+/// A hung test can only be killed if it runs in a process of its own, so tests with a
+/// timeout are moved out of process whenever possible.
+fn get_timeout_and_strategy(
+    opts: &TestOpts,
+    desc: &TestDesc,
+    testfn: &TestFn,
+    strategy: RunStrategy,
+) -> (Option<Duration>, RunStrategy) {
+    // The attribute on the test takes precedence over `--test-timeout`.
+    let timeout = desc.timeout().or(opts.test_timeout);
+
+    let supports_processes = !cfg!(target_arch = "wasm32") && !cfg!(target_os = "emscripten");
+    let strategy = match (strategy, testfn) {
+        (RunStrategy::InProcess, StaticTestFn(_))
+            if timeout.is_some() && supports_processes && !opts.force_run_in_process =>
+        {
+            RunStrategy::SpawnPrimary
+        }
+        _ => strategy,
+    };
+    (timeout, strategy)
+}
+
 /// Fixed frame used to clean the backtrace with `RUST_BACKTRACE=1`.
 #[inline(never)]
 fn __rust_begin_short_backtrace<F: FnOnce()>(f: F) {
@@ -526,34 +794,42 @@ fn run_test_in_process(
     testfn: Box<dyn FnOnce() + Send>,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    fixtures: TestFixtures,
 ) {
     // Buffer for capturing standard I/O
     let data = Arc::new(Mutex::new(Vec::new()));
 
-    let oldio = if !nocapture {
-        Some((
-            io::set_print(Some(Sink::new_boxed(&data))),
-            io::set_panic(Some(Sink::new_boxed(&data))),
-        ))
-    } else {
-        None
-    };
+    let oldio = if !nocapture { Some(io::set_output_capture(Some(data.clone()))) } else { None };
+
+    // A panicking fixture fails the test, even if the test is expected to panic.
+    // The teardown runs after failed tests too, but not if the setup failed.
+    let setup = catch_unwind(AssertUnwindSafe(|| run_fixtures(fixtures.setup_each)));
 
     let start = report_time.then(Instant::now);
-    let result = catch_unwind(AssertUnwindSafe(testfn));
+    let result = match setup {
+        Ok(()) => Some(catch_unwind(AssertUnwindSafe(testfn))),
+        Err(_) => None,
+    };
     let exec_time = start.map(|start| {
         let duration = start.elapsed();
         TestExecTime(duration)
     });
 
-    if let Some((printio, panicio)) = oldio {
-        io::set_print(printio);
-        io::set_panic(panicio);
+    let teardown_failed = result.is_some()
+        && catch_unwind(AssertUnwindSafe(|| run_fixtures(fixtures.teardown_each))).is_err();
+
+    if let Some(oldio) = oldio {
+        io::set_output_capture(oldio);
     }
 
     let test_result = match result {
-        Ok(()) => calc_result(&desc, Ok(()), &time_opts, &exec_time),
-        Err(e) => calc_result(&desc, Err(e.as_ref()), &time_opts, &exec_time),
+        None => TrFailedMsg("test setup failed".to_string()),
+        Some(Ok(())) => calc_result(&desc, Ok(()), &time_opts, &exec_time),
+        Some(Err(e)) => calc_result(&desc, Err(e.as_ref()), &time_opts, &exec_time),
+    };
+    let test_result = match test_result {
+        TrOk if teardown_failed => TrFailedMsg("test teardown failed".to_string()),
+        test_result => test_result,
     };
     let stdout = data.lock().unwrap().to_vec();
     let message = CompletedTest::new(desc, test_result, exec_time, stdout);
@@ -566,20 +842,30 @@ fn spawn_test_subprocess(
     report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    timeout: Option<Duration>,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
         let current_exe = &args[0];
 
         let mut command = Command::new(current_exe);
-        command.env(SECONDARY_TEST_INVOKER_VAR, desc.name.as_slice());
+        command
+            .env(SECONDARY_TEST_INVOKER_VAR, desc.name.as_slice())
+            .env_remove(SECONDARY_SUITE_FIXTURE_VAR);
         if nocapture {
             command.stdout(process::Stdio::inherit());
             command.stderr(process::Stdio::inherit());
+        } else {
+            command.stdout(process::Stdio::piped());
+            command.stderr(process::Stdio::piped());
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let output = match timeout {
+            Some(timeout) => output_with_timeout(&mut command, timeout),
+            None => command.output().map(|output| (output, false)),
+        };
+        let (output, timed_out) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -596,6 +882,10 @@ fn spawn_test_subprocess(
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
+        if timed_out {
+            return (TrTimedOut, test_output, exec_time);
+        }
+
         let result = match (|| -> Result<TestResult, String> {
             let exit_code = get_exit_code(status)?;
             Ok(get_result_from_exit_code(&desc, exit_code, &time_opts, &exec_time))
@@ -614,12 +904,92 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
-fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Send>) -> ! {
+/// How long to wait for the rest of the output of a test that was killed for running
+/// too long. The processes it started are killed along with it, so its pipes are
+/// normally closed right away, but a process may have left the test's process group.
+const KILLED_TEST_OUTPUT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Runs `command` like `Command::output` does, but kills the process and the processes
+/// it started once `timeout` has elapsed. Returns the collected output and whether the
+/// process had to be killed.
+fn output_with_timeout(
+    command: &mut Command,
+    timeout: Duration,
+) -> io::Result<(process::Output, bool)> {
+    type PipeOutput = (Arc<Mutex<Vec<u8>>>, Receiver<()>);
+
+    /// Reads `pipe` to the end on a thread of its own. The output read so far can be taken at
+    /// any time, and the receiver is notified once the end of the pipe has been reached.
+    fn read_pipe<R: io::Read + Send + 'static>(pipe: Option<R>) -> PipeOutput {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let (done_tx, done_rx) = channel();
+        let output2 = output.clone();
+        thread::spawn(move || {
+            if let Some(mut pipe) = pipe {
+                let mut buf = [0; 4096];
+                loop {
+                    match pipe.read(&mut buf) {
+                        Ok(0) => break,
+                        Ok(n) => output2.lock().unwrap().extend_from_slice(&buf[..n]),
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        // A failed read only means that part of the test output is lost.
+                        Err(_) => break,
+                    }
+                }
+            }
+            // The receiver is gone if the rest of the output was given up on.
+            let _ = done_tx.send(());
+        });
+        (output, done_rx)
+    }
+
+    let mut child = spawn_in_process_group(command.stdin(process::Stdio::null()))?;
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let (status, timed_out) = match child.wait_timeout(timeout)? {
+        Some(status) => (status, false),
+        None => {
+            // Processes started by the test may have inherited its stdout and stderr, so they
+            // are killed too, otherwise the pipes would never be closed. The test may have
+            // exited in the meantime, `wait` reaps it either way.
+            let _ = kill_process_group(&mut child);
+            (child.wait()?, true)
+        }
+    };
+
+    // A reader thread only outlives this function if a process that escaped the kill keeps
+    // the pipe open. It then exits once that process does.
+    let take_output = |(output, done): PipeOutput| {
+        if timed_out {
+            let _ = done.recv_timeout(KILLED_TEST_OUTPUT_TIMEOUT);
+        } else {
+            let _ = done.recv();
+        }
+        let output = output.lock().unwrap().clone();
+        output
+    };
+    let stdout = take_output(stdout);
+    let stderr = take_output(stderr);
+    Ok((process::Output { status, stdout, stderr }, timed_out))
+}
+
+fn run_test_in_spawned_subprocess(
+    desc: TestDesc,
+    testfn: Box<dyn FnOnce() + Send>,
+    fixtures: &TestFixtures,
+) -> ! {
+    // The fixture that is currently running, if any.
+    let fixture = Arc::new(Mutex::new(Some("setup")));
+    let fixture2 = fixture.clone();
+
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
-        let test_result = match panic_info {
-            Some(info) => calc_result(&desc, Err(info.payload()), &None, &None),
-            None => calc_result(&desc, Ok(()), &None, &None),
+        let test_result = match (panic_info, *fixture2.lock().unwrap()) {
+            // A panicking fixture fails the test, even if the test is expected to panic.
+            (Some(_), Some(fixture)) => TrFailedMsg(format!("test {} failed", fixture)),
+            (Some(info), None) => calc_result(&desc, Err(info.payload()), &None, &None),
+            (None, _) => calc_result(&desc, Ok(()), &None, &None),
         };
 
         // We don't support serializing TrFailedMsg, so just
@@ -640,7 +1010,11 @@ fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Sen
     });
     let record_result2 = record_result.clone();
     panic::set_hook(Box::new(move |info| record_result2(Some(&info))));
+    run_fixtures(fixtures.setup_each);
+    *fixture.lock().unwrap() = None;
     testfn();
+    *fixture.lock().unwrap() = Some("teardown");
+    run_fixtures(fixtures.teardown_each);
     record_result(None);
     unreachable!("panic=abort callback should have exited the process")
 }
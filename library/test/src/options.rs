@@ -1,5 +1,7 @@
 //! Enums denoting options for test execution.
 
+use super::types::TestFixtures;
+
 /// Whether to execute tests concurrently or not
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Concurrent {
@@ -39,6 +41,8 @@ pub enum OutputFormat {
     Terse,
     /// JSON output
     Json,
+    /// JUnit output
+    Junit,
 }
 
 /// Whether ignored test should be run or not
@@ -50,6 +54,37 @@ pub enum RunIgnored {
     Only,
 }
 
+/// How test names are matched against the filters and skip filters
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FilterMode {
+    /// The name contains the filter
+    Substring,
+    /// The name is equal to the filter
+    Exact,
+    /// The name matches the filter as a glob
+    Glob,
+    /// The name matches the filter as a regex
+    Regex,
+    /// The filter is the name itself or the path of one of the modules containing the test
+    Path,
+}
+
+/// Subset of the filtered tests to run, used to split a test suite over several runners.
+/// Tests are assigned round-robin in name order to one of `count` partitions; `index` is
+/// 1-based.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Partition {
+    pub index: usize,
+    pub count: usize,
+}
+
+impl Partition {
+    /// Whether the test at `position` of the sorted test list belongs to this partition.
+    pub fn contains(&self, position: usize) -> bool {
+        position % self.count == self.index - 1
+    }
+}
+
 #[derive(Clone, Copy)]
 pub enum RunStrategy {
     /// Runs the test in the current process, and sends the result back over the
@@ -68,11 +103,12 @@ pub enum RunStrategy {
 pub struct Options {
     pub display_output: bool,
     pub panic_abort: bool,
+    pub fixtures: TestFixtures,
 }
 
 impl Options {
     pub fn new() -> Options {
-        Options { display_output: false, panic_abort: false }
+        Options { display_output: false, panic_abort: false, fixtures: TestFixtures::default() }
     }
 
     pub fn display_output(mut self, display_output: bool) -> Options {
@@ -84,4 +120,9 @@ impl Options {
         self.panic_abort = panic_abort;
         self
     }
+
+    pub fn fixtures(mut self, fixtures: TestFixtures) -> Options {
+        self.fixtures = fixtures;
+        self
+    }
 }
@@ -22,6 +22,8 @@ pub enum TestResult {
     TrAllowedFail,
     TrBench(BenchSamples),
     TrTimedFail,
+    /// The test did not finish within its timeout and was killed.
+    TrTimedOut,
 }
 
 unsafe impl Send for TestResult {}
@@ -1,9 +1,12 @@
 use super::*;
 
 use crate::{
-    bench::Bencher,
+    baseline::{t_critical, Baseline, BenchChange, BenchComparison},
+    bench::{BenchSamples, Bencher},
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    event::{SuiteError, TestEvent},
+    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter},
+    helpers::{failed_list, pattern::Pattern, shuffle::shuffle_tests},
     options::OutputFormat,
     test::{
         filter_tests,
@@ -19,6 +22,7 @@ use crate::{
         TestDesc,
         TestDescAndFn,
         TestOpts,
+        TrFailedMsg,
         TrIgnored,
         TrOk,
         // FIXME (introduced by #65251)
@@ -28,6 +32,8 @@ use crate::{
     time::{TestTimeOptions, TimeThreshold},
 };
 use std::any::TypeId;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::channel;
 use std::time::Duration;
 
@@ -35,8 +41,8 @@ impl TestOpts {
     fn new() -> TestOpts {
         TestOpts {
             list: false,
-            filter: None,
-            filter_exact: false,
+            filters: vec![],
+            filter_mode: FilterMode::Substring,
             force_run_in_process: false,
             exclude_should_panic: false,
             run_ignored: RunIgnored::No,
@@ -48,6 +54,14 @@ impl TestOpts {
             format: OutputFormat::Pretty,
             test_threads: None,
             skip: vec![],
+            shuffle: false,
+            shuffle_seed: None,
+            partition: None,
+            test_timeout: None,
+            save_baseline: None,
+            baseline: None,
+            failed_list: None,
+            rerun_failed: None,
             time_options: None,
             options: Options::new(),
         }
@@ -62,6 +76,12 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 ignore: true,
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
+                #[cfg(not(bootstrap))]
+                timeout: None,
+                #[cfg(not(bootstrap))]
+                ignore_message: None,
+                #[cfg(not(bootstrap))]
+                location: None,
                 test_type: TestType::Unknown,
             },
             testfn: DynTestFn(Box::new(move || {})),
@@ -72,6 +92,12 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 ignore: false,
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
+                #[cfg(not(bootstrap))]
+                timeout: None,
+                #[cfg(not(bootstrap))]
+                ignore_message: None,
+                #[cfg(not(bootstrap))]
+                location: None,
                 test_type: TestType::Unknown,
             },
             testfn: DynTestFn(Box::new(move || {})),
@@ -90,6 +116,12 @@ pub fn do_not_run_ignored_tests() {
             ignore: true,
             should_panic: ShouldPanic::No,
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -109,6 +141,12 @@ pub fn ignored_tests_result_in_ignored() {
             ignore: true,
             should_panic: ShouldPanic::No,
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -132,6 +170,12 @@ fn test_should_panic() {
             ignore: false,
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -155,6 +199,12 @@ fn test_should_panic_good_message() {
             ignore: false,
             should_panic: ShouldPanic::YesWithMessage("error message"),
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -183,6 +233,12 @@ fn test_should_panic_bad_message() {
             ignore: false,
             should_panic: ShouldPanic::YesWithMessage(expected),
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -214,6 +270,12 @@ fn test_should_panic_non_string_message_type() {
             ignore: false,
             should_panic: ShouldPanic::YesWithMessage(expected),
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -235,6 +297,12 @@ fn test_should_panic_but_succeeds() {
             ignore: false,
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -245,6 +313,23 @@ fn test_should_panic_but_succeeds() {
     assert_eq!(result, TrFailedMsg("test did not panic as expected".to_string()));
 }
 
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_captures_output_of_spawned_threads() {
+    fn f() {
+        println!("from test");
+        std::thread::spawn(|| println!("from spawned thread")).join().unwrap();
+    }
+    let desc = TestDescAndFn {
+        desc: named_test_desc(StaticTestName("whatever")),
+        testfn: DynTestFn(Box::new(f)),
+    };
+    let (tx, rx) = channel();
+    run_test(&TestOpts::new(), false, desc, RunStrategy::InProcess, tx, Concurrent::No);
+    let stdout = rx.recv().unwrap().stdout;
+    assert_eq!(String::from_utf8(stdout).unwrap(), "from test\nfrom spawned thread\n");
+}
+
 fn report_time_test_template(report_time: bool) -> Option<TestExecTime> {
     fn f() {}
     let desc = TestDescAndFn {
@@ -253,6 +338,12 @@ fn report_time_test_template(report_time: bool) -> Option<TestExecTime> {
             ignore: false,
             should_panic: ShouldPanic::No,
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -286,6 +377,12 @@ fn time_test_failure_template(test_type: TestType) -> TestResult {
             ignore: false,
             should_panic: ShouldPanic::No,
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -317,12 +414,42 @@ fn test_error_on_exceed() {
     assert_eq!(result, TestResult::TrOk);
 }
 
+fn named_test_desc(name: TestName) -> TestDesc {
+    TestDesc {
+        name,
+        ignore: false,
+        should_panic: ShouldPanic::No,
+        allow_fail: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
+        #[cfg(not(bootstrap))]
+        location: None,
+        test_type: TestType::Unknown,
+    }
+}
+
+fn named_test(name: TestName, testfn: fn()) -> TestDescAndFn {
+    TestDescAndFn { desc: named_test_desc(name), testfn: DynTestFn(Box::new(testfn)) }
+}
+
+fn noop_tests(names: &[&'static str]) -> Vec<TestDescAndFn> {
+    names.iter().map(|&name| named_test(StaticTestName(name), || {})).collect()
+}
+
 fn typed_test_desc(test_type: TestType) -> TestDesc {
     TestDesc {
         name: StaticTestName("whatever"),
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
+        #[cfg(not(bootstrap))]
+        location: None,
         test_type,
     }
 }
@@ -392,6 +519,183 @@ fn parse_include_ignored_flag() {
     assert_eq!(opts.run_ignored, RunIgnored::Yes);
 }
 
+#[test]
+fn parse_shuffle_seed_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shuffle-seed".to_string(),
+        "42".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.shuffle_seed, Some(42));
+
+    let args = vec!["progname".to_string(), "--shuffle".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_partition_flag() {
+    let parse = |partition: &str| {
+        let args = vec![
+            "progname".to_string(),
+            "-Zunstable-options".to_string(),
+            "--partition".to_string(),
+            partition.to_string(),
+        ];
+        parse_opts(&args).unwrap().map(|opts| opts.partition)
+    };
+    assert_eq!(parse("2/3"), Ok(Some(Partition { index: 2, count: 3 })));
+    assert!(parse("0/3").is_err());
+    assert!(parse("4/3").is_err());
+    assert!(parse("1").is_err());
+}
+
+#[test]
+fn parse_test_timeout_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "30".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.test_timeout, Some(Duration::from_secs(30)));
+
+    let args = vec!["progname".to_string(), "--test-timeout".to_string(), "30".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    // Tests that run in-process can't be killed.
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "30".to_string(),
+        "--force-run-in-process".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+#[cfg(unix)]
+fn output_with_timeout_kills_started_processes() {
+    // The `sleep` keeps the pipes open unless it is killed along with the shell.
+    let mut command = Command::new("sh");
+    command.args(&["-c", "echo started; sleep 60 & wait"]);
+    command.stdout(process::Stdio::piped()).stderr(process::Stdio::piped());
+    let start = Instant::now();
+    let (output, timed_out) = output_with_timeout(&mut command, Duration::from_secs(1)).unwrap();
+    assert!(timed_out);
+    assert_eq!(output.stdout, b"started\n");
+    assert!(start.elapsed() < Duration::from_secs(30));
+}
+
+#[test]
+fn parse_baseline_flags() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--save-baseline".to_string(),
+        "new".to_string(),
+        "--baseline".to_string(),
+        "old".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.save_baseline.as_deref(), Some("new"));
+    assert_eq!(opts.baseline.as_deref(), Some("old"));
+
+    let args = vec!["progname".to_string(), "--baseline".to_string(), "old".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_failed_list_flags() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--failed-list=failed.txt".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.failed_list, Some(PathBuf::from("failed.txt")));
+    assert_eq!(opts.rerun_failed, None);
+
+    let path = std::env::temp_dir().join(format!("libtest-rerun-{}", std::process::id()));
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        format!("--failed-list={}", path.display()),
+        "--rerun-failed".to_string(),
+    ];
+    // Nothing has been recorded yet.
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    failed_list::write(&path, vec!["tests::a"].into_iter()).unwrap();
+    let opts = parse_opts(&args).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let opts = opts.unwrap();
+    assert_eq!(opts.failed_list, Some(path));
+    assert_eq!(opts.rerun_failed, Some(vec!["tests::a".to_string()].into_iter().collect()));
+
+    let args = vec!["progname".to_string(), "--rerun-failed".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_failed_list_forms() {
+    // The path may also be passed as a separate argument, it isn't taken as a filter.
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--failed-list".to_string(),
+        "failed.txt".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.failed_list, Some(PathBuf::from("failed.txt")));
+    assert!(opts.filters.is_empty());
+
+    let path = std::env::temp_dir().join(format!("libtest-rerun-forms-{}", std::process::id()));
+    failed_list::write(&path, vec!["tests::a"].into_iter()).unwrap();
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--rerun-failed".to_string(),
+        "--failed-list".to_string(),
+        path.display().to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let opts = opts.unwrap();
+    assert_eq!(opts.failed_list, Some(path));
+    assert_eq!(opts.rerun_failed, Some(vec!["tests::a".to_string()].into_iter().collect()));
+    assert!(opts.filters.is_empty());
+
+    // `--record-failed` uses the default file.
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--record-failed".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.failed_list, Some(failed_list::default_path()));
+    assert_eq!(opts.rerun_failed, None);
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--record-failed".to_string(),
+        "--failed-list=failed.txt".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    // The path can't be left out.
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--failed-list".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 pub fn filter_for_ignored_option() {
     // When we run ignored tests the test filter should filter out all the
@@ -439,6 +743,12 @@ pub fn exclude_should_panic_option() {
             ignore: false,
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            #[cfg(not(bootstrap))]
+            location: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(move || {})),
@@ -461,6 +771,12 @@ pub fn exact_filter_match() {
                     ignore: false,
                     should_panic: ShouldPanic::No,
                     allow_fail: false,
+                    #[cfg(not(bootstrap))]
+                    timeout: None,
+                    #[cfg(not(bootstrap))]
+                    ignore_message: None,
+                    #[cfg(not(bootstrap))]
+                    location: None,
                     test_type: TestType::Unknown,
                 },
                 testfn: DynTestFn(Box::new(move || {})),
@@ -469,45 +785,424 @@ pub fn exact_filter_match() {
     }
 
     let substr =
-        filter_tests(&TestOpts { filter: Some("base".into()), ..TestOpts::new() }, tests());
+        filter_tests(&TestOpts { filters: vec!["base".into()], ..TestOpts::new() }, tests());
     assert_eq!(substr.len(), 4);
 
-    let substr = filter_tests(&TestOpts { filter: Some("bas".into()), ..TestOpts::new() }, tests());
+    let substr =
+        filter_tests(&TestOpts { filters: vec!["bas".into()], ..TestOpts::new() }, tests());
     assert_eq!(substr.len(), 4);
 
     let substr =
-        filter_tests(&TestOpts { filter: Some("::test".into()), ..TestOpts::new() }, tests());
+        filter_tests(&TestOpts { filters: vec!["::test".into()], ..TestOpts::new() }, tests());
     assert_eq!(substr.len(), 3);
 
     let substr =
-        filter_tests(&TestOpts { filter: Some("base::test".into()), ..TestOpts::new() }, tests());
+        filter_tests(&TestOpts { filters: vec!["base::test".into()], ..TestOpts::new() }, tests());
     assert_eq!(substr.len(), 3);
 
     let exact = filter_tests(
-        &TestOpts { filter: Some("base".into()), filter_exact: true, ..TestOpts::new() },
+        &TestOpts {
+            filters: vec!["base".into()],
+            filter_mode: FilterMode::Exact,
+            ..TestOpts::new()
+        },
         tests(),
     );
     assert_eq!(exact.len(), 1);
 
     let exact = filter_tests(
-        &TestOpts { filter: Some("bas".into()), filter_exact: true, ..TestOpts::new() },
+        &TestOpts {
+            filters: vec!["bas".into()],
+            filter_mode: FilterMode::Exact,
+            ..TestOpts::new()
+        },
         tests(),
     );
     assert_eq!(exact.len(), 0);
 
     let exact = filter_tests(
-        &TestOpts { filter: Some("::test".into()), filter_exact: true, ..TestOpts::new() },
+        &TestOpts {
+            filters: vec!["::test".into()],
+            filter_mode: FilterMode::Exact,
+            ..TestOpts::new()
+        },
         tests(),
     );
     assert_eq!(exact.len(), 0);
 
     let exact = filter_tests(
-        &TestOpts { filter: Some("base::test".into()), filter_exact: true, ..TestOpts::new() },
+        &TestOpts {
+            filters: vec!["base::test".into()],
+            filter_mode: FilterMode::Exact,
+            ..TestOpts::new()
+        },
         tests(),
     );
     assert_eq!(exact.len(), 1);
 }
 
+#[test]
+pub fn filter_modes_match() {
+    fn tests() -> Vec<TestDescAndFn> {
+        noop_tests(&[
+            "io::read",
+            "io::read_to_end",
+            "io::buffered::read",
+            "fs::read",
+            "fs::write_10",
+        ])
+    }
+    let filter = |filter_mode, filters: &[&str], skip: &[&str]| {
+        let opts = TestOpts {
+            filters: filters.iter().map(|s| s.to_string()).collect(),
+            skip: skip.iter().map(|s| s.to_string()).collect(),
+            filter_mode,
+            ..TestOpts::new()
+        };
+        filter_tests(&opts, tests())
+            .into_iter()
+            .map(|test| test.desc.name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // Multiple filters select the tests matching any of them.
+    assert_eq!(
+        filter(FilterMode::Substring, &["buffered", "write"], &[]),
+        ["fs::write_10", "io::buffered::read"]
+    );
+    assert_eq!(filter(FilterMode::Exact, &["fs::read", "io::read"], &[]), ["fs::read", "io::read"]);
+
+    assert_eq!(filter(FilterMode::Glob, &["io::read*"], &[]), ["io::read", "io::read_to_end"]);
+    assert_eq!(filter(FilterMode::Glob, &["*::read"], &["io::*"]), ["fs::read"]);
+    assert_eq!(filter(FilterMode::Glob, &["read"], &[]), Vec::<String>::new());
+
+    assert_eq!(filter(FilterMode::Regex, &[r"_\d+$"], &[]), ["fs::write_10"]);
+    assert_eq!(filter(FilterMode::Regex, &["^(fs|io)::read$"], &[]), ["fs::read", "io::read"]);
+    assert_eq!(filter(FilterMode::Regex, &["read"], &["^io::"]), ["fs::read"]);
+
+    assert_eq!(filter(FilterMode::Path, &["io"], &[]).len(), 3);
+    assert_eq!(filter(FilterMode::Path, &["io::read"], &[]), ["io::read"]);
+    assert_eq!(
+        filter(FilterMode::Path, &["io"], &["io::buffered"]),
+        ["io::read", "io::read_to_end"]
+    );
+    assert_eq!(filter(FilterMode::Path, &["i"], &[]), Vec::<String>::new());
+}
+
+#[test]
+fn parse_filter_mode_flag() {
+    let parse = |args: &[&str]| {
+        let mut all_args = vec!["progname".to_string(), "-Zunstable-options".to_string()];
+        all_args.extend(args.iter().map(|s| s.to_string()));
+        parse_opts(&all_args).unwrap().map(|opts| (opts.filter_mode, opts.filters))
+    };
+
+    assert_eq!(parse(&["a", "b"]), Ok((FilterMode::Substring, vec!["a".into(), "b".into()])));
+    assert_eq!(parse(&["--exact", "a"]), Ok((FilterMode::Exact, vec!["a".into()])));
+    assert_eq!(parse(&["--filter-mode=regex", "^a"]), Ok((FilterMode::Regex, vec!["^a".into()])));
+    assert_eq!(parse(&["--filter-mode=glob"]), Ok((FilterMode::Glob, vec![])));
+    assert_eq!(parse(&["--filter-mode", "path", "io"]), Ok((FilterMode::Path, vec!["io".into()])));
+    assert!(parse(&["--filter-mode=fuzzy"]).is_err());
+    assert!(parse(&["--exact", "--filter-mode=glob"]).is_err());
+    assert!(parse(&["--filter-mode=regex", "(a"]).is_err());
+    assert!(parse(&["--filter-mode=glob", "--skip", "[a"]).is_err());
+
+    let args = vec!["progname".to_string(), "--filter-mode=regex".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn regex_and_glob_patterns() {
+    let regex = |pattern: &str, name: &str| Pattern::regex(pattern).unwrap().is_match(name);
+    assert!(regex("foo", "a::foo::b"));
+    assert!(!regex("^foo", "a::foo"));
+    assert!(regex(r"^a::(?:foo|bar)$", "a::bar"));
+    assert!(!regex(r"^a::(foo|bar)$", "a::baz"));
+    assert!(regex(r"^[a-c_]+\d{2,3}$", "ab_c123"));
+    assert!(!regex(r"^[a-c_]+\d{2,3}$", "ab_c1234"));
+    assert!(regex("^x?y.*$", "y"));
+    assert!(!regex("^(a*)*b$", "aaaa"));
+    assert!(Pattern::regex("*a").is_err());
+    assert!(Pattern::regex("a{3,2}").is_err());
+
+    let glob = |pattern: &str, name: &str| Pattern::glob(pattern).unwrap().is_match(name);
+    assert!(glob("io::*", "io::read::eof"));
+    assert!(!glob("io::*", "fs::io::read"));
+    assert!(glob("*::test_?", "a::test_1"));
+    assert!(glob("[!x]::a.b", "y::a.b"));
+    assert!(!glob("[!x]::a.b", "x::a.b"));
+    assert!(!glob("a.b", "axb"));
+    assert!(Pattern::glob("[ab").is_err());
+}
+
+#[test]
+fn pathological_patterns() {
+    let name = "a".repeat(100);
+    // These take exponential time in a backtracking matcher.
+    assert!(!Pattern::regex("(a*)*b").unwrap().is_match(&name));
+    assert!(!Pattern::regex("(.*)*x").unwrap().is_match(&name));
+    assert!(!Pattern::regex("^(a|aa)+$").unwrap().is_match(&format!("{}b", name)));
+    assert!(Pattern::regex("^(a*)*$").unwrap().is_match(&name));
+    assert!(Pattern::regex("^(a?){1000}$").unwrap().is_match(&name));
+    assert!(!Pattern::regex("^(a?){10}$").unwrap().is_match(&name));
+    // Repetition counts and the size of the compiled pattern are limited.
+    assert!(Pattern::regex("(a?){1000000}").is_err());
+    assert!(Pattern::regex("a{1,1001}").is_err());
+    assert!(Pattern::regex("(a{1000}){1000}").is_err());
+}
+
+#[test]
+pub fn partition_filtered_tests() {
+    fn tests() -> Vec<TestDescAndFn> {
+        noop_tests(&["e", "a", "d", "b", "c"])
+    }
+    let partition = |index| {
+        let opts = TestOpts { partition: Some(Partition { index, count: 2 }), ..TestOpts::new() };
+        filter_tests(&opts, tests())
+            .into_iter()
+            .map(|test| test.desc.name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(partition(1), ["a", "c", "e"]);
+    assert_eq!(partition(2), ["b", "d"]);
+}
+
+#[test]
+pub fn rerun_failed_filters_tests() {
+    let tests = noop_tests(&["a", "b", "c"]);
+
+    let failed = vec!["c".to_string(), "a".to_string(), "removed".to_string()];
+    let opts = TestOpts { rerun_failed: Some(failed.into_iter().collect()), ..TestOpts::new() };
+    let filtered = filter_tests(&opts, tests);
+
+    let names = filtered.iter().map(|test| test.desc.name.to_string()).collect::<Vec<_>>();
+    assert_eq!(names, ["a", "c"]);
+}
+
+#[test]
+fn failed_list_roundtrip() {
+    let path = std::env::temp_dir().join(format!("libtest-failed-{}", std::process::id()));
+
+    failed_list::write(&path, vec!["tests::b", "tests::a", "tests::b"].into_iter()).unwrap();
+    let failed = failed_list::read(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "tests::a\ntests::b\n");
+
+    failed_list::write(&path, std::iter::empty()).unwrap();
+    let passed = failed_list::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(failed.into_iter().collect::<Vec<_>>(), ["tests::a", "tests::b"]);
+    assert!(passed.is_empty());
+}
+
+fn run_with_fixtures(
+    opts: &mut TestOpts,
+    tests: Vec<TestDescAndFn>,
+    fixtures: TestFixtures,
+) -> Vec<TestEvent> {
+    opts.run_tests = true;
+    opts.test_threads = Some(1);
+    opts.options = opts.options.fixtures(fixtures);
+    let mut events = Vec::new();
+    run_tests(opts, tests, |event| {
+        events.push(event);
+        Ok(())
+    })
+    .unwrap();
+    events
+}
+
+fn results(events: &[TestEvent]) -> Vec<(String, TestResult)> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            TestEvent::TeResult(test) => Some((test.desc.name.to_string(), test.result.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+pub fn fixtures_run_around_tests() {
+    static SETUP: AtomicUsize = AtomicUsize::new(0);
+    static TEARDOWN: AtomicUsize = AtomicUsize::new(0);
+    static SETUP_EACH: AtomicUsize = AtomicUsize::new(0);
+    static TEARDOWN_EACH: AtomicUsize = AtomicUsize::new(0);
+    fn setup() {
+        // The suite setup runs before everything else.
+        assert_eq!(SETUP_EACH.load(Ordering::SeqCst), 0);
+        SETUP.fetch_add(1, Ordering::SeqCst);
+    }
+    fn teardown() {
+        assert_eq!(TEARDOWN_EACH.load(Ordering::SeqCst), 2);
+        TEARDOWN.fetch_add(1, Ordering::SeqCst);
+    }
+    fn setup_each() {
+        SETUP_EACH.fetch_add(1, Ordering::SeqCst);
+    }
+    fn teardown_each() {
+        TEARDOWN_EACH.fetch_add(1, Ordering::SeqCst);
+    }
+    fn f() {
+        assert_eq!(SETUP_EACH.load(Ordering::SeqCst), TEARDOWN_EACH.load(Ordering::SeqCst) + 1);
+    }
+
+    let fixtures = TestFixtures {
+        setup: &[setup],
+        teardown: &[teardown],
+        setup_each: &[setup_each],
+        teardown_each: &[teardown_each],
+    };
+    let tests = vec![named_test(StaticTestName("a"), f), named_test(StaticTestName("b"), f)];
+    let events = run_with_fixtures(&mut TestOpts::new(), tests, fixtures);
+    assert_eq!(results(&events), vec![("a".to_string(), TrOk), ("b".to_string(), TrOk)]);
+    assert_eq!(SETUP.load(Ordering::SeqCst), 1);
+    assert_eq!(TEARDOWN.load(Ordering::SeqCst), 1);
+    assert_eq!(SETUP_EACH.load(Ordering::SeqCst), 2);
+    assert_eq!(TEARDOWN_EACH.load(Ordering::SeqCst), 2);
+
+    // The suite fixtures don't run if all tests are filtered out.
+    let mut opts = TestOpts::new();
+    opts.filters = vec!["nothing".to_string()];
+    let events = run_with_fixtures(&mut opts, vec![named_test(StaticTestName("a"), f)], fixtures);
+    assert!(results(&events).is_empty());
+    assert_eq!(SETUP.load(Ordering::SeqCst), 1);
+    assert_eq!(TEARDOWN.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+pub fn failed_suite_setup_skips_tests() {
+    static RAN: AtomicUsize = AtomicUsize::new(0);
+    fn setup() {
+        println!("connecting");
+        panic!("no database");
+    }
+    fn teardown() {
+        RAN.fetch_add(1, Ordering::SeqCst);
+    }
+    fn f() {
+        RAN.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let fixtures = TestFixtures { setup: &[setup], teardown: &[teardown], ..Default::default() };
+    let tests = vec![named_test(StaticTestName("a"), f)];
+    let events = run_with_fixtures(&mut TestOpts::new(), tests, fixtures);
+    assert!(results(&events).is_empty());
+    assert_eq!(RAN.load(Ordering::SeqCst), 0);
+
+    let errors: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            TestEvent::TeSuiteError(error) => Some(error),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].fixture, "setup");
+    assert_eq!(errors[0].message, "no database");
+    assert_eq!(errors[0].stdout, b"connecting\n");
+}
+
+#[test]
+pub fn failed_fixtures_fail_the_test() {
+    static RAN: AtomicUsize = AtomicUsize::new(0);
+    fn panics() {
+        panic!();
+    }
+    fn f() {
+        RAN.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let fixtures = TestFixtures { setup_each: &[panics], ..Default::default() };
+    let tests = vec![named_test(StaticTestName("a"), f)];
+    let events = run_with_fixtures(&mut TestOpts::new(), tests, fixtures);
+    let failed = TrFailedMsg("test setup failed".to_string());
+    assert_eq!(results(&events), vec![("a".to_string(), failed)]);
+    assert_eq!(RAN.load(Ordering::SeqCst), 0);
+
+    let fixtures = TestFixtures { teardown_each: &[panics], ..Default::default() };
+    let tests = vec![named_test(StaticTestName("a"), f)];
+    let events = run_with_fixtures(&mut TestOpts::new(), tests, fixtures);
+    let failed = TrFailedMsg("test teardown failed".to_string());
+    assert_eq!(results(&events), vec![("a".to_string(), failed)]);
+    assert_eq!(RAN.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+pub fn fixtures_run_around_benchmarks() {
+    static SETUP: AtomicUsize = AtomicUsize::new(0);
+    static TEARDOWN: AtomicUsize = AtomicUsize::new(0);
+    static SETUP_EACH: AtomicUsize = AtomicUsize::new(0);
+    static TEARDOWN_EACH: AtomicUsize = AtomicUsize::new(0);
+    fn setup() {
+        SETUP.fetch_add(1, Ordering::SeqCst);
+    }
+    fn teardown() {
+        // The suite teardown waits for the benchmarks, which run after the tests.
+        assert_eq!(TEARDOWN_EACH.load(Ordering::SeqCst), 2);
+        TEARDOWN.fetch_add(1, Ordering::SeqCst);
+    }
+    fn setup_each() {
+        assert_eq!(SETUP.load(Ordering::SeqCst), 1);
+        SETUP_EACH.fetch_add(1, Ordering::SeqCst);
+    }
+    fn teardown_each() {
+        TEARDOWN_EACH.fetch_add(1, Ordering::SeqCst);
+    }
+    fn bench(b: &mut Bencher) {
+        assert_eq!(SETUP_EACH.load(Ordering::SeqCst), TEARDOWN_EACH.load(Ordering::SeqCst) + 1);
+        b.iter(|| {})
+    }
+    fn panics() {
+        panic!();
+    }
+
+    let fixtures = TestFixtures {
+        setup: &[setup],
+        teardown: &[teardown],
+        setup_each: &[setup_each],
+        teardown_each: &[teardown_each],
+    };
+    let bench_test = |name| TestDescAndFn {
+        desc: named_test_desc(StaticTestName(name)),
+        testfn: StaticBenchFn(bench),
+    };
+    let mut opts = TestOpts::new();
+    opts.bench_benchmarks = true;
+    let events = run_with_fixtures(&mut opts, vec![bench_test("a"), bench_test("b")], fixtures);
+    let results = results(&events);
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|(_, result)| matches!(result, TrBench(_))));
+    assert_eq!(SETUP.load(Ordering::SeqCst), 1);
+    assert_eq!(TEARDOWN.load(Ordering::SeqCst), 1);
+
+    // A failing fixture fails the benchmark.
+    let fixtures = TestFixtures { setup_each: &[panics], ..Default::default() };
+    let events = run_with_fixtures(&mut opts, vec![bench_test("a")], fixtures);
+    let failed = TrFailedMsg("test setup failed".to_string());
+    assert_eq!(results(&events), vec![("a".to_string(), failed)]);
+}
+
+#[test]
+fn pretty_formatter_reports_suite_errors() {
+    let mut st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+    let error = SuiteError::new("teardown", "oops".to_string(), b"cleaning up\n".to_vec());
+
+    let mut out = PrettyFormatter::new(OutputLocation::Raw(Vec::new()), false, 10, false, None);
+    out.write_suite_error(&error).unwrap();
+    st.suite_errors.push(error);
+    assert!(!out.write_run_finish(&st).unwrap());
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert!(s.starts_with("suite teardown ... FAILED\n"));
+    assert!(s.contains("---- suite teardown stdout ----\ncleaning up\n"));
+    assert!(s.contains("\nsuite errors:\n    teardown: oops\n"));
+}
+
 #[test]
 pub fn sort_tests() {
     let mut opts = TestOpts::new();
@@ -536,6 +1231,12 @@ pub fn sort_tests() {
                     ignore: false,
                     should_panic: ShouldPanic::No,
                     allow_fail: false,
+                    #[cfg(not(bootstrap))]
+                    timeout: None,
+                    #[cfg(not(bootstrap))]
+                    ignore_message: None,
+                    #[cfg(not(bootstrap))]
+                    location: None,
                     test_type: TestType::Unknown,
                 },
                 testfn: DynTestFn(Box::new(testfn)),
@@ -565,6 +1266,32 @@ pub fn sort_tests() {
     }
 }
 
+#[test]
+pub fn shuffle_tests_is_reproducible() {
+    fn tests() -> Vec<TestDescAndFn> {
+        (0..20).map(|i| named_test(DynTestName(format!("test::t{}", i)), || {})).collect()
+    }
+    let names = |tests: &[TestDescAndFn]| -> Vec<String> {
+        tests.iter().map(|test| test.desc.name.to_string()).collect()
+    };
+
+    let mut first = tests();
+    shuffle_tests(1234, &mut first);
+    let mut second = tests();
+    shuffle_tests(1234, &mut second);
+    let mut other = tests();
+    shuffle_tests(4321, &mut other);
+
+    assert_eq!(names(&first), names(&second));
+    assert_ne!(names(&first), names(&other));
+
+    let mut sorted = names(&first);
+    sorted.sort();
+    let mut expected = names(&tests());
+    expected.sort();
+    assert_eq!(sorted, expected);
+}
+
 #[test]
 pub fn test_metricmap_compare() {
     let mut m1 = MetricMap::new();
@@ -613,6 +1340,12 @@ pub fn test_bench_no_iter() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
+        #[cfg(not(bootstrap))]
+        location: None,
         test_type: TestType::Unknown,
     };
 
@@ -633,6 +1366,12 @@ pub fn test_bench_iter() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
+        #[cfg(not(bootstrap))]
+        location: None,
         test_type: TestType::Unknown,
     };
 
@@ -647,6 +1386,12 @@ fn should_sort_failures_before_printing_them() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
+        #[cfg(not(bootstrap))]
+        location: None,
         test_type: TestType::Unknown,
     };
 
@@ -655,6 +1400,12 @@ fn should_sort_failures_before_printing_them() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
+        #[cfg(not(bootstrap))]
+        location: None,
         test_type: TestType::Unknown,
     };
 
@@ -674,6 +1425,9 @@ fn should_sort_failures_before_printing_them() {
         options: Options::new(),
         not_failures: Vec::new(),
         time_failures: Vec::new(),
+        baseline: None,
+        bench_results: Baseline::new(),
+        suite_errors: Vec::new(),
     };
 
     out.write_failures(&st).unwrap();
@@ -686,3 +1440,212 @@ fn should_sort_failures_before_printing_them() {
     let bpos = s.find("b").unwrap();
     assert!(apos < bpos);
 }
+
+#[test]
+fn junit_formatter_reports_escaped_results() {
+    let desc = |name| TestDesc {
+        name: StaticTestName(name),
+        ignore: false,
+        should_panic: ShouldPanic::No,
+        allow_fail: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
+        #[cfg(not(bootstrap))]
+        location: None,
+        test_type: TestType::Unknown,
+    };
+
+    let mut out = JunitFormatter::new(OutputLocation::Raw(Vec::new()));
+
+    let st = console::ConsoleTestState {
+        log_out: None,
+        total: 3,
+        passed: 1,
+        failed: 1,
+        ignored: 1,
+        allowed_fail: 0,
+        filtered_out: 0,
+        measured: 0,
+        metrics: MetricMap::new(),
+        failures: Vec::new(),
+        options: Options::new(),
+        not_failures: Vec::new(),
+        time_failures: Vec::new(),
+        baseline: None,
+        bench_results: Baseline::new(),
+        suite_errors: Vec::new(),
+    };
+
+    let failed_msg = TrFailedMsg("a < b".to_owned());
+    let exec_time = test_exec_time(1500);
+    out.write_run_start(3, None).unwrap();
+    out.write_result(&desc("tests::passes"), &TrOk, None, b"", &st).unwrap();
+    out.write_result(&desc("tests::fails"), &failed_msg, Some(&exec_time), b"out & about", &st)
+        .unwrap();
+    out.write_result(&desc("skipped"), &TrIgnored, None, b"", &st).unwrap();
+    assert!(!out.write_run_finish(&st).unwrap());
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+
+    assert!(s.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n"));
+    assert!(s.contains(r#"failures="1" tests="3" skipped="1" time="1.5">"#));
+    assert!(s.contains(r#"<testcase classname="tests" name="passes" time="0"/>"#));
+    assert!(s.contains(
+        "<testcase classname=\"tests\" name=\"fails\" time=\"1.5\">\n\
+         <failure type=\"assert\" message=\"a &lt; b\"/>\n\
+         <system-out>out &amp; about</system-out>\n\
+         </testcase>"
+    ));
+    assert!(s.contains(
+        "<testcase classname=\"crate\" name=\"skipped\" time=\"0\">\n<skipped/>\n</testcase>"
+    ));
+    assert!(s.ends_with("</testsuite>\n</testsuites>\n"));
+}
+
+#[cfg(not(bootstrap))]
+fn ignored_test_desc_with_location() -> TestDesc {
+    TestDesc {
+        name: StaticTestName("tests::skipped"),
+        ignore: true,
+        ignore_message: Some("not \"yet\""),
+        location: Some(TestLocation { file: "src/lib.rs", line: 12, col: 4 }),
+        should_panic: ShouldPanic::No,
+        allow_fail: false,
+        timeout: None,
+        test_type: TestType::UnitTest,
+    }
+}
+
+#[test]
+#[cfg(not(bootstrap))]
+fn json_formatter_reports_location_and_ignore_message() {
+    let desc = ignored_test_desc_with_location();
+    let st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+
+    let mut out = JsonFormatter::new(OutputLocation::Raw(Vec::new()));
+    out.write_test_discovered(&desc, "test").unwrap();
+    out.write_result(&desc, &TrIgnored, None, b"", &st).unwrap();
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    let location = r#""source_file": "src/lib.rs", "line": 12, "column": 4"#;
+    let expected = format!(
+        "{{ \"type\": \"test\", \"event\": \"discovered\", \"name\": \"tests::skipped\", \
+         \"ignore\": true, \"message\": \"not \\\"yet\\\"\", {loc} }}\n\
+         {{ \"type\": \"test\", \"name\": \"tests::skipped\", \"event\": \"ignored\", {loc}, \
+         \"message\": \"not \\\"yet\\\"\" }}\n",
+        loc = location
+    );
+    assert_eq!(s, expected);
+}
+
+#[test]
+#[cfg(not(bootstrap))]
+fn pretty_formatter_prints_ignore_message() {
+    let desc = ignored_test_desc_with_location();
+    let st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+
+    let mut out = PrettyFormatter::new(OutputLocation::Raw(Vec::new()), false, 10, false, None);
+    out.write_result(&desc, &TrIgnored, None, b"", &st).unwrap();
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert_eq!(s, "ignored, not \"yet\"\n");
+}
+
+#[test]
+#[cfg(not(bootstrap))]
+fn list_line_includes_location_and_ignore_message() {
+    let mut desc = ignored_test_desc_with_location();
+    assert_eq!(
+        console::list_line(&desc, "test"),
+        "tests::skipped: test at src/lib.rs:12:4, ignored, not \"yet\""
+    );
+
+    desc.ignore_message = None;
+    assert_eq!(
+        console::list_line(&desc, "test"),
+        "tests::skipped: test at src/lib.rs:12:4, ignored"
+    );
+
+    desc.ignore = false;
+    desc.location = None;
+    assert_eq!(console::list_line(&desc, "benchmark"), "tests::skipped: benchmark");
+}
+
+fn bench_samples(samples: &[f64]) -> BenchSamples {
+    BenchSamples { ns_iter_summ: stats::Summary::new(samples), mb_s: 0 }
+}
+
+#[test]
+fn baseline_save_and_load() {
+    let name = std::env::temp_dir().join(format!("libtest-baseline-{}", std::process::id()));
+    let name = name.to_str().unwrap();
+
+    let mut first = Baseline::new();
+    first.insert("bench_a", bench_samples(&[1.5, 2.0, 2.5]));
+    first.insert("bench b\twith tab", bench_samples(&[100.0, 110.0, 105.0]));
+    first.save(name).unwrap();
+
+    // Saving again only replaces the benchmarks that ran.
+    let mut second = Baseline::new();
+    second.insert("bench_a", bench_samples(&[3.0, 3.5, 4.0]));
+    second.save(name).unwrap();
+
+    let loaded = Baseline::load(name).unwrap();
+    std::fs::remove_file(Baseline::path(name)).unwrap();
+
+    assert_eq!(loaded.get("bench_a"), second.get("bench_a"));
+    assert_eq!(loaded.get("bench b\twith tab"), first.get("bench b\twith tab"));
+    assert_eq!(loaded.get("bench_c"), None);
+}
+
+#[test]
+fn bench_comparison_detects_significant_changes() {
+    let old = stats::Summary::new(&[100.0, 101.0, 99.0, 100.0, 102.0, 98.0]);
+
+    let same = BenchComparison::new(&old, &old);
+    assert_eq!(same.change, BenchChange::Unchanged);
+    assert_eq!(same.change_pct, 0.0);
+
+    let noisy = stats::Summary::new(&[80.0, 125.0, 95.0, 100.0, 130.0, 70.0]);
+    assert_eq!(BenchComparison::new(&old, &noisy).change, BenchChange::Unchanged);
+
+    let slower = stats::Summary::new(&[150.0, 151.0, 149.0, 150.0, 152.0, 148.0]);
+    let regressed = BenchComparison::new(&old, &slower);
+    assert_eq!(regressed.change, BenchChange::Regressed);
+    assert_eq!(regressed.change_pct, 50.0);
+    assert_eq!(regressed.to_string(), "change: +50.00% (regressed)");
+
+    let improved = BenchComparison::new(&slower, &old);
+    assert_eq!(improved.change, BenchChange::Improved);
+
+    // The medians are compared, so outliers that move the mean don't count.
+    let outlier = stats::Summary::new(&[100.0, 101.0, 99.0, 100.0, 102.0, 1000.0]);
+    assert_eq!(BenchComparison::new(&old, &outlier).change, BenchChange::Unchanged);
+}
+
+#[test]
+fn t_critical_values() {
+    // Quantiles of Student's t-distribution for a two-sided test at 95%.
+    for &(df, t) in &[(10.0, 2.228), (49.0, 2.010), (98.0, 1.984), (1000.0, 1.962)] {
+        assert!((t_critical(df) - t).abs() < 0.001, "df = {}", df);
+    }
+}
+
+#[test]
+fn missing_baseline_is_not_an_error() {
+    let mut opts = TestOpts::new();
+    opts.baseline = Some("libtest-missing-baseline".to_string());
+    let st = console::ConsoleTestState::new(&opts).unwrap();
+    assert!(st.baseline.is_none());
+}
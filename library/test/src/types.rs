@@ -2,6 +2,7 @@
 
 use std::borrow::Cow;
 use std::fmt;
+use std::time::Duration;
 
 use super::bench::Bencher;
 use super::options;
@@ -112,18 +113,74 @@ impl fmt::Debug for TestFn {
     }
 }
 
+/// Where a test is defined in the source code. Lines and columns are 1-based.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TestLocation {
+    pub file: &'static str,
+    pub line: usize,
+    pub col: usize,
+}
+
+impl fmt::Display for TestLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.col)
+    }
+}
+
 // The definition of a single test. A test runner will run a list of
 // these.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct TestDesc {
     pub name: TestName,
     pub ignore: bool,
+    /// The reason given with `#[ignore = "reason"]`.
+    #[cfg(not(bootstrap))]
+    pub ignore_message: Option<&'static str>,
+    /// Where the test is defined, unknown for tests not generated by `#[test]`.
+    #[cfg(not(bootstrap))]
+    pub location: Option<TestLocation>,
     pub should_panic: options::ShouldPanic,
     pub allow_fail: bool,
+    /// Timeout in seconds set with the `#[test_timeout]` attribute.
+    #[cfg(not(bootstrap))]
+    pub timeout: Option<u64>,
     pub test_type: TestType,
 }
 
 impl TestDesc {
+    /// Returns the timeout set with the `#[test_timeout]` attribute, if any.
+    #[cfg(not(bootstrap))]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    #[cfg(bootstrap)]
+    pub fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the reason given with `#[ignore = "reason"]`, if any.
+    #[cfg(not(bootstrap))]
+    pub fn ignore_message(&self) -> Option<&'static str> {
+        self.ignore_message
+    }
+
+    #[cfg(bootstrap)]
+    pub fn ignore_message(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns where the test is defined, if known.
+    #[cfg(not(bootstrap))]
+    pub fn location(&self) -> Option<TestLocation> {
+        self.location
+    }
+
+    #[cfg(bootstrap)]
+    pub fn location(&self) -> Option<TestLocation> {
+        None
+    }
+
     pub fn padded_name(&self, column_count: usize, align: NamePadding) -> String {
         let mut name = String::from(self.name.as_slice());
         let fill = column_count.saturating_sub(name.len());
@@ -143,3 +200,14 @@ pub struct TestDescAndFn {
     pub desc: TestDesc,
     pub testfn: TestFn,
 }
+
+/// Functions to run around the tests, marked with `#[test_setup]` and `#[test_teardown]`.
+/// `setup` and `teardown` run once before and after all tests, `setup_each` and
+/// `teardown_each` before and after every test.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TestFixtures {
+    pub setup: &'static [fn()],
+    pub teardown: &'static [fn()],
+    pub setup_each: &'static [fn()],
+    pub teardown_each: &'static [fn()],
+}
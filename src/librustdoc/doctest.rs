@@ -773,6 +773,12 @@ impl Tester for Collector {
                 // compiler failures are test failures
                 should_panic: testing::ShouldPanic::No,
                 allow_fail: config.allow_fail,
+                #[cfg(not(bootstrap))]
+                timeout: None,
+                #[cfg(not(bootstrap))]
+                ignore_message: None,
+                #[cfg(not(bootstrap))]
+                location: None,
                 test_type: testing::TestType::DocTest,
             },
             testfn: testing::DynTestFn(box move || {
@@ -0,0 +1,15 @@
+// Test that arrays are captured entirely, even if only one element is used.
+
+#![feature(capture_disjoint_fields)]
+//~^ WARNING: the feature `capture_disjoint_fields` is incomplete
+#![feature(rustc_attrs, stmt_expr_attributes)]
+
+fn main() {
+    let mut m = [1, 2, 3, 4, 5];
+
+    let mut c = #[rustc_capture_analysis] || { m[0] += 10; };
+    //~^ ERROR: First Pass analysis includes:
+    //~| ERROR: Min Capture analysis includes:
+
+    c();
+}
@@ -0,0 +1,21 @@
+// Test that when both a place and one of its fields are used, only the place is captured,
+// with the strongest of the two capture kinds.
+
+#![feature(capture_disjoint_fields)]
+//~^ WARNING: the feature `capture_disjoint_fields` is incomplete
+#![feature(rustc_attrs, stmt_expr_attributes)]
+
+struct Point {
+    x: String,
+    y: String,
+}
+
+fn main() {
+    let p = Point { x: String::new(), y: String::new() };
+
+    let c = #[rustc_capture_analysis] || { let _x = &p.x; drop(p); };
+    //~^ ERROR: First Pass analysis includes:
+    //~| ERROR: Min Capture analysis includes:
+
+    c();
+}
@@ -0,0 +1,21 @@
+// Test that with `capture_disjoint_fields` a closure captures the fields of a struct
+// it uses, instead of the whole struct.
+
+#![feature(capture_disjoint_fields)]
+//~^ WARNING: the feature `capture_disjoint_fields` is incomplete
+#![feature(rustc_attrs, stmt_expr_attributes)]
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    let mut p = Point { x: 10, y: 10 };
+
+    let mut c = #[rustc_capture_analysis] || { let _x = &p.x; p.y += 1; };
+    //~^ ERROR: First Pass analysis includes:
+    //~| ERROR: Min Capture analysis includes:
+
+    c();
+}
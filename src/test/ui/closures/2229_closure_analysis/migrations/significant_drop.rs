@@ -0,0 +1,62 @@
+#![deny(disjoint_capture_drop_reorder)]
+//~^ NOTE: the lint level is defined here
+
+// Test that closures whose drop order would change with `capture_disjoint_fields`
+// are reported, and that the suggestion captures the affected variables entirely.
+
+#[derive(Debug)]
+struct Foo(i32);
+impl Drop for Foo {
+    fn drop(&mut self) {
+        println!("{:?} dropped", self.0);
+    }
+}
+
+struct ContainsDropField(Foo, Foo);
+
+// `t` is moved into the closure, but only `t.0` would be with `capture_disjoint_fields`.
+fn test_one_variable() {
+    let t = ContainsDropField(Foo(10), Foo(20));
+
+    let c = || { let _t = t.0; };
+    //~^ ERROR: drop order affected for closure because of `capture_disjoint_fields`
+    //~| HELP: add a dummy let to cause `t` to be fully captured
+
+    c();
+}
+
+fn test_two_variables() {
+    let t = ContainsDropField(Foo(10), Foo(20));
+    let t1 = ContainsDropField(Foo(30), Foo(40));
+
+    let c = || { let _t = t.0; let _t1 = t1.1; };
+    //~^ ERROR: drop order affected for closure because of `capture_disjoint_fields`
+    //~| HELP: add a dummy let to cause `t`, `t1` to be fully captured
+
+    c();
+}
+
+// `t` is captured entirely either way.
+fn test_entire_variable_used() {
+    let t = ContainsDropField(Foo(10), Foo(20));
+
+    let c = || { let _t = &t.0; let _t = t; };
+
+    c();
+}
+
+// `t` doesn't need to be dropped, so the drop order doesn't matter.
+fn test_no_drop() {
+    let t = (String::new().len(), 10);
+
+    let c = move || { let _t = t.0; };
+
+    c();
+}
+
+fn main() {
+    test_one_variable();
+    test_two_variables();
+    test_entire_variable_used();
+    test_no_drop();
+}
@@ -0,0 +1,35 @@
+// run-pass
+
+// Test that a closure that only uses some fields of a struct doesn't borrow
+// the other fields.
+
+#![feature(capture_disjoint_fields)]
+//~^ WARNING: the feature `capture_disjoint_fields` is incomplete
+
+struct S {
+    a: Vec<i32>,
+    b: String,
+    c: (i32, String),
+}
+
+fn main() {
+    let mut s = S { a: vec![], b: String::from("hello"), c: (0, String::from("world")) };
+
+    let mut c = || {
+        s.a.push(1);
+        s.c.0 += 1;
+    };
+    // `s.b` and `s.c.1` aren't captured, so they can still be used while `c` is live.
+    let b = &mut s.b;
+    b.push('!');
+    assert_eq!(s.c.1.len(), 5);
+    c();
+    c();
+
+    // Moving a field out only moves that field.
+    let d = move || s.c.1;
+    assert_eq!(s.a, vec![1, 1]);
+    assert_eq!(s.b, "hello!");
+    assert_eq!(d(), "world");
+    assert_eq!(s.c.0, 2);
+}
@@ -3,5 +3,5 @@ mod underscore;
 
 fn main() {
     underscore!();
-    //~^ ERROR expected expression, found reserved identifier `_`
+    //~^ ERROR in expressions, `_` can only be used on the left-hand side of an assignment
 }
@@ -0,0 +1,28 @@
+// run-pass
+
+#![feature(derive_default_enum)]
+
+// nb: does not impl Default
+#[derive(Debug, PartialEq)]
+struct NotDefault;
+
+#[derive(Debug, Default, PartialEq)]
+enum Foo {
+    #[default]
+    Alpha,
+    #[allow(dead_code)]
+    Beta(NotDefault),
+}
+
+#[derive(Debug, Default, PartialEq)]
+enum MyOption<T> {
+    #[default]
+    None,
+    #[allow(dead_code)]
+    Some(T),
+}
+
+fn main() {
+    assert_eq!(Foo::default(), Foo::Alpha);
+    assert_eq!(MyOption::<i32>::default(), MyOption::None);
+}
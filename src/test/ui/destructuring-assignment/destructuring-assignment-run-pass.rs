@@ -0,0 +1,63 @@
+// run-pass
+
+#![feature(destructuring_assignment)]
+
+struct Struct<S, T> {
+    a: S,
+    b: T,
+}
+
+struct TupleStruct<S, T>(S, T);
+
+enum Enum<S, T> {
+    SingleVariant(S, T),
+}
+
+fn main() {
+    let (mut a, mut b);
+
+    // Tuples, including nesting and swapping.
+    (a, b) = (0, 1);
+    assert_eq!((a, b), (0, 1));
+    (a, b) = (b, a);
+    assert_eq!((a, b), (1, 0));
+    ((a, b), _) = ((2, 3), 4);
+    assert_eq!((a, b), (2, 3));
+    (a, .., b) = (4, 5, 6, 7);
+    assert_eq!((a, b), (4, 7));
+    (..) = (8, 9);
+
+    // Slices.
+    [a, .., b] = [10, 11, 12, 13];
+    assert_eq!((a, b), (10, 13));
+    [_, a, _] = [14, 15, 16];
+    assert_eq!(a, 15);
+
+    // Structs.
+    Struct { a, b } = Struct { a: 17, b: 18 };
+    assert_eq!((a, b), (17, 18));
+    Struct { a: b, b: a } = Struct { a: 19, b: 20 };
+    assert_eq!((a, b), (20, 19));
+    Struct { a, .. } = Struct { a: 21, b: 22 };
+    assert_eq!(a, 21);
+
+    // Tuple structs and variants.
+    TupleStruct(a, b) = TupleStruct(23, 24);
+    assert_eq!((a, b), (23, 24));
+    Enum::SingleVariant(b, _) = Enum::SingleVariant(25, 26);
+    assert_eq!(b, 25);
+
+    // Places other than locals.
+    let mut v = [0; 2];
+    let mut t = (0, 0);
+    (v[0], t.1) = (27, 28);
+    assert_eq!((v[0], t.1), (27, 28));
+
+    // Assignments evaluate to `()`.
+    let unit: () = (a, b) = (29, 30);
+    assert_eq!(unit, ());
+    assert_eq!((a, b), (29, 30));
+
+    // `_` on its own discards the value.
+    _ = 31;
+}
@@ -0,0 +1,13 @@
+#![feature(destructuring_assignment)]
+
+struct S { x: u8 }
+
+fn f() -> u8 { 0 }
+
+fn main() {
+    let (mut a, b) = (0u8, 0u8);
+    (a, 1) = (2, 3); //~ ERROR invalid left-hand side of assignment
+    (a, b + 1) = (2, 3); //~ ERROR invalid left-hand side of assignment
+    S { x: f() } = S { x: 4 }; //~ ERROR invalid left-hand side of assignment
+    let _ = (a, b);
+}
@@ -0,0 +1,20 @@
+#![feature(destructuring_assignment)]
+
+struct Struct<S, T> {
+    a: S,
+    b: T,
+}
+
+fn main() {
+    let (mut a, mut b);
+    let base = Struct { a: 0, b: 1 };
+    Struct { a, ..base } = Struct { a: 2, b: 3 };
+    //~^ ERROR functional record updates are not allowed in destructuring assignments
+    (a, .., b, ..) = (0, 1);
+    //~^ ERROR `..` can only be used once per tuple pattern
+    [a, .., b, ..] = [0, 1];
+    //~^ ERROR `..` can only be used once per slice pattern
+    let _ = Struct { a, .. };
+    //~^ ERROR base expression required after `..`
+    let _ = (a, b);
+}
@@ -0,0 +1,7 @@
+#[derive(Default)] //~ ERROR E0665
+enum Foo {
+    #[default]
+    Alpha,
+}
+
+fn main() {}
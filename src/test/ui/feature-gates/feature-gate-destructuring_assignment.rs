@@ -0,0 +1,5 @@
+fn main() {
+    let (a, b);
+    (a, b) = (1, 2); //~ ERROR destructuring assignments are unstable
+    let _ = (a, b);
+}
@@ -0,0 +1,9 @@
+// check that #[test_setup] and #[test_teardown] are feature-gated
+
+#[test_setup] //~ ERROR the `#[test_setup]` attribute is an experimental feature
+fn setup() {}
+
+#[test_teardown(each)] //~ ERROR the `#[test_teardown]` attribute is an experimental feature
+fn teardown() {}
+
+fn main() {}
@@ -0,0 +1,13 @@
+// run-pass
+
+#![allow(incomplete_features)]
+#![feature(inline_const)]
+
+fn foo<T>() -> usize {
+    const { std::mem::size_of::<T>() }
+}
+
+fn main() {
+    assert_eq!(foo::<u8>(), 1);
+    assert_eq!(foo::<u64>(), 8);
+}
@@ -0,0 +1,15 @@
+#![allow(incomplete_features)]
+#![feature(inline_const)]
+
+fn foo<T>(x: usize) -> bool {
+    match x {
+        const { std::mem::size_of::<T>() } => true,
+        //~^ ERROR constant pattern depends on a generic parameter
+        //~| ERROR constant pattern depends on a generic parameter
+        _ => false,
+    }
+}
+
+fn main() {
+    foo::<u8>(1);
+}
@@ -0,0 +1,28 @@
+// check-pass
+// Checks that the attributes of a `let...else` apply to its bindings, and that the desugaring
+// does not cause spurious `unused_mut` or `unused_variables` lints.
+
+#![feature(let_else, or_patterns)]
+#![deny(unused_mut, unused_variables)]
+
+#[allow(dead_code)]
+enum Pair {
+    Both(u32, u32),
+    Left(u32),
+    Neither,
+}
+
+fn main() {
+    #[allow(unused_variables)]
+    let Some(x) = Some(1) else { return };
+
+    let Some(mut y) = Some(1) else { return };
+    y += 1;
+
+    let (Pair::Both(a, _) | Pair::Left(a)) = Pair::Left(y) else { return };
+
+    let opt = Some(String::new());
+    let Some(ref s) = opt else { return };
+
+    let _ = (a, s);
+}
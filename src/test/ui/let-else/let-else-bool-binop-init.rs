@@ -0,0 +1,8 @@
+#![feature(let_else)]
+
+fn main() {
+    let true = true && false else { return }; //~ ERROR a `&&` expression cannot be directly assigned in `let...else`
+    let true = true || false else { return }; //~ ERROR a `||` expression cannot be directly assigned in `let...else`
+    let Some(x) = match Some(1) { x => x } else { return }; //~ ERROR right curly brace `}` before `else`
+    let _ = x;
+}
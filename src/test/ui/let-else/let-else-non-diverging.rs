@@ -0,0 +1,11 @@
+#![feature(let_else)]
+
+fn main() {
+    let Some(x) = Some(1) else { }; //~ ERROR does not diverge
+    let Some(y) = Some(2) else { //~ ERROR does not diverge
+        if x == 1 {
+            return;
+        }
+    };
+    assert_eq!(x + y, 3);
+}
@@ -0,0 +1,38 @@
+// run-pass
+
+#![feature(let_else)]
+
+enum Shape {
+    Circle(u32),
+    Square(u32),
+}
+
+fn radius(shape: &Shape) -> Option<u32> {
+    let Shape::Circle(r) = shape else { return None };
+    Some(*r)
+}
+
+fn sum_firsts(pairs: &[(u32, Option<u32>)]) -> u32 {
+    let mut sum = 0;
+    for &(a, b) in pairs {
+        let Some(b) = b else { continue };
+        sum += a + b;
+    }
+    sum
+}
+
+fn typed() -> u64 {
+    let Some(x): Option<u64> = Some(7) else { panic!() };
+    x
+}
+
+fn main() {
+    assert_eq!(radius(&Shape::Circle(3)), Some(3));
+    assert_eq!(radius(&Shape::Square(3)), None);
+    assert_eq!(sum_firsts(&[(1, Some(2)), (3, None), (4, Some(5))]), 12);
+    assert_eq!(typed(), 7);
+
+    let v = vec![1, 2, 3];
+    let [first, .., last] = v[..] else { unreachable!() };
+    assert_eq!((first, last), (1, 3));
+}
@@ -0,0 +1,41 @@
+// run-pass
+// Checks that the temporaries of the initializer of a `let...else` are dropped at the end of the
+// statement, as they are for a plain `let`, rather than at the end of the enclosing block.
+
+#![feature(let_else)]
+
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+struct Droppy<'a> {
+    log: &'a RefCell<Vec<&'static str>>,
+    name: &'static str,
+}
+
+impl Droppy<'_> {
+    fn get(&self) -> Option<u32> {
+        Some(1)
+    }
+}
+
+impl Drop for Droppy<'_> {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(self.name);
+    }
+}
+
+fn main() {
+    let log = RefCell::new(vec![]);
+    {
+        let Some(x) = Droppy { log: &log, name: "temporary" }.get() else { panic!() };
+        log.borrow_mut().push("next statement");
+        assert_eq!(x, 1);
+        let _local = Droppy { log: &log, name: "local" };
+    }
+    assert_eq!(*log.borrow(), ["temporary", "next statement", "local"]);
+
+    let mutex = Mutex::new(Some(2));
+    let Some(y) = *mutex.lock().unwrap() else { panic!() };
+    assert!(mutex.try_lock().is_ok());
+    assert_eq!(y, 2);
+}
@@ -0,0 +1,50 @@
+// compile-flags: --test
+// run-flags: --test-threads=1
+// run-pass
+// check-run-results
+
+#![cfg(test)]
+#![feature(test_fixtures)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SETUP: AtomicUsize = AtomicUsize::new(0);
+static SETUP_EACH: AtomicUsize = AtomicUsize::new(0);
+
+mod outer {
+    use super::*;
+
+    #[test_setup]
+    fn setup() {
+        SETUP.fetch_add(1, Ordering::SeqCst);
+    }
+
+    mod inner {
+        use super::super::*;
+
+        // Fixtures don't need to be public, and may share their names with the
+        // fixtures of other modules.
+        #[test_setup]
+        fn setup() {
+            SETUP.fetch_add(10, Ordering::SeqCst);
+        }
+
+        #[test_setup(each)]
+        fn setup_each() {
+            SETUP_EACH.fetch_add(1, Ordering::SeqCst);
+        }
+
+        #[test]
+        fn inner_test() {
+            assert_eq!(SETUP.load(Ordering::SeqCst), 11);
+            assert_eq!(SETUP_EACH.load(Ordering::SeqCst), 1);
+        }
+    }
+}
+
+// The fixtures of nested modules run around all tests of the crate.
+#[test]
+fn top_level_test() {
+    assert_eq!(SETUP.load(Ordering::SeqCst), 11);
+    assert_eq!(SETUP_EACH.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,24 @@
+// no-prefer-dynamic
+// compile-flags: --test -Cpanic=abort -Zpanic_abort_tests
+// run-flags: --test-threads=1
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+
+// ignore-wasm no panic or subprocess support
+// ignore-emscripten no panic or subprocess support
+// ignore-sgx no subprocess support
+
+#![cfg(test)]
+#![feature(test_fixtures)]
+
+#[test_setup]
+fn setup() {
+    println!("connecting");
+    panic!("no database");
+}
+
+#[test]
+fn it_works() {
+    assert_eq!(1 + 1, 2);
+}
@@ -0,0 +1,46 @@
+// compile-flags: --test
+// run-flags: --test-threads=1
+// run-pass
+// check-run-results
+
+#![cfg(test)]
+#![feature(test_fixtures)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SETUP: AtomicUsize = AtomicUsize::new(0);
+static SETUP_EACH: AtomicUsize = AtomicUsize::new(0);
+static TEARDOWN_EACH: AtomicUsize = AtomicUsize::new(0);
+
+#[test_setup]
+fn setup() {
+    SETUP.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test_teardown]
+fn teardown() {
+    assert_eq!(SETUP.load(Ordering::SeqCst), 1);
+    assert_eq!(TEARDOWN_EACH.load(Ordering::SeqCst), 2);
+}
+
+#[test_setup(each)]
+fn setup_each() {
+    SETUP_EACH.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test_teardown(each)]
+fn teardown_each() {
+    TEARDOWN_EACH.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn first() {
+    assert_eq!(SETUP.load(Ordering::SeqCst), 1);
+    assert_eq!(SETUP_EACH.load(Ordering::SeqCst), TEARDOWN_EACH.load(Ordering::SeqCst) + 1);
+}
+
+#[test]
+fn second() {
+    assert_eq!(SETUP.load(Ordering::SeqCst), 1);
+    assert_eq!(SETUP_EACH.load(Ordering::SeqCst), TEARDOWN_EACH.load(Ordering::SeqCst) + 1);
+}
@@ -0,0 +1,23 @@
+// run-pass
+// compile-flags: --test
+// run-flags: --test-threads=1
+// check-run-results
+
+#![cfg(test)]
+
+#[test]
+#[ignore]
+fn ignored() {
+    panic!("not run");
+}
+
+#[test]
+#[ignore = "requires network access"]
+fn ignored_with_reason() {
+    panic!("not run");
+}
+
+#[test]
+fn it_works() {
+    assert_eq!(1 + 1, 2);
+}
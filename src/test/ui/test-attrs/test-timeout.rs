@@ -0,0 +1,37 @@
+// no-prefer-dynamic
+// compile-flags: --test
+// run-flags: --test-threads=1
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+
+// ignore-wasm no subprocess support
+// ignore-emscripten no subprocess support
+// ignore-sgx no subprocess support
+
+#![cfg(test)]
+#![feature(test_timeout)]
+
+use std::thread;
+use std::time::Duration;
+
+#[test]
+#[test_timeout = "60"]
+fn it_works() {
+    assert_eq!(1 + 1, 2);
+}
+
+#[test]
+#[test_timeout = "60"]
+#[should_panic]
+fn it_panics() {
+    assert_eq!(1 + 1, 4);
+}
+
+#[test]
+#[test_timeout = "1"]
+fn it_hangs() {
+    loop {
+        thread::sleep(Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,33 @@
+// run-pass
+// ignore-emscripten no threads support
+
+// Threads inherit the output capture of the thread spawning them, including
+// threads spawned by threads that inherited it.
+
+#![feature(internal_output_capture)]
+
+use std::io;
+use std::str;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    let data = Arc::new(Mutex::new(Vec::new()));
+    io::set_output_capture(Some(data.clone()));
+
+    println!("from main");
+    thread::spawn(|| {
+        println!("from child");
+        thread::spawn(|| eprintln!("from grandchild")).join().unwrap();
+    })
+    .join()
+    .unwrap();
+
+    let captured = io::set_output_capture(None).unwrap();
+    assert!(Arc::ptr_eq(&captured, &data));
+    println!("not captured");
+
+    let output = data.lock().unwrap();
+    let output = str::from_utf8(&output).unwrap();
+    assert_eq!(output, "from main\nfrom child\nfrom grandchild\n");
+}
@@ -73,6 +73,7 @@ fn identify_some_pure_patterns(expr: &Expr<'_>) -> bool {
         | ExprKind::Ret(..)
         | ExprKind::InlineAsm(..)
         | ExprKind::LlvmInlineAsm(..)
+        | ExprKind::ConstBlock(..)
         | ExprKind::Repeat(..)
         | ExprKind::Yield(..)
         | ExprKind::Err => false,
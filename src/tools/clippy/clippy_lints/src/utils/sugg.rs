@@ -103,6 +103,7 @@ impl<'a> Sugg<'a> {
             hir::ExprKind::Continue(..)
             | hir::ExprKind::Yield(..)
             | hir::ExprKind::Array(..)
+            | hir::ExprKind::ConstBlock(..)
             | hir::ExprKind::Block(..)
             | hir::ExprKind::Break(..)
             | hir::ExprKind::Call(..)
@@ -170,8 +171,10 @@ impl<'a> Sugg<'a> {
             | ast::ExprKind::TryBlock(..)
             | ast::ExprKind::Tup(..)
             | ast::ExprKind::Array(..)
+            | ast::ExprKind::ConstBlock(..)
             | ast::ExprKind::While(..)
             | ast::ExprKind::Await(..)
+            | ast::ExprKind::Underscore
             | ast::ExprKind::Err => Sugg::NonParen(snippet),
             ast::ExprKind::Range(.., RangeLimits::HalfOpen) => Sugg::BinOp(AssocOp::DotDot, snippet),
             ast::ExprKind::Range(.., RangeLimits::Closed) => Sugg::BinOp(AssocOp::DotDotEq, snippet),
@@ -425,8 +425,12 @@ fn configure_lldb(config: &Config) -> Option<Config> {
 pub fn test_opts(config: &Config) -> test::TestOpts {
     test::TestOpts {
         exclude_should_panic: false,
-        filter: config.filter.clone(),
-        filter_exact: config.filter_exact,
+        filters: config.filter.iter().cloned().collect(),
+        filter_mode: if config.filter_exact {
+            test::FilterMode::Exact
+        } else {
+            test::FilterMode::Substring
+        },
         run_ignored: if config.run_ignored { test::RunIgnored::Yes } else { test::RunIgnored::No },
         format: if config.quiet { test::OutputFormat::Terse } else { test::OutputFormat::Pretty },
         logfile: config.logfile.clone(),
@@ -440,6 +444,14 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         test_threads: None,
         skip: vec![],
         list: false,
+        shuffle: false,
+        shuffle_seed: None,
+        partition: None,
+        test_timeout: None,
+        save_baseline: None,
+        baseline: None,
+        failed_list: None,
+        rerun_failed: None,
         options: test::Options::new(),
         time_options: None,
         force_run_in_process: false,
@@ -603,6 +615,12 @@ fn make_test(config: &Config, testpaths: &TestPaths, inputs: &Stamp) -> Vec<test
                     ignore,
                     should_panic,
                     allow_fail: false,
+                    #[cfg(not(bootstrap))]
+                    timeout: None,
+                    #[cfg(not(bootstrap))]
+                    ignore_message: None,
+                    #[cfg(not(bootstrap))]
+                    location: None,
                     test_type: test::TestType::Unknown,
                 },
                 testfn: make_test_closure(config, testpaths, revision),